@@ -2,6 +2,7 @@
 extern crate simple_redis;
 extern crate test;
 
+use std::time::Duration;
 use test::Bencher;
 
 #[bench]
@@ -20,3 +21,23 @@ fn set_get_del(bencher: &mut Bencher) {
         client.del("bnch_set_get").unwrap();
     });
 }
+
+#[bench]
+fn set_get_del_health_check(bencher: &mut Bencher) {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    // verify the connection before every command
+    client.set_health_check_interval(Some(Duration::from_secs(0)));
+
+    client.set("bnch_set_get_hc", "my_value").unwrap();
+    client.get_string("bnch_set_get_hc").unwrap();
+    client.del("bnch_set_get_hc").unwrap();
+
+    assert!(client.is_connection_open());
+
+    bencher.iter(|| {
+        client.set("bnch_set_get_hc", "my_value").unwrap();
+        client.get_string("bnch_set_get_hc").unwrap();
+        client.del("bnch_set_get_hc").unwrap();
+    });
+}
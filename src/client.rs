@@ -7,36 +7,143 @@
 #[path = "./client_test.rs"]
 mod client_test;
 
+use cluster;
 use connection;
+use pipeline;
 use redis;
+use sentinel;
+use socket;
+use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
+use std::time::{Duration, Instant};
 use subscriber;
+#[cfg(feature = "tls")]
+use tls;
+use transaction;
+use types;
 use types::{
     ErrorInfo, RedisBoolResult, RedisEmptyResult, RedisError, RedisMessageResult, RedisResult,
-    RedisStringResult,
+    RedisStringResult, RetryPolicy,
 };
 
 /// The redis client which enables to invoke redis operations.
 pub struct Client {
-    /// Internal redis client
-    client: redis::Client,
+    /// The redis server connection info
+    connection_info: redis::ConnectionInfo,
     /// Holds the current redis connection
     connection: connection::Connection,
+    /// Holds the replica connection used for read only commands (sentinel clients only)
+    replica_connection: Option<connection::Connection>,
+    /// Routes the commands to the cluster nodes (cluster clients only)
+    cluster: Option<cluster::ClusterConnections>,
     /// Internal subscriber
     subscriber: subscriber::Subscriber,
 }
 
+/// Sets the connection read and write timeouts to the time left until the provided deadline (if set).
+fn apply_deadline(connection: &socket::Connection, deadline: Option<Instant>) -> RedisEmptyResult {
+    if let Some(deadline) = deadline {
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(create_command_timeout_error());
+        }
+
+        let remaining = Some(deadline - now);
+        if let Err(error) = connection
+            .set_read_timeout(remaining)
+            .and_then(|_| connection.set_write_timeout(remaining))
+        {
+            return Err(RedisError {
+                info: ErrorInfo::RedisError(error),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts the redis error to a command timeout error in case it is a timeout and a deadline was set.
+fn to_command_error(error: redis::RedisError, deadline: Option<Instant>) -> RedisError {
+    if deadline.is_some() && error.is_timeout() {
+        create_command_timeout_error()
+    } else {
+        RedisError {
+            info: ErrorInfo::RedisError(error),
+        }
+    }
+}
+
+fn create_command_timeout_error() -> RedisError {
+    RedisError {
+        info: ErrorInfo::TimeoutError("Timeout while waiting for redis response."),
+    }
+}
+
+fn run_packed_commands_on_connection(
+    connection: &socket::Connection,
+    packed: &[u8],
+    count: usize,
+    deadline: Option<Instant>,
+    results: &mut Vec<RedisResult<redis::Value>>,
+) -> bool {
+    if let Err(error) = apply_deadline(connection, deadline) {
+        results.push(Err(error));
+        return true;
+    }
+
+    match connection.send_packed_command(packed) {
+        Ok(_) => {
+            while results.len() < count {
+                if let Err(error) = apply_deadline(connection, deadline) {
+                    results.push(Err(error));
+                    return true;
+                }
+
+                match connection.recv_response() {
+                    Ok(value) => results.push(Ok(value)),
+                    Err(error) => {
+                        let connection_error = error.is_io_error() || connection.is_closed();
+
+                        results.push(Err(to_command_error(error, deadline)));
+
+                        // the rest of the responses can't be read
+                        if connection_error {
+                            return true;
+                        }
+                    }
+                }
+            }
+
+            false
+        }
+        Err(error) => {
+            let connection_error = error.is_io_error();
+
+            results.push(Err(to_command_error(error, deadline)));
+
+            connection_error
+        }
+    }
+}
+
 fn run_command_on_connection<T: redis::FromRedisValue>(
-    connection: &redis::Connection,
+    connection: &socket::Connection,
     command: &str,
-    args: Vec<&str>,
+    args: Vec<impl AsRef<[u8]>>,
 ) -> RedisResult<T> {
     let mut cmd = redis::cmd(command);
 
     for arg in args {
-        cmd.arg(arg);
+        cmd.arg(arg.as_ref());
     }
 
+    query_on_connection(connection, &cmd)
+}
+
+fn query_on_connection<T: redis::FromRedisValue>(
+    connection: &socket::Connection,
+    cmd: &redis::Cmd,
+) -> RedisResult<T> {
     let result: redis::RedisResult<T> = cmd.query(connection);
 
     match result {
@@ -47,12 +154,106 @@ fn run_command_on_connection<T: redis::FromRedisValue>(
     }
 }
 
+fn create_cluster_unsupported_error() -> RedisError {
+    RedisError {
+        info: ErrorInfo::Description(
+            "Pipelines and transactions are not supported in cluster mode.",
+        ),
+    }
+}
+
 impl Client {
     /// Returns true if the currently stored connection is valid, otherwise false.<br>
     /// There is no need to call this function as any redis operation invocation will
     /// ensure a valid connection is created.
     pub fn is_connection_open(self: &Client) -> bool {
-        self.connection.is_connection_open()
+        match self.cluster {
+            Some(ref cluster) => cluster.is_connection_open(),
+            None => self.connection.is_connection_open(),
+        }
+    }
+
+    /// Sets the interval after which an idle connection is verified (using PING) before being used.<br>
+    /// By default, connections are not verified before being used and broken connections are detected by the
+    /// command connection errors, in which case idempotent commands are invoked again on a new connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `health_check_interval` - The max idle time before the connection is verified (None to disable)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// client.set_health_check_interval(Some(std::time::Duration::from_secs(30)));
+    /// ```
+    pub fn set_health_check_interval(self: &mut Client, health_check_interval: Option<Duration>) {
+        self.connection
+            .set_health_check_interval(health_check_interval);
+        if let Some(ref mut replica_connection) = self.replica_connection {
+            replica_connection.set_health_check_interval(health_check_interval);
+        }
+        if let Some(ref mut cluster) = self.cluster {
+            cluster.set_health_check_interval(health_check_interval);
+        }
+    }
+
+    /// Returns a client wrapper which fails every command with a TimeoutError in case it is not completed within
+    /// the provided timeout.<br>
+    /// In case of a timeout, the connection is dropped and the next command will create a new connection.<br>
+    /// The timeout only applies for commands invoked via the returned wrapper.<br>
+    /// Pipelines created via the wrapper must be completed within the timeout, while transactions apply it on
+    /// each round trip (every WATCH, transaction run_command and the final EXEC) rather than on the entire
+    /// transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The max duration of each command
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// match client.with_timeout(std::time::Duration::from_secs(1)).get_string("my_key") {
+    ///     Ok(value) => println!("Read value from Redis: {}", value),
+    ///     Err(error) => println!("Unable to get value from Redis: {}", error),
+    /// };
+    /// ```
+    pub fn with_timeout(self: &mut Client, timeout: Duration) -> TimeoutClient {
+        self.set_command_timeout(Some(timeout));
+
+        TimeoutClient { client: self }
+    }
+
+    fn set_command_timeout(self: &mut Client, timeout: Option<Duration>) {
+        self.connection.set_command_timeout(timeout);
+        if let Some(ref mut replica_connection) = self.replica_connection {
+            replica_connection.set_command_timeout(timeout);
+        }
+        if let Some(ref mut cluster) = self.cluster {
+            cluster.set_command_timeout(timeout);
+        }
+    }
+
+    /// Returns true if a connection was created, without verifying it is still valid.
+    pub(crate) fn is_connection_created(self: &Client) -> bool {
+        self.connection.is_connection_created()
+    }
+
+    /// Returns the current connection generation (changes on every reconnection).
+    pub(crate) fn get_connection_generation(self: &Client) -> u64 {
+        match self.cluster {
+            Some(ref cluster) => cluster.get_generation(),
+            None => self.connection.get_generation(),
+        }
+    }
+
+    /// Reloads the cluster slot map (cluster clients only).
+    pub(crate) fn refresh_cluster_slots(self: &mut Client) -> RedisEmptyResult {
+        match self.cluster {
+            Some(ref mut cluster) => cluster.refresh_slots(),
+            None => Ok(()),
+        }
     }
 
     /// Closes the internal connection to redis.<br>
@@ -78,6 +279,11 @@ impl Client {
             result = Ok(());
         }
 
+        // QUIT is only sent to a single node, so the rest of the node connections are dropped
+        if let Some(ref mut cluster) = self.cluster {
+            cluster.close();
+        }
+
         if result.is_ok() {
             result = self.unsubscribe_all();
         }
@@ -110,10 +316,245 @@ impl Client {
         command: &str,
         args: Vec<&str>,
     ) -> RedisResult<T> {
-        match self.connection.get_redis_connection(&self.client) {
-            Ok(ref connection) => run_command_on_connection::<T>(connection, command, args),
+        self.run_blocking_command(command, args, None)
+    }
+
+    /// Invokes the requested command with the provided binary safe arguments and returns the operation
+    /// response.<br>
+    /// This is the same as the run_command function, however the arguments are sent as is, without any string
+    /// conversion.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The Redis command, for example: `SET`
+    /// * `args` - Vector of arguments (strings or bytes) for the given command
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// let value: &[u8] = &[0, 1, 2, 255];
+    /// match client.run_binary_command::<Vec<u8>>("ECHO", vec![value]) {
+    ///     Ok(output) => assert_eq!(output, value),
+    ///     _ => panic!("test error"),
+    /// }
+    /// ```
+    pub fn run_binary_command<T: redis::FromRedisValue>(
+        self: &mut Client,
+        command: &str,
+        args: Vec<impl AsRef<[u8]>>,
+    ) -> RedisResult<T> {
+        self.run_blocking_command(command, args, None)
+    }
+
+    /// Invokes the requested command which may block on the server side for up to the provided block time
+    /// (zero to block forever).<br>
+    /// The connection read timeout is extended by the block time, so the connection is not considered broken
+    /// while the server is blocking.
+    pub(crate) fn run_blocking_command<T: redis::FromRedisValue>(
+        self: &mut Client,
+        command: &str,
+        args: Vec<impl AsRef<[u8]>>,
+        block: Option<Duration>,
+    ) -> RedisResult<T> {
+        let slot = match self.cluster {
+            Some(_) => cluster::get_command_slot(command, &args)?,
+            None => None,
+        };
+
+        let idempotent = connection::is_idempotent_invocation(command, &args);
+
+        let mut cmd = redis::cmd(command);
+        for arg in args {
+            cmd.arg(arg.as_ref());
+        }
+
+        if let Some(ref mut cluster) = self.cluster {
+            return cluster.run(slot, idempotent, block, |connection| {
+                query_on_connection(connection, &cmd)
+            });
+        }
+
+        let connection = match self.replica_connection {
+            Some(ref mut replica_connection) if connection::is_read_only_command(command) => {
+                replica_connection
+            }
+            _ => &mut self.connection,
+        };
+
+        connection.run(&self.connection_info, idempotent, block, |connection| {
+            query_on_connection(connection, &cmd)
+        })
+    }
+
+    /// Returns a new pipeline which enables to send multiple commands to redis in a single round trip.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// let values: Vec<String> = client
+    ///     .pipeline()
+    ///     .echo("first")
+    ///     .cmd("ECHO", vec!["second"])
+    ///     .execute()
+    ///     .unwrap();
+    /// assert_eq!(values, vec!["first", "second"]);
+    /// ```
+    pub fn pipeline(self: &mut Client) -> pipeline::Pipeline {
+        pipeline::create(self)
+    }
+
+    /// Sends all provided packed commands in a single write and reads the response of each command.<br>
+    /// In case reconnect is false, the commands are only sent on the current connection.<br>
+    /// In case a command timeout is set, it bounds the entire round trip of all the commands.<br>
+    /// The connection is dropped in case of any connection error.
+    pub(crate) fn run_packed_commands(
+        self: &mut Client,
+        packed: &[u8],
+        count: usize,
+        reconnect: bool,
+    ) -> Vec<RedisResult<redis::Value>> {
+        let mut results = vec![];
+        let deadline = self.get_command_deadline();
+
+        let connection_result = if self.cluster.is_some() {
+            Err(create_cluster_unsupported_error())
+        } else if reconnect {
+            self.connection.get_redis_connection(&self.connection_info)
+        } else {
+            self.connection.get_open_redis_connection()
+        };
+
+        let connection_error = match connection_result {
+            Ok(connection) => {
+                run_packed_commands_on_connection(connection, packed, count, deadline, &mut results)
+            }
+            Err(error) => {
+                results.push(Err(error));
+                false
+            }
+        };
+
+        // unread responses may still be queued on the connection, so it can't be used for other commands
+        if connection_error {
+            self.connection.close();
+        } else if deadline.is_some() {
+            self.connection.restore_timeouts();
+        }
+
+        while results.len() < count {
+            results.push(Err(RedisError {
+                info: ErrorInfo::Description(types::PIPELINE_ABORTED),
+            }));
+        }
+
+        results
+    }
+
+    /// Ensures a valid connection is available, verifying a previously created connection before it is used and
+    /// reconnecting if needed.
+    pub(crate) fn open_verified_connection(self: &mut Client) -> RedisEmptyResult {
+        if self.cluster.is_some() {
+            return Err(create_cluster_unsupported_error());
+        }
+
+        match self
+            .connection
+            .get_verified_redis_connection(&self.connection_info)
+        {
+            Ok(_) => Ok(()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Invokes the requested command on the current connection without validating it or reconnecting.<br>
+    /// In case a command timeout is set, it bounds the command round trip.<br>
+    /// In case of any connection error, the connection is dropped.
+    pub(crate) fn run_command_pinned<T: redis::FromRedisValue>(
+        self: &mut Client,
+        command: &str,
+        args: Vec<impl AsRef<[u8]>>,
+    ) -> RedisResult<T> {
+        let deadline = self.get_command_deadline();
+
+        let result = match self.connection.get_open_redis_connection() {
+            Ok(connection) => match apply_deadline(connection, deadline) {
+                Ok(_) => run_command_on_connection::<T>(connection, command, args),
+                Err(error) => Err(error),
+            },
             Err(error) => Err(error),
+        };
+
+        let result = match result {
+            Err(RedisError {
+                info: ErrorInfo::RedisError(cause),
+            }) => Err(to_command_error(cause, deadline)),
+            _ => result,
+        };
+
+        let connection_error = match result {
+            Err(ref error) => error.is_connection_error(),
+            _ => false,
+        };
+        if connection_error {
+            self.connection.close();
+        } else {
+            self.connection.drop_closed_connection();
+
+            if deadline.is_some() {
+                self.connection.restore_timeouts();
+            }
         }
+
+        result
+    }
+
+    /// Returns the time by which the current command must be completed, based on the command timeout (if set).
+    fn get_command_deadline(self: &Client) -> Option<Instant> {
+        self.connection
+            .get_command_timeout()
+            .map(|timeout| Instant::now() + timeout)
+    }
+
+    /// Runs the provided function as an atomic redis transaction
+    /// (see redis [MULTI](https://redis.io/commands/multi) and [EXEC](https://redis.io/commands/exec) commands).<br>
+    /// The provided keys are watched (see redis [WATCH](https://redis.io/commands/watch) command) before the function
+    /// is invoked, and in case any of them was modified before the transaction was executed, the function is invoked
+    /// again (up to 100 times, after which the transaction fails).<br>
+    /// Commands invoked via the transaction run_command function are executed immediately (for example to read the
+    /// current values of the watched keys), while all other commands are queued and executed atomically once the
+    /// function returns.<br>
+    /// The entire transaction runs on a single connection, which is verified (using PING) before the transaction
+    /// starts, and in case of any connection error, the transaction fails instead of reconnecting.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys to watch
+    /// * `func` - The function which queues the transaction commands
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// let (_, value): ((), i64) = client
+    ///     .transaction(vec!["my_counter"], |transaction| {
+    ///         let current = transaction
+    ///             .run_command::<Option<i64>>("GET", vec!["my_counter"])?
+    ///             .unwrap_or(0);
+    ///
+    ///         transaction.set("my_counter", current + 1).get("my_counter");
+    ///
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn transaction<T, F>(self: &mut Client, keys: Vec<&str>, func: F) -> RedisResult<T>
+    where
+        T: redis::FromRedisValue,
+        F: FnMut(&mut transaction::Transaction) -> RedisEmptyResult,
+    {
+        transaction::run(self, keys, func)
     }
 
     /// invokes the run_command and returns typed result
@@ -122,17 +563,53 @@ impl Client {
         command: &str,
         args: Vec<&str>,
     ) -> RedisResult<T> {
-        match self.run_command::<String>(command, args) {
+        self.run_binary_command_from_string_response(command, args)
+    }
+
+    /// invokes the run_command and returns typed result or None in case of a nil response
+    pub fn run_command_from_optional_string_response<T: FromStr>(
+        self: &mut Client,
+        command: &str,
+        args: Vec<&str>,
+    ) -> RedisResult<Option<T>> {
+        self.run_binary_command_from_optional_string_response(command, args)
+    }
+
+    /// invokes the run_binary_command and returns typed result
+    pub(crate) fn run_binary_command_from_string_response<T: FromStr>(
+        self: &mut Client,
+        command: &str,
+        args: Vec<impl AsRef<[u8]>>,
+    ) -> RedisResult<T> {
+        match self.run_binary_command::<String>(command, args) {
             Ok(value) => match T::from_str(&value) {
                 Ok(typed_value) => Ok(typed_value),
                 _ => Err(RedisError {
-                    info: ErrorInfo::Description("Unable to parse output value."),
+                    info: ErrorInfo::Description(types::PARSE_FAILED),
                 }),
             },
             Err(error) => Err(error),
         }
     }
 
+    /// invokes the run_binary_command and returns typed result or None in case of a nil response
+    pub(crate) fn run_binary_command_from_optional_string_response<T: FromStr>(
+        self: &mut Client,
+        command: &str,
+        args: Vec<impl AsRef<[u8]>>,
+    ) -> RedisResult<Option<T>> {
+        match self.run_binary_command::<Option<String>>(command, args) {
+            Ok(Some(value)) => match T::from_str(&value) {
+                Ok(typed_value) => Ok(Some(typed_value)),
+                _ => Err(RedisError {
+                    info: ErrorInfo::Description(types::PARSE_FAILED),
+                }),
+            },
+            Ok(None) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
     /// invokes the run_command but returns empty result
     pub fn run_command_empty_response(
         self: &mut Client,
@@ -245,12 +722,39 @@ impl Client {
     /// }
     /// ```
     pub fn get_message(self: &mut Client, timeout: u64) -> RedisMessageResult {
-        self.subscriber.get_message(&self.client, timeout)
+        self.subscriber.get_message(&self.connection_info, timeout)
+    }
+}
+
+/// A client wrapper which limits the duration of every invoked command.<br>
+/// The timeout is removed once this value is dropped.
+pub struct TimeoutClient<'a> {
+    client: &'a mut Client,
+}
+
+impl<'a> Deref for TimeoutClient<'a> {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        self.client
+    }
+}
+
+impl<'a> DerefMut for TimeoutClient<'a> {
+    fn deref_mut(&mut self) -> &mut Client {
+        self.client
+    }
+}
+
+impl<'a> Drop for TimeoutClient<'a> {
+    fn drop(&mut self) {
+        self.client.set_command_timeout(None);
     }
 }
 
 /// Constructs a new redis client.<br>
-/// The redis connection string must be in the following format: `redis://[:<passwd>@]<hostname>[:port][/<db>]`
+/// The redis connection string must be in the following format: `redis://[:<passwd>@]<hostname>[:port][/<db>]`<br>
+/// Unix domain sockets are supported (on unix platforms) using the following format: `unix:///<path>[?db=<db>]`
 ///
 /// # Arguments
 ///
@@ -268,21 +772,264 @@ impl Client {
 /// }
 /// ```
 pub fn create(connection_string: &str) -> Result<Client, RedisError> {
-    match redis::Client::open(connection_string) {
-        Ok(redis_client) => {
-            let redis_connection = connection::create();
-            let redis_pubsub = subscriber::create();
-
-            let client = Client {
-                client: redis_client,
-                connection: redis_connection,
-                subscriber: redis_pubsub,
-            };
-
-            Ok(client)
+    create_with_retry_policy(connection_string, RetryPolicy::default())
+}
+
+/// Constructs a new redis client which retries failed commands based on the provided retry policy.<br>
+/// The redis connection string must be in the following format: `redis://[:<passwd>@]<hostname>[:port][/<db>]`<br>
+/// Unix domain sockets are supported (on unix platforms) using the following format: `unix:///<path>[?db=<db>]`
+///
+/// # Arguments
+///
+/// * `connection_string` - The connection string in the format of: `redis://[:<passwd>@]<hostname>[:port][/<db>]`
+/// * `retry_policy` - Defines how failed commands are retried
+///
+/// # Example
+///
+/// ```
+/// extern crate simple_redis;
+/// use simple_redis::types::RetryPolicy;
+/// use std::time::{Duration, Instant};
+///
+/// fn main() {
+///     let mut retry_policy = RetryPolicy::default();
+///     retry_policy.max_attempts = 5;
+///     retry_policy.max_backoff = Duration::from_secs(2);
+///
+///     match simple_redis::client::create_with_retry_policy("redis://127.0.0.1:6379/", retry_policy) {
+///         Ok(client) => println!("Created Redis Client"),
+///         Err(error) => println!("Unable to create Redis client: {}", error)
+///     }
+/// }
+/// ```
+pub fn create_with_retry_policy(
+    connection_string: &str,
+    retry_policy: RetryPolicy,
+) -> Result<Client, RedisError> {
+    ClientBuilder::new(connection_string)
+        .retry_policy(retry_policy)
+        .build()
+}
+
+/// Builds redis clients with custom connection options.<br>
+/// All options are applied on every new connection (including reconnections and pubsub connections).
+///
+/// # Example
+///
+/// ```
+/// extern crate simple_redis;
+/// use std::time::{Duration, Instant};
+///
+/// fn main() {
+///     let result = simple_redis::ClientBuilder::new("redis://127.0.0.1:6379/")
+///         .connect_timeout(Duration::from_secs(1))
+///         .read_timeout(Duration::from_secs(5))
+///         .write_timeout(Duration::from_secs(5))
+///         .tcp_nodelay(true)
+///         .tcp_keepalive(Duration::from_secs(60))
+///         .client_name("my_service")
+///         .database(1)
+///         .build();
+///
+///     match result {
+///         Ok(client) => println!("Created Redis Client"),
+///         Err(error) => println!("Unable to create Redis client: {}", error)
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClientBuilder {
+    connection_string: String,
+    database: Option<i64>,
+    retry_policy: RetryPolicy,
+    health_check_interval: Option<Duration>,
+    options: connection::ConnectionOptions,
+    read_from_replicas: bool,
+    cluster_nodes: Option<Vec<(String, u16)>>,
+    #[cfg(feature = "tls")]
+    tls_options: Option<tls::TlsOptions>,
+}
+
+impl ClientBuilder {
+    /// Creates a new builder for the provided connection string.
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_string` - The connection string in the format of: `redis://[:<passwd>@]<hostname>[:port][/<db>]`
+    pub fn new(connection_string: &str) -> ClientBuilder {
+        ClientBuilder {
+            connection_string: connection_string.to_string(),
+            database: None,
+            retry_policy: RetryPolicy::default(),
+            health_check_interval: None,
+            options: connection::ConnectionOptions::default(),
+            read_from_replicas: false,
+            cluster_nodes: None,
+            #[cfg(feature = "tls")]
+            tls_options: None,
         }
-        Err(error) => Err(RedisError {
-            info: ErrorInfo::RedisError(error),
-        }),
+    }
+
+    /// Sets the max time to wait for a new connection to be established.
+    pub fn connect_timeout(mut self: ClientBuilder, timeout: Duration) -> ClientBuilder {
+        self.options.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the max time to wait for a command response.
+    pub fn read_timeout(mut self: ClientBuilder, timeout: Duration) -> ClientBuilder {
+        self.options.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the max time to wait for a command to be sent.
+    pub fn write_timeout(mut self: ClientBuilder, timeout: Duration) -> ClientBuilder {
+        self.options.write_timeout = Some(timeout);
+        self
+    }
+
+    /// Enables or disables TCP_NODELAY on every TCP connection (disabled by default).
+    pub fn tcp_nodelay(mut self: ClientBuilder, nodelay: bool) -> ClientBuilder {
+        self.options.tcp_nodelay = nodelay;
+        self
+    }
+
+    /// Enables TCP keepalive on every TCP connection, probing the connection once it was idle for the provided
+    /// duration.
+    pub fn tcp_keepalive(mut self: ClientBuilder, idle: Duration) -> ClientBuilder {
+        self.options.tcp_keepalive = Some(idle);
+        self
+    }
+
+    /// Sets the client name (see redis [CLIENT SETNAME](https://redis.io/commands/client-setname) command).
+    pub fn client_name(mut self: ClientBuilder, name: &str) -> ClientBuilder {
+        self.options.client_name = Some(name.to_string());
+        self
+    }
+
+    /// Sets the database to use, overriding the database defined in the connection string.
+    pub fn database(mut self: ClientBuilder, database: i64) -> ClientBuilder {
+        self.database = Some(database);
+        self
+    }
+
+    /// Sets the retry policy which defines how failed commands are retried (by default, commands are not retried).
+    pub fn retry_policy(mut self: ClientBuilder, retry_policy: RetryPolicy) -> ClientBuilder {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the interval after which an idle connection is verified before being used
+    /// (see Client::set_health_check_interval).
+    pub fn health_check_interval(mut self: ClientBuilder, interval: Duration) -> ClientBuilder {
+        self.health_check_interval = Some(interval);
+        self
+    }
+
+    /// Resolves the server address via redis sentinel on every new connection.
+    pub(crate) fn sentinel(
+        mut self: ClientBuilder,
+        sentinel_config: sentinel::SentinelConfig,
+        read_from_replicas: bool,
+    ) -> ClientBuilder {
+        self.options.sentinel = Some(sentinel_config);
+        self.read_from_replicas = read_from_replicas;
+        self
+    }
+
+    /// Routes every command to the cluster node serving its keys, based on the slot map loaded from the provided
+    /// nodes.
+    pub(crate) fn cluster(mut self: ClientBuilder, nodes: Vec<(String, u16)>) -> ClientBuilder {
+        self.cluster_nodes = Some(nodes);
+        self
+    }
+
+    /// Sets the TLS options and enables TLS for all connections (also enabled by using a `rediss://` connection
+    /// string).<br>
+    /// Only available with the tls feature.
+    #[cfg(feature = "tls")]
+    pub fn tls(mut self: ClientBuilder, tls_options: tls::TlsOptions) -> ClientBuilder {
+        self.tls_options = Some(tls_options);
+        self
+    }
+
+    #[cfg(feature = "tls")]
+    fn apply_tls(self: &ClientBuilder, options: &mut connection::ConnectionOptions) {
+        options.tls = match self.tls_options {
+            Some(ref tls_options) => Some(tls_options.clone()),
+            None if connection::is_tls_url(&self.connection_string) => {
+                Some(tls::TlsOptions::default())
+            }
+            None => None,
+        };
+    }
+
+    #[cfg(not(feature = "tls"))]
+    fn apply_tls(self: &ClientBuilder, _options: &mut connection::ConnectionOptions) {}
+
+    /// Creates a new client based on the builder configuration.<br>
+    /// The connection itself is only created once the first operation is invoked.
+    pub fn build(self: &ClientBuilder) -> Result<Client, RedisError> {
+        let connection_info = match connection::parse_connection_info(&self.connection_string) {
+            Ok(mut connection_info) => {
+                if let Some(database) = self.database {
+                    connection_info.db = database;
+                }
+
+                connection_info
+            }
+            Err(error) => return Err(error),
+        };
+
+        let mut options = self.options.clone();
+        self.apply_tls(&mut options);
+
+        if options.sentinel.is_some() && options.is_tls() {
+            return Err(RedisError {
+                info: ErrorInfo::RedisError(redis::RedisError::from((
+                    redis::ErrorKind::InvalidClientConfig,
+                    "TLS is not supported for sentinel connections.",
+                ))),
+            });
+        }
+
+        let replica_connection = match options.sentinel {
+            Some(ref sentinel_config) if self.read_from_replicas => {
+                let mut replica_options = options.clone();
+                let mut replica_config = sentinel_config.clone();
+                replica_config.role = sentinel::Role::Replica;
+                replica_options.sentinel = Some(replica_config);
+
+                let mut replica_connection =
+                    connection::create(self.retry_policy.clone(), replica_options);
+                replica_connection.set_health_check_interval(self.health_check_interval);
+
+                Some(replica_connection)
+            }
+            _ => None,
+        };
+
+        let cluster = match self.cluster_nodes {
+            Some(ref nodes) => Some(cluster::create_connections(
+                nodes.clone(),
+                connection_info.passwd.clone(),
+                self.retry_policy.clone(),
+                options.clone(),
+                self.health_check_interval,
+            )),
+            None => None,
+        };
+
+        let mut redis_connection = connection::create(self.retry_policy.clone(), options.clone());
+        redis_connection.set_health_check_interval(self.health_check_interval);
+        let redis_pubsub = subscriber::create(options);
+
+        Ok(Client {
+            connection_info,
+            connection: redis_connection,
+            replica_connection,
+            cluster,
+            subscriber: redis_pubsub,
+        })
     }
 }
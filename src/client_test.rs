@@ -40,3 +40,175 @@ fn run_command_typed_response() {
         .unwrap();
     assert_eq!(value, "my_value");
 }
+
+#[test]
+fn builder_invalid_url() {
+    let result = ClientBuilder::new("test/bad/url").build();
+    assert!(result.is_err());
+}
+
+#[test]
+fn builder_options() {
+    let builder = ClientBuilder::new("redis://127.0.0.1:6379/2")
+        .connect_timeout(Duration::from_secs(1))
+        .read_timeout(Duration::from_secs(2))
+        .write_timeout(Duration::from_secs(3))
+        .tcp_nodelay(true)
+        .tcp_keepalive(Duration::from_secs(4))
+        .client_name("test_client")
+        .database(5);
+
+    assert_eq!(builder.database, Some(5));
+    assert_eq!(
+        builder.options.connect_timeout,
+        Some(Duration::from_secs(1))
+    );
+    assert_eq!(builder.options.read_timeout, Some(Duration::from_secs(2)));
+    assert_eq!(builder.options.write_timeout, Some(Duration::from_secs(3)));
+    assert!(builder.options.tcp_nodelay);
+    assert_eq!(builder.options.tcp_keepalive, Some(Duration::from_secs(4)));
+    assert_eq!(builder.options.client_name, Some("test_client".to_string()));
+
+    let client = builder.build().unwrap();
+    assert!(!client.is_connection_open());
+}
+
+#[test]
+fn builder_connect_timeout() {
+    let mut client = ClientBuilder::new("redis://127.0.0.1:1/")
+        .connect_timeout(Duration::from_millis(100))
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let error = client
+        .run_command::<String>("ECHO", vec!["testing"])
+        .unwrap_err();
+    assert!(error.is_connection_error());
+}
+
+#[test]
+fn with_timeout_hung_server() {
+    // accepts connections but never responds
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        std::thread::sleep(Duration::from_millis(500));
+        drop(stream);
+    });
+
+    let mut client = create(&format!("redis://127.0.0.1:{}/", port)).unwrap();
+
+    let error = client
+        .with_timeout(Duration::from_millis(100))
+        .run_command::<String>("GET", vec!["timeout_key"])
+        .unwrap_err();
+    match error.info {
+        ErrorInfo::TimeoutError(_) => (),
+        _ => panic!("test error"),
+    };
+    assert!(!client.is_connection_created());
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn with_timeout_hung_server_pipeline() {
+    // accepts connections but never responds
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        std::thread::sleep(Duration::from_millis(500));
+        drop(stream);
+    });
+
+    let mut client = create(&format!("redis://127.0.0.1:{}/", port)).unwrap();
+
+    let start = std::time::Instant::now();
+    let error = client
+        .with_timeout(Duration::from_millis(100))
+        .pipeline()
+        .echo("first")
+        .echo("second")
+        .execute::<Vec<String>>()
+        .unwrap_err();
+    match error.info {
+        ErrorInfo::TimeoutError(_) => (),
+        _ => panic!("test error"),
+    };
+    assert!(start.elapsed() < Duration::from_millis(400));
+    assert!(!client.is_connection_created());
+
+    handle.join().unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn create_unix_socket_url() {
+    let client = create("unix:///tmp/simple_redis.sock?db=2").unwrap();
+    assert!(!client.is_connection_open());
+
+    let client = create("redis+unix:///tmp/simple_redis.sock").unwrap();
+    assert!(!client.is_connection_open());
+}
+
+#[test]
+fn pipeline_connection_error_closes_connection() {
+    // responds to the first pipelined command and then stops responding
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buffer = [0; 1024];
+        let _ = std::io::Read::read(&mut stream, &mut buffer);
+        std::io::Write::write_all(&mut stream, b"+OK\r\n").unwrap();
+        std::thread::sleep(Duration::from_millis(500));
+    });
+
+    let mut client = ClientBuilder::new(&format!("redis://127.0.0.1:{}/", port))
+        .read_timeout(Duration::from_millis(100))
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let results = client
+        .pipeline()
+        .cmd("SET", vec!["pipeline_key1", "value"])
+        .cmd("SET", vec!["pipeline_key2", "value"])
+        .execute_all();
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+
+    // the second response may still arrive, so it must not be read by the next command
+    assert!(!client.is_connection_created());
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn run_command_server_closed_idle_connection() {
+    // responds to a single command on every connection and then closes it (as the server idle timeout does)
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handle = std::thread::spawn(move || {
+        for _ in 0..2 {
+            let (mut stream, _) = listener.accept().unwrap();
+            redis::Parser::new(&mut stream).parse_value().unwrap();
+            std::io::Write::write_all(&mut stream, b"$5\r\nvalue\r\n").unwrap();
+        }
+    });
+
+    let mut client = create(&format!("redis://127.0.0.1:{}/", port)).unwrap();
+
+    let value: String = client.run_command("GET", vec!["idle_key"]).unwrap();
+    assert_eq!(value, "value");
+
+    // the idempotent command is replayed on a new connection once the closed connection is detected
+    std::thread::sleep(Duration::from_millis(100));
+    let value: String = client.run_command("GET", vec!["idle_key"]).unwrap();
+    assert_eq!(value, "value");
+
+    handle.join().unwrap();
+}
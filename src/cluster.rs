@@ -0,0 +1,866 @@
+//! # cluster
+//!
+//! Enables connecting to [redis cluster](https://redis.io/topics/cluster-spec).<br>
+//! Every command is routed to the node serving the hash slot of its keys, based on a slot map which is loaded
+//! from the cluster and refreshed whenever the cluster topology changes (detected via MOVED redirections and
+//! connection errors).
+//!
+
+#[cfg(test)]
+#[path = "./cluster_test.rs"]
+mod cluster_test;
+
+use client;
+use client::Client;
+use connection;
+use redis;
+use socket;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use types;
+use types::{ErrorInfo, ErrorKind, RedisEmptyResult, RedisError, RedisResult, RetryPolicy};
+
+/// The number of hash slots in a redis cluster
+const SLOTS_COUNT: usize = 16384;
+/// The max number of MOVED/ASK redirections followed for a single command
+const MAX_REDIRECTIONS: u32 = 5;
+/// The min time between automatic slot map reloads, so an unavailable cluster is not queried on every command
+const MIN_REFRESH_INTERVAL_MILLIS: u64 = 1000;
+/// The description of the error raised for commands which only cover the keys of a single node
+const SINGLE_NODE_COMMAND: &'static str =
+    "Command only covers the keys of a single node and is not supported in cluster mode.";
+
+/// Commands without keys which only cover the keys of the node they are invoked on
+static SINGLE_NODE_COMMANDS: &'static [&'static str] =
+    &["DBSIZE", "FLUSHALL", "FLUSHDB", "KEYS", "RANDOMKEY", "SCAN"];
+
+/// Commands which do not operate on keys and are invoked on any node
+static KEYLESS_COMMANDS: &'static [&'static str] = &[
+    "ASKING",
+    "AUTH",
+    "BGREWRITEAOF",
+    "BGSAVE",
+    "CLIENT",
+    "CLUSTER",
+    "COMMAND",
+    "CONFIG",
+    "DISCARD",
+    "ECHO",
+    "EXEC",
+    "FUNCTION",
+    "HELLO",
+    "INFO",
+    "LASTSAVE",
+    "LATENCY",
+    "MULTI",
+    "PING",
+    "PUBLISH",
+    "QUIT",
+    "READONLY",
+    "READWRITE",
+    "ROLE",
+    "SAVE",
+    "SCRIPT",
+    "SELECT",
+    "SLOWLOG",
+    "TIME",
+    "UNWATCH",
+    "WAIT",
+];
+
+/// Commands which all their arguments are keys
+static ALL_KEYS_COMMANDS: &'static [&'static str] = &[
+    "DEL",
+    "EXISTS",
+    "MGET",
+    "PFCOUNT",
+    "PFMERGE",
+    "SDIFF",
+    "SDIFFSTORE",
+    "SINTER",
+    "SINTERSTORE",
+    "SUNION",
+    "SUNIONSTORE",
+    "TOUCH",
+    "UNLINK",
+    "WATCH",
+];
+
+/// Commands which the first two arguments are keys
+static TWO_KEYS_COMMANDS: &'static [&'static str] = &[
+    "BLMOVE",
+    "BRPOPLPUSH",
+    "COPY",
+    "LMOVE",
+    "RENAME",
+    "RENAMENX",
+    "RPOPLPUSH",
+    "SMOVE",
+];
+
+/// Commands which all their arguments are keys, except for the last argument (timeout)
+static BLOCKING_KEYS_COMMANDS: &'static [&'static str] =
+    &["BLPOP", "BRPOP", "BZPOPMAX", "BZPOPMIN"];
+
+/// Commands which the first argument is the number of keys, followed by the keys
+static NUMKEYS_COMMANDS: &'static [&'static str] = &[
+    "LMPOP",
+    "SINTERCARD",
+    "ZDIFF",
+    "ZINTER",
+    "ZINTERCARD",
+    "ZMPOP",
+    "ZUNION",
+];
+
+/// Commands which the second argument is the number of keys, followed by the keys
+static SECOND_NUMKEYS_COMMANDS: &'static [&'static str] = &[
+    "BLMPOP",
+    "BZMPOP",
+    "EVAL",
+    "EVALSHA",
+    "EVALSHA_RO",
+    "EVAL_RO",
+    "FCALL",
+    "FCALL_RO",
+];
+
+/// Commands which the first argument is a destination key, followed by the number of keys and the keys
+static STORE_NUMKEYS_COMMANDS: &'static [&'static str] =
+    &["ZDIFFSTORE", "ZINTERSTORE", "ZUNIONSTORE"];
+
+/// Commands which the first argument is a sub command, followed by the key
+static SUBCOMMAND_KEY_COMMANDS: &'static [&'static str] = &["MEMORY", "OBJECT", "XGROUP", "XINFO"];
+
+/// Holds the cluster slot map, mapping every hash slot to the address of the master node serving it.
+#[derive(Debug, Clone)]
+struct SlotMap {
+    /// The addresses of all known master nodes
+    addresses: Vec<(String, u16)>,
+    /// The index (in the addresses) of the node serving each slot
+    slots: Vec<Option<usize>>,
+}
+
+/// A single cluster node connection.
+struct Node {
+    /// The node connection info
+    connection_info: redis::ConnectionInfo,
+    /// Holds the current node connection
+    connection: connection::Connection,
+}
+
+/// Manages the connections to all cluster nodes and routes every command to the node serving its keys.
+pub(crate) struct ClusterConnections {
+    /// The initial nodes used to load the slot map
+    seeds: Vec<(String, u16)>,
+    /// The password of all cluster nodes
+    password: Option<String>,
+    /// Defines how failed commands are retried (per node)
+    retry_policy: RetryPolicy,
+    /// The options applied on every new node connection
+    options: connection::ConnectionOptions,
+    /// The health check interval of every node connection
+    health_check_interval: Option<Duration>,
+    /// The command timeout of every node connection
+    command_timeout: Option<Duration>,
+    /// The current slot map
+    slot_map: SlotMap,
+    /// If true, the slot map is reloaded before the next command
+    refresh_needed: bool,
+    /// The time of the last slot map reload attempt
+    last_refresh: Option<Instant>,
+    /// Incremented every time the slot map is reloaded or a node connection is created, so it never returns to a
+    /// previous value (unlike the node connection generations, as nodes are dropped once no longer masters)
+    generation: u64,
+    /// The connections of all nodes used so far
+    nodes: HashMap<(String, u16), Node>,
+}
+
+/// Computes the CRC16 (XMODEM) checksum used for the key hash slots.
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+
+    for byte in data {
+        crc ^= u16::from(*byte) << 8;
+
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+
+    crc
+}
+
+/// Returns the hash slot of the provided key.<br>
+/// In case the key contains a non empty hash tag (for example `{user1000}.following`), only the hash tag is hashed,
+/// which enables forcing multiple keys into the same slot.
+///
+/// # Example
+///
+/// ```
+/// use simple_redis::cluster::get_key_slot;
+///
+/// assert_eq!(get_key_slot(b"{user1000}.following"), get_key_slot(b"{user1000}.followers"));
+/// ```
+pub fn get_key_slot(key: &[u8]) -> u16 {
+    let hashed = match key.iter().position(|byte| *byte == b'{') {
+        Some(start) => match key[start + 1..].iter().position(|byte| *byte == b'}') {
+            Some(length) if length > 0 => &key[start + 1..start + 1 + length],
+            _ => key,
+        },
+        None => key,
+    };
+
+    crc16(hashed) % SLOTS_COUNT as u16
+}
+
+fn parse_numkeys<A: AsRef<[u8]>>(args: &[A], index: usize) -> usize {
+    match args.get(index) {
+        Some(value) => match String::from_utf8_lossy(value.as_ref()).parse::<usize>() {
+            Ok(numkeys) => numkeys,
+            Err(_) => 0,
+        },
+        None => 0,
+    }
+}
+
+fn get_range<A: AsRef<[u8]>>(args: &[A], start: usize, count: usize) -> Vec<&[u8]> {
+    args.iter()
+        .skip(start)
+        .take(count)
+        .map(|arg| arg.as_ref())
+        .collect()
+}
+
+/// Returns the keys of the provided command (based on the command arguments layout).
+fn get_command_keys<'a, A: AsRef<[u8]>>(command: &str, args: &'a [A]) -> Vec<&'a [u8]> {
+    let command = command.to_uppercase();
+    let command = command.as_str();
+
+    if KEYLESS_COMMANDS.contains(&command) || SINGLE_NODE_COMMANDS.contains(&command) {
+        vec![]
+    } else if ALL_KEYS_COMMANDS.contains(&command) {
+        get_range(args, 0, args.len())
+    } else if TWO_KEYS_COMMANDS.contains(&command) {
+        get_range(args, 0, 2)
+    } else if BLOCKING_KEYS_COMMANDS.contains(&command) {
+        get_range(args, 0, args.len().saturating_sub(1))
+    } else if NUMKEYS_COMMANDS.contains(&command) {
+        get_range(args, 1, parse_numkeys(args, 0))
+    } else if SECOND_NUMKEYS_COMMANDS.contains(&command) {
+        get_range(args, 2, parse_numkeys(args, 1))
+    } else if STORE_NUMKEYS_COMMANDS.contains(&command) {
+        let mut keys = get_range(args, 0, 1);
+        keys.extend(get_range(args, 2, parse_numkeys(args, 1)));
+        keys
+    } else if SUBCOMMAND_KEY_COMMANDS.contains(&command) {
+        get_range(args, 1, 1)
+    } else if command == "MSET" || command == "MSETNX" {
+        args.iter().step_by(2).map(|arg| arg.as_ref()).collect()
+    } else if command == "XREAD" || command == "XREADGROUP" {
+        // the keys are followed by the same number of ids
+        match args
+            .iter()
+            .position(|arg| arg.as_ref().eq_ignore_ascii_case(b"STREAMS"))
+        {
+            Some(index) => get_range(args, index + 1, (args.len() - index - 1) / 2),
+            None => vec![],
+        }
+    } else {
+        get_range(args, 0, 1)
+    }
+}
+
+/// Returns the hash slot of the provided command keys or None for commands without keys.<br>
+/// In case the keys do not hash to the same slot, a cross slot error is returned, as the command can't be served
+/// by a single node.<br>
+/// Commands which only cover the keys of a single node (for example KEYS and SCAN) are rejected, as invoking them
+/// on any single node would silently return partial results.
+pub(crate) fn get_command_slot<A: AsRef<[u8]>>(
+    command: &str,
+    args: &[A],
+) -> RedisResult<Option<u16>> {
+    if SINGLE_NODE_COMMANDS.contains(&command.to_uppercase().as_str()) {
+        return Err(RedisError {
+            info: ErrorInfo::Description(SINGLE_NODE_COMMAND),
+        });
+    }
+
+    let mut slot = None;
+
+    for key in get_command_keys(command, args) {
+        let key_slot = get_key_slot(key);
+
+        match slot {
+            Some(slot) if slot != key_slot => {
+                return Err(RedisError {
+                    info: ErrorInfo::Description(types::CROSS_SLOT),
+                })
+            }
+            _ => slot = Some(key_slot),
+        }
+    }
+
+    Ok(slot)
+}
+
+/// Returns the redirection target (slot and node address) of a MOVED or ASK error.
+fn get_redirection(error: &RedisError) -> Option<(u16, (String, u16))> {
+    let detail = match error.info {
+        ErrorInfo::RedisError(ref cause) => types::get_extension_error_detail(cause)?,
+        _ => return None,
+    };
+    let mut parts = detail.split_whitespace();
+
+    let slot = match parts.next().map(u16::from_str) {
+        Some(Ok(slot)) => slot,
+        _ => return None,
+    };
+
+    match parts.next() {
+        Some(address) => match address.rfind(':') {
+            Some(index) => match u16::from_str(&address[index + 1..]) {
+                Ok(port) => Some((slot, (address[..index].to_string(), port))),
+                Err(_) => None,
+            },
+            None => None,
+        },
+        None => None,
+    }
+}
+
+fn create_slot_map_error() -> redis::RedisError {
+    redis::RedisError::from((
+        redis::ErrorKind::TypeError,
+        "Unable to parse the cluster slot map.",
+    ))
+}
+
+/// Parses the CLUSTER SLOTS response into slot ranges and their master node address.<br>
+/// Nodes with an unknown host are served on the host which returned the response.
+fn parse_cluster_slots(
+    ranges: Vec<Vec<redis::Value>>,
+    queried_host: &str,
+) -> redis::RedisResult<Vec<(u16, u16, (String, u16))>> {
+    let mut output = vec![];
+
+    for range in ranges {
+        if range.len() < 3 {
+            return Err(create_slot_map_error());
+        }
+
+        let start: u16 = redis::from_redis_value(&range[0])?;
+        let end: u16 = redis::from_redis_value(&range[1])?;
+        let master: Vec<redis::Value> = redis::from_redis_value(&range[2])?;
+        if master.len() < 2 {
+            return Err(create_slot_map_error());
+        }
+
+        let mut host: String = redis::from_redis_value(&master[0])?;
+        let port: u16 = redis::from_redis_value(&master[1])?;
+        if host.is_empty() || host == "?" {
+            host = queried_host.to_string();
+        }
+
+        output.push((start, end, (host, port)));
+    }
+
+    Ok(output)
+}
+
+/// Parses the CLUSTER SHARDS response into slot ranges and their master node address.
+fn parse_cluster_shards(
+    shards: Vec<HashMap<String, redis::Value>>,
+    queried_host: &str,
+) -> redis::RedisResult<Vec<(u16, u16, (String, u16))>> {
+    let mut output = vec![];
+
+    for shard in shards {
+        let slots: Vec<u16> = match shard.get("slots") {
+            Some(value) => redis::from_redis_value(value)?,
+            None => return Err(create_slot_map_error()),
+        };
+        let nodes: Vec<HashMap<String, redis::Value>> = match shard.get("nodes") {
+            Some(value) => redis::from_redis_value(value)?,
+            None => return Err(create_slot_map_error()),
+        };
+
+        let master = nodes.iter().find(|node| match node.get("role") {
+            Some(role) => redis::from_redis_value::<String>(role)
+                .map(|role| role == "master")
+                .unwrap_or(false),
+            None => false,
+        });
+
+        let address = match master {
+            Some(master) => {
+                let mut host: String = match master.get("ip").or_else(|| master.get("endpoint")) {
+                    Some(value) => redis::from_redis_value(value)?,
+                    None => queried_host.to_string(),
+                };
+                let port: u16 = match master.get("port").or_else(|| master.get("tls-port")) {
+                    Some(value) => redis::from_redis_value(value)?,
+                    None => return Err(create_slot_map_error()),
+                };
+                if host.is_empty() || host == "?" {
+                    host = queried_host.to_string();
+                }
+
+                (host, port)
+            }
+            // shards without a master do not serve any slot
+            None => continue,
+        };
+
+        for range in slots.chunks(2) {
+            if range.len() == 2 {
+                output.push((range[0], range[1], address.clone()));
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+/// Loads the slot map from the provided node using CLUSTER SLOTS, or CLUSTER SHARDS in case CLUSTER SLOTS is not
+/// supported.
+fn query_slot_map(
+    connection: &socket::Connection,
+    queried_host: &str,
+) -> redis::RedisResult<SlotMap> {
+    let result: redis::RedisResult<Vec<Vec<redis::Value>>> =
+        redis::cmd("CLUSTER").arg("SLOTS").query(connection);
+
+    let ranges = match result {
+        Ok(ranges) => parse_cluster_slots(ranges, queried_host)?,
+        Err(ref error) if error.is_io_error() || connection.is_closed() => {
+            return Err(result.unwrap_err())
+        }
+        Err(_) => {
+            let shards: Vec<HashMap<String, redis::Value>> =
+                redis::cmd("CLUSTER").arg("SHARDS").query(connection)?;
+
+            parse_cluster_shards(shards, queried_host)?
+        }
+    };
+
+    Ok(SlotMap::from_ranges(ranges))
+}
+
+impl SlotMap {
+    fn new() -> SlotMap {
+        SlotMap {
+            addresses: vec![],
+            slots: vec![None; SLOTS_COUNT],
+        }
+    }
+
+    fn from_ranges(ranges: Vec<(u16, u16, (String, u16))>) -> SlotMap {
+        let mut slot_map = SlotMap::new();
+
+        for (start, end, address) in ranges {
+            for slot in start..=end {
+                slot_map.set(slot, address.clone());
+            }
+        }
+
+        slot_map
+    }
+
+    fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+
+    fn get(&self, slot: u16) -> Option<&(String, u16)> {
+        match self.slots.get(slot as usize) {
+            Some(&Some(index)) => self.addresses.get(index),
+            _ => None,
+        }
+    }
+
+    fn set(&mut self, slot: u16, address: (String, u16)) {
+        if (slot as usize) >= SLOTS_COUNT {
+            return;
+        }
+
+        let index = match self.addresses.iter().position(|value| *value == address) {
+            Some(index) => index,
+            None => {
+                self.addresses.push(address);
+                self.addresses.len() - 1
+            }
+        };
+
+        self.slots[slot as usize] = Some(index);
+    }
+}
+
+impl ClusterConnections {
+    fn get_node_connection_info(&self, address: &(String, u16)) -> redis::ConnectionInfo {
+        redis::ConnectionInfo {
+            addr: Box::new(redis::ConnectionAddr::Tcp(address.0.clone(), address.1)),
+            db: 0,
+            passwd: self.password.clone(),
+        }
+    }
+
+    fn get_node(&mut self, address: &(String, u16)) -> RedisResult<&mut Node> {
+        if !self.nodes.contains_key(address) {
+            let connection_info = self.get_node_connection_info(address);
+
+            let mut connection =
+                connection::create(self.retry_policy.clone(), self.options.clone());
+            connection.set_health_check_interval(self.health_check_interval);
+            connection.set_command_timeout(self.command_timeout);
+
+            self.nodes.insert(
+                address.clone(),
+                Node {
+                    connection_info,
+                    connection,
+                },
+            );
+        }
+
+        match self.nodes.get_mut(address) {
+            Some(node) => Ok(node),
+            None => Err(RedisError {
+                info: ErrorInfo::Description(types::CONNECTION_NOT_AVAILABLE),
+            }),
+        }
+    }
+
+    /// Returns true if the slot map should be reloaded before the next command.<br>
+    /// Automatic reloads are limited to one per MIN_REFRESH_INTERVAL_MILLIS, so in case the cluster can't be
+    /// queried, the previous slot map (or the seed nodes) are used instead of connecting to every node on every
+    /// command.
+    fn should_refresh(&self) -> bool {
+        if !self.refresh_needed && !self.slot_map.is_empty() {
+            return false;
+        }
+
+        match self.last_refresh {
+            Some(last_refresh) => {
+                last_refresh.elapsed() >= Duration::from_millis(MIN_REFRESH_INTERVAL_MILLIS)
+            }
+            None => true,
+        }
+    }
+
+    /// Reloads the slot map from the first available node (known nodes are queried before the seed nodes).
+    pub(crate) fn refresh_slots(&mut self) -> RedisEmptyResult {
+        self.last_refresh = Some(Instant::now());
+
+        let mut candidates: Vec<(String, u16)> = self.slot_map.addresses.clone();
+        for seed in &self.seeds {
+            if !candidates.contains(seed) {
+                candidates.push(seed.clone());
+            }
+        }
+
+        let mut last_error = None;
+        for address in candidates {
+            let connection_info = self.get_node_connection_info(&address);
+
+            let result = connection::connect(&connection_info, &self.options)
+                .and_then(|connection| query_slot_map(&connection, &address.0));
+
+            match result {
+                Ok(ref slot_map) if slot_map.is_empty() => {
+                    last_error = Some(RedisError {
+                        info: ErrorInfo::Description("Cluster slot map is empty."),
+                    })
+                }
+                Ok(slot_map) => {
+                    // drop the connections of nodes which are no longer masters
+                    self.nodes
+                        .retain(|address, _| slot_map.addresses.contains(address));
+                    self.slot_map = slot_map;
+                    self.refresh_needed = false;
+                    self.generation = self.generation + 1;
+
+                    return Ok(());
+                }
+                Err(error) => {
+                    last_error = Some(RedisError {
+                        info: ErrorInfo::RedisError(error),
+                    })
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or(RedisError {
+            info: ErrorInfo::Description("No cluster nodes defined."),
+        }))
+    }
+
+    /// Returns the address of the node serving the provided slot, or any node for commands without keys.
+    fn get_address(&self, slot: Option<u16>) -> Option<(String, u16)> {
+        let slot_address = match slot {
+            Some(slot) => self.slot_map.get(slot),
+            // keyless commands are always invoked on the same node, which keeps cursors (SCAN) valid
+            None => self.slot_map.get(0),
+        };
+
+        match slot_address {
+            Some(address) => Some(address.clone()),
+            None => self.seeds.first().cloned(),
+        }
+    }
+
+    /// Invokes the provided function on the node serving the provided slot, following MOVED and ASK redirections.<br>
+    /// The block time is provided for commands which may block on the server side (see Connection::run).
+    pub(crate) fn run<T, F>(
+        self: &mut ClusterConnections,
+        slot: Option<u16>,
+        idempotent: bool,
+        block: Option<Duration>,
+        mut func: F,
+    ) -> RedisResult<T>
+    where
+        F: FnMut(&socket::Connection) -> RedisResult<T>,
+    {
+        if self.should_refresh() {
+            // in case the slot map can't be loaded, the previous slot map (or the seed nodes) are used
+            let _ = self.refresh_slots();
+        }
+
+        let mut address = match self.get_address(slot) {
+            Some(address) => address,
+            None => {
+                return Err(RedisError {
+                    info: ErrorInfo::Description("No cluster nodes defined."),
+                })
+            }
+        };
+        let mut asking = false;
+        let mut redirections = 0;
+
+        loop {
+            let (result, reconnected) = {
+                let node = self.get_node(&address)?;
+                let connection_info = &node.connection_info;
+                let node_generation = node.connection.get_generation();
+
+                let result =
+                    node.connection
+                        .run(connection_info, idempotent, block, |connection| {
+                            if asking {
+                                let asking_result: redis::RedisResult<()> =
+                                    redis::cmd("ASKING").query(connection);
+                                if let Err(error) = asking_result {
+                                    return Err(RedisError {
+                                        info: ErrorInfo::RedisError(error),
+                                    });
+                                }
+                            }
+
+                            func(connection)
+                        });
+
+                (result, node.connection.get_generation() != node_generation)
+            };
+
+            if reconnected {
+                self.generation = self.generation + 1;
+            }
+
+            match result {
+                Err(error) => {
+                    let kind = error.kind();
+
+                    if kind == ErrorKind::Moved || kind == ErrorKind::Ask {
+                        if redirections >= MAX_REDIRECTIONS {
+                            return Err(error);
+                        }
+
+                        match get_redirection(&error) {
+                            Some((moved_slot, target)) => {
+                                if kind == ErrorKind::Moved {
+                                    // the slot was permanently moved, so the rest of the slot map may be stale too
+                                    self.slot_map.set(moved_slot, target.clone());
+                                    self.refresh_needed = true;
+                                }
+
+                                // the command is sent to a different node, so the node state (for example a
+                                // scan cursor) no longer applies
+                                self.generation = self.generation + 1;
+                                asking = kind == ErrorKind::Ask;
+                                address = target;
+                                redirections = redirections + 1;
+                            }
+                            None => return Err(error),
+                        }
+                    } else {
+                        // the node may have failed, so the topology is reloaded before the next command
+                        if error.is_connection_error()
+                            || kind == ErrorKind::ServerError("CLUSTERDOWN".to_string())
+                        {
+                            self.refresh_needed = true;
+                        }
+
+                        return Err(error);
+                    }
+                }
+                output => return output,
+            }
+        }
+    }
+
+    /// Returns true if a connection to any node is open.
+    pub(crate) fn is_connection_open(self: &ClusterConnections) -> bool {
+        self.nodes
+            .values()
+            .any(|node| node.connection.is_connection_open())
+    }
+
+    /// Returns a value which changes every time a node connection is created or the slot map is reloaded.
+    pub(crate) fn get_generation(self: &ClusterConnections) -> u64 {
+        self.generation
+    }
+
+    /// Sets the health check interval of all node connections.
+    pub(crate) fn set_health_check_interval(
+        self: &mut ClusterConnections,
+        health_check_interval: Option<Duration>,
+    ) {
+        self.health_check_interval = health_check_interval;
+
+        for node in self.nodes.values_mut() {
+            node.connection
+                .set_health_check_interval(health_check_interval);
+        }
+    }
+
+    /// Sets the command timeout of all node connections.
+    pub(crate) fn set_command_timeout(
+        self: &mut ClusterConnections,
+        command_timeout: Option<Duration>,
+    ) {
+        self.command_timeout = command_timeout;
+
+        for node in self.nodes.values_mut() {
+            node.connection.set_command_timeout(command_timeout);
+        }
+    }
+
+    /// Drops all node connections.
+    pub(crate) fn close(self: &mut ClusterConnections) {
+        for node in self.nodes.values_mut() {
+            node.connection.close();
+        }
+    }
+}
+
+/// Creates the cluster connections manager.<br>
+/// The slot map is only loaded once the first command is invoked.
+pub(crate) fn create_connections(
+    seeds: Vec<(String, u16)>,
+    password: Option<String>,
+    retry_policy: RetryPolicy,
+    options: connection::ConnectionOptions,
+    health_check_interval: Option<Duration>,
+) -> ClusterConnections {
+    ClusterConnections {
+        seeds,
+        password,
+        retry_policy,
+        options,
+        health_check_interval,
+        command_timeout: None,
+        slot_map: SlotMap::new(),
+        refresh_needed: false,
+        last_refresh: None,
+        generation: 0,
+        nodes: HashMap::new(),
+    }
+}
+
+/// The redis cluster client which routes every command to the cluster node serving its keys.<br>
+/// All client commands are available via the cluster client, however commands with multiple keys must only use
+/// keys of the same hash slot (see `get_key_slot`), and commands without keys are invoked on a single node.<br>
+/// Commands which only cover the keys of a single node (DBSIZE, FLUSHALL, FLUSHDB, KEYS, RANDOMKEY and SCAN) are
+/// not supported and fail instead of returning partial results.<br>
+/// Pipelines and transactions are not supported by the cluster client.
+pub struct ClusterClient {
+    client: Client,
+}
+
+impl ClusterClient {
+    /// Reloads the cluster slot map.<br>
+    /// There is no need to call this function as the slot map is reloaded automatically once the cluster topology
+    /// changes.
+    pub fn refresh_slots(self: &mut ClusterClient) -> RedisEmptyResult {
+        self.client.refresh_cluster_slots()
+    }
+}
+
+impl Deref for ClusterClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        &self.client
+    }
+}
+
+impl DerefMut for ClusterClient {
+    fn deref_mut(&mut self) -> &mut Client {
+        &mut self.client
+    }
+}
+
+/// Constructs a new redis cluster client.<br>
+/// The cluster topology is loaded from the provided nodes, so only some of the cluster nodes are required.<br>
+/// The node connection strings must be in the following format: `redis://[:<passwd>@]<hostname>[:port]`
+///
+/// # Arguments
+///
+/// * `nodes` - The cluster nodes connection strings in the format of: `redis://[:<passwd>@]<hostname>[:port]`
+///
+/// # Example
+///
+/// ```
+/// extern crate simple_redis;
+///
+/// fn main() {
+///     match simple_redis::cluster::create(vec!["redis://127.0.0.1:7000", "redis://127.0.0.1:7001"]) {
+///         Ok(client) => println!("Created Redis Cluster Client"),
+///         Err(error) => println!("Unable to create Redis cluster client: {}", error)
+///     }
+/// }
+/// ```
+pub fn create(nodes: Vec<&str>) -> Result<ClusterClient, RedisError> {
+    if nodes.is_empty() {
+        return Err(RedisError {
+            info: ErrorInfo::Description("No cluster nodes defined."),
+        });
+    }
+
+    let mut seeds = vec![];
+    for node in &nodes {
+        let connection_info = connection::parse_connection_info(node)?;
+
+        match *connection_info.addr {
+            redis::ConnectionAddr::Tcp(ref host, port) => seeds.push((host.clone(), port)),
+            _ => {
+                return Err(RedisError {
+                    info: ErrorInfo::RedisError(redis::RedisError::from((
+                        redis::ErrorKind::InvalidClientConfig,
+                        "Cluster nodes must be TCP addresses.",
+                    ))),
+                })
+            }
+        }
+    }
+
+    match client::ClientBuilder::new(nodes[0]).cluster(seeds).build() {
+        Ok(client) => Ok(ClusterClient { client }),
+        Err(error) => Err(error),
+    }
+}
@@ -0,0 +1,431 @@
+use super::*;
+use std::io::Write;
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Starts a fake cluster node which responds to every request using the provided handler and returns its port.<br>
+/// The handler is invoked with the request arguments and a flag set once ASKING was sent on the connection.
+fn start_node<F>(handler: F) -> u16
+where
+    F: Fn(&[String], bool) -> String + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handler = Arc::new(handler);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let handler = handler.clone();
+
+            thread::spawn(move || {
+                let mut stream: TcpStream = stream.unwrap();
+                let mut asking = false;
+
+                loop {
+                    let request = match redis::Parser::new(&mut stream).parse_value() {
+                        Ok(request) => request,
+                        Err(_) => break,
+                    };
+                    let args: Vec<String> = redis::from_redis_value(&request).unwrap();
+
+                    let response = if args[0] == "ASKING" {
+                        asking = true;
+                        "+OK\r\n".to_string()
+                    } else {
+                        let response = handler(&args, asking);
+                        asking = false;
+                        response
+                    };
+
+                    if stream.write_all(response.as_bytes()).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+
+    port
+}
+
+fn create_slots_response(port: u16) -> String {
+    format!(
+        "*1\r\n*3\r\n:0\r\n:16383\r\n*2\r\n$9\r\n127.0.0.1\r\n:{}\r\n",
+        port
+    )
+}
+
+fn create_bulk_response(value: &str) -> String {
+    format!("${}\r\n{}\r\n", value.len(), value)
+}
+
+#[test]
+fn crc16_checksum() {
+    assert_eq!(crc16(b"123456789"), 0x31C3);
+    assert_eq!(crc16(b""), 0);
+}
+
+#[test]
+fn get_key_slot_no_hashtag() {
+    assert_eq!(get_key_slot(b"foo"), 12182);
+    assert_eq!(get_key_slot(b"123456789"), 12739);
+}
+
+#[test]
+fn get_key_slot_hashtag() {
+    assert_eq!(
+        get_key_slot(b"{user1000}.following"),
+        get_key_slot(b"user1000")
+    );
+    assert_eq!(
+        get_key_slot(b"{user1000}.following"),
+        get_key_slot(b"{user1000}.followers")
+    );
+    assert_eq!(get_key_slot(b"foo{bar}{zap}"), get_key_slot(b"bar"));
+    assert_eq!(get_key_slot(b"foo{{bar}}zap"), get_key_slot(b"{bar"));
+}
+
+#[test]
+fn get_key_slot_empty_hashtag() {
+    assert_eq!(get_key_slot(b"foo{}{bar}"), crc16(b"foo{}{bar}") % 16384);
+    assert_eq!(get_key_slot(b"foo{bar"), crc16(b"foo{bar") % 16384);
+}
+
+#[test]
+fn get_command_keys_layouts() {
+    assert!(get_command_keys("PING", &Vec::<&str>::new()).is_empty());
+    assert!(get_command_keys("scan", &vec!["0"]).is_empty());
+    assert_eq!(get_command_keys("GET", &vec!["a"]), vec![b"a"]);
+    assert_eq!(get_command_keys("SET", &vec!["a", "1"]), vec![b"a"]);
+    assert_eq!(get_command_keys("DEL", &vec!["a", "b"]), vec![b"a", b"b"]);
+    assert_eq!(
+        get_command_keys("MSET", &vec!["a", "1", "b", "2"]),
+        vec![b"a", b"b"]
+    );
+    assert_eq!(
+        get_command_keys("SMOVE", &vec!["a", "b", "m"]),
+        vec![b"a", b"b"]
+    );
+    assert_eq!(
+        get_command_keys("BLPOP", &vec!["a", "b", "0"]),
+        vec![b"a", b"b"]
+    );
+    assert_eq!(
+        get_command_keys(
+            "ZUNIONSTORE",
+            &vec!["d", "2", "a", "b", "WEIGHTS", "1", "2"]
+        ),
+        vec![b"d", b"a", b"b"]
+    );
+    assert_eq!(
+        get_command_keys("EVAL", &vec!["return 1", "1", "a", "arg"]),
+        vec![b"a"]
+    );
+    assert!(get_command_keys("EVALSHA", &vec!["sha", "0", "arg"]).is_empty());
+    assert_eq!(
+        get_command_keys("XREAD", &vec!["COUNT", "2", "STREAMS", "a", "b", "0", "0"]),
+        vec![b"a", b"b"]
+    );
+    assert_eq!(get_command_keys("XINFO", &vec!["STREAM", "a"]), vec![b"a"]);
+}
+
+#[test]
+fn get_command_slot_same_slot() {
+    let slot = get_command_slot("MGET", &vec!["{user}.a", "{user}.b"]).unwrap();
+    assert_eq!(slot, Some(get_key_slot(b"user")));
+
+    let slot = get_command_slot("PING", &Vec::<&str>::new()).unwrap();
+    assert!(slot.is_none());
+}
+
+#[test]
+fn get_command_slot_cross_slot() {
+    let error = get_command_slot("MGET", &vec!["a", "b"]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::CrossSlot);
+}
+
+#[test]
+fn get_command_slot_single_node_commands() {
+    for command in &["KEYS", "scan", "DBSIZE", "RANDOMKEY", "FLUSHALL", "FLUSHDB"] {
+        let error = get_command_slot(command, &vec!["*"]).unwrap_err();
+        assert_eq!(error.to_string(), SINGLE_NODE_COMMAND);
+    }
+}
+
+#[test]
+fn get_redirection_valid() {
+    let error = RedisError {
+        info: ErrorInfo::RedisError(
+            redis::parse_redis_value(b"-MOVED 3999 127.0.0.1:6381\r\n").unwrap_err(),
+        ),
+    };
+
+    let redirection = get_redirection(&error).unwrap();
+    assert_eq!(redirection, (3999, ("127.0.0.1".to_string(), 6381)));
+}
+
+#[test]
+fn get_redirection_invalid() {
+    let error = RedisError {
+        info: ErrorInfo::RedisError(redis::parse_redis_value(b"-MOVED 3999\r\n").unwrap_err()),
+    };
+
+    assert!(get_redirection(&error).is_none());
+
+    let error = RedisError {
+        info: ErrorInfo::RedisError(
+            redis::parse_redis_value(b"-ERR 3999 127.0.0.1:6381\r\n").unwrap_err(),
+        ),
+    };
+
+    assert!(get_redirection(&error).is_none());
+}
+
+#[test]
+fn should_refresh_rate_limited() {
+    let mut connections = create_connections(
+        vec![("127.0.0.1".to_string(), 1)],
+        None,
+        RetryPolicy::none(),
+        connection::ConnectionOptions::default(),
+        None,
+    );
+    assert!(connections.should_refresh());
+
+    // the only node is unreachable
+    assert!(connections.refresh_slots().is_err());
+    assert!(!connections.should_refresh());
+
+    connections.last_refresh =
+        Some(Instant::now() - Duration::from_millis(MIN_REFRESH_INTERVAL_MILLIS));
+    assert!(connections.should_refresh());
+}
+
+#[test]
+fn parse_cluster_slots_unknown_host() {
+    let ranges: Vec<Vec<redis::Value>> = redis::from_redis_value(
+        &redis::parse_redis_value(
+            b"*2\r\n*3\r\n:0\r\n:100\r\n*2\r\n$8\r\n10.0.0.1\r\n:7000\r\n*4\r\n:101\r\n:16383\r\n*2\r\n$0\r\n\r\n:7001\r\n*2\r\n$8\r\n10.0.0.3\r\n:7002\r\n",
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    let output = parse_cluster_slots(ranges, "10.0.0.2").unwrap();
+    assert_eq!(
+        output,
+        vec![
+            (0, 100, ("10.0.0.1".to_string(), 7000)),
+            (101, 16383, ("10.0.0.2".to_string(), 7001)),
+        ]
+    );
+}
+
+#[test]
+fn parse_cluster_shards_masters() {
+    let shards: Vec<HashMap<String, redis::Value>> = redis::from_redis_value(
+        &redis::parse_redis_value(
+            b"*1\r\n*4\r\n$5\r\nslots\r\n*4\r\n:0\r\n:10\r\n:20\r\n:30\r\n$5\r\nnodes\r\n*2\r\n*6\r\n$2\r\nip\r\n$8\r\n10.0.0.1\r\n$4\r\nport\r\n:7000\r\n$4\r\nrole\r\n$7\r\nreplica\r\n*6\r\n$2\r\nip\r\n$8\r\n10.0.0.2\r\n$4\r\nport\r\n:7001\r\n$4\r\nrole\r\n$6\r\nmaster\r\n",
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    let output = parse_cluster_shards(shards, "10.0.0.5").unwrap();
+    assert_eq!(
+        output,
+        vec![
+            (0, 10, ("10.0.0.2".to_string(), 7001)),
+            (20, 30, ("10.0.0.2".to_string(), 7001)),
+        ]
+    );
+}
+
+#[test]
+fn slot_map_set_get() {
+    let mut slot_map = SlotMap::from_ranges(vec![(0, 9, ("a".to_string(), 1))]);
+    assert!(!slot_map.is_empty());
+    assert_eq!(slot_map.get(5), Some(&("a".to_string(), 1)));
+    assert!(slot_map.get(10).is_none());
+
+    slot_map.set(5, ("b".to_string(), 2));
+    assert_eq!(slot_map.get(5), Some(&("b".to_string(), 2)));
+    assert_eq!(slot_map.get(4), Some(&("a".to_string(), 1)));
+    assert_eq!(slot_map.addresses.len(), 2);
+}
+
+#[test]
+fn create_no_nodes() {
+    assert!(create(vec![]).is_err());
+}
+
+#[test]
+fn create_invalid_node() {
+    assert!(create(vec!["test/bad/url"]).is_err());
+}
+
+#[test]
+fn create_valid() {
+    let client = create(vec!["redis://127.0.0.1:7000", "redis://127.0.0.1:7001"]).unwrap();
+    assert!(!client.is_connection_open());
+}
+
+#[test]
+fn run_command_cross_slot() {
+    let mut client = create(vec!["redis://127.0.0.1:7000"]).unwrap();
+
+    let error = client
+        .run_command::<Vec<String>>("MGET", vec!["a", "b"])
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::CrossSlot);
+}
+
+#[test]
+fn run_command_moved() {
+    let migrated = Arc::new(AtomicBool::new(false));
+    let redirected = Arc::new(AtomicUsize::new(0));
+    let source_port_holder = Arc::new(Mutex::new(0));
+
+    let target_port = start_node(|args, _| match args[0].as_str() {
+        "GET" => create_bulk_response("moved_value"),
+        _ => "+OK\r\n".to_string(),
+    });
+
+    let node_migrated = migrated.clone();
+    let source_holder = source_port_holder.clone();
+    let node_redirected = redirected.clone();
+    let source_port = start_node(move |args, _| match args[0].as_str() {
+        "CLUSTER" if node_migrated.load(Ordering::SeqCst) => create_slots_response(target_port),
+        "CLUSTER" => create_slots_response(*source_holder.lock().unwrap()),
+        "GET" => {
+            node_migrated.store(true, Ordering::SeqCst);
+            node_redirected.fetch_add(1, Ordering::SeqCst);
+            format!(
+                "-MOVED {} 127.0.0.1:{}\r\n",
+                get_key_slot(b"my_key"),
+                target_port
+            )
+        }
+        _ => "+OK\r\n".to_string(),
+    });
+    *source_port_holder.lock().unwrap() = source_port;
+
+    let mut client = create(vec![&format!("redis://127.0.0.1:{}", source_port)]).unwrap();
+
+    let value = client.get_string("my_key").unwrap();
+    assert_eq!(value, "moved_value");
+    assert!(migrated.load(Ordering::SeqCst));
+
+    // the slot map is reloaded and the command is sent directly to the new node
+    let value = client.get_string("my_key").unwrap();
+    assert_eq!(value, "moved_value");
+    assert_eq!(redirected.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn get_generation_increases() {
+    let migrated = Arc::new(AtomicBool::new(false));
+    let source_port_holder = Arc::new(Mutex::new(0));
+
+    let target_port = start_node(|args, _| match args[0].as_str() {
+        "GET" => create_bulk_response("moved_value"),
+        _ => "+OK\r\n".to_string(),
+    });
+
+    let node_migrated = migrated.clone();
+    let source_holder = source_port_holder.clone();
+    let source_port = start_node(move |args, _| match args[0].as_str() {
+        "CLUSTER" if node_migrated.load(Ordering::SeqCst) => create_slots_response(target_port),
+        "CLUSTER" => create_slots_response(*source_holder.lock().unwrap()),
+        "GET" if node_migrated.load(Ordering::SeqCst) => format!(
+            "-MOVED {} 127.0.0.1:{}\r\n",
+            get_key_slot(b"my_key"),
+            target_port
+        ),
+        "GET" => create_bulk_response("value"),
+        _ => "+OK\r\n".to_string(),
+    });
+    *source_port_holder.lock().unwrap() = source_port;
+
+    let mut connections = create_connections(
+        vec![("127.0.0.1".to_string(), source_port)],
+        None,
+        RetryPolicy::default(),
+        connection::ConnectionOptions::default(),
+        None,
+    );
+    let slot = Some(get_key_slot(b"my_key"));
+    let get = |connections: &mut ClusterConnections| -> String {
+        connections
+            .run(slot, true, None, |connection| {
+                redis::cmd("GET")
+                    .arg("my_key")
+                    .query(connection)
+                    .map_err(|error| RedisError {
+                        info: ErrorInfo::RedisError(error),
+                    })
+            })
+            .unwrap()
+    };
+
+    let mut generations = vec![connections.get_generation()];
+
+    assert_eq!(get(&mut connections), "value");
+    generations.push(connections.get_generation());
+
+    // the redirection moves the command to a new node
+    migrated.store(true, Ordering::SeqCst);
+    assert_eq!(get(&mut connections), "moved_value");
+    generations.push(connections.get_generation());
+
+    // the reload drops the source node, which must not bring the generation back to a previous value
+    connections.last_refresh = None;
+    connections.refresh_slots().unwrap();
+    assert_eq!(connections.nodes.len(), 1);
+    generations.push(connections.get_generation());
+
+    for pair in generations.windows(2) {
+        assert!(pair[0] < pair[1]);
+    }
+}
+
+#[test]
+fn run_command_ask() {
+    let source_port_holder = Arc::new(Mutex::new(0));
+
+    let target_holder = source_port_holder.clone();
+    let target_port = start_node(move |args, asking| match args[0].as_str() {
+        "GET" if asking => create_bulk_response("ask_value"),
+        "GET" => format!(
+            "-MOVED {} 127.0.0.1:{}\r\n",
+            get_key_slot(b"my_key"),
+            *target_holder.lock().unwrap()
+        ),
+        _ => "+OK\r\n".to_string(),
+    });
+
+    let source_port = start_node(move |args, _| match args[0].as_str() {
+        "GET" => format!(
+            "-ASK {} 127.0.0.1:{}\r\n",
+            get_key_slot(b"my_key"),
+            target_port
+        ),
+        _ => "+OK\r\n".to_string(),
+    });
+    *source_port_holder.lock().unwrap() = source_port;
+
+    let mut client = create(vec![&format!("redis://127.0.0.1:{}", source_port)]).unwrap();
+
+    let value = client.get_string("my_key").unwrap();
+    assert_eq!(value, "ask_value");
+}
+
+#[test]
+fn pipeline_not_supported() {
+    let mut client = create(vec!["redis://127.0.0.1:7000"]).unwrap();
+
+    let result: RedisResult<Vec<String>> = client.pipeline().echo("test").execute();
+    assert!(result.is_err());
+}
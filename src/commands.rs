@@ -8,9 +8,305 @@
 mod commands_test;
 
 use client::Client;
+use hash::RedisHash;
+use redis;
+use scan;
+use scan::ScanIterator;
 use std::collections::HashMap;
 use std::str::FromStr;
-use types::{RedisArg, RedisBoolResult, RedisEmptyResult, RedisResult, RedisStringResult};
+use std::time::Duration;
+use stream;
+use stream::StreamGroupReader;
+use types;
+use types::{
+    ErrorInfo, ExpireCondition, FunctionInfo, FunctionLibrary, FunctionRestorePolicy,
+    HashFieldExpireStatus, HashFieldPersistStatus, HashFieldTtl, LexBound, ListDirection, RedisArg,
+    RedisBinaryArg, RedisBoolResult, RedisEmptyResult, RedisError, RedisResult, RedisStringResult,
+    ScanOptions, ScoreBound, SetOptions, SetResult, StreamAutoClaimResult, StreamEntry,
+    StreamPendingEntry, StreamPendingSummary, StreamReadOptions, StreamTrim, ZAddOptions,
+};
+
+/// Replaces the hash stored in KEYS[1] with the (field, value) pairs in ARGV, setting one field at a time as unpacking
+/// all arguments at once is limited by the lua stack size
+const HSET_STRUCT_SCRIPT: &'static str = "redis.call('DEL', KEYS[1]) \
+    for index = 1, #ARGV, 2 do redis.call('HSET', KEYS[1], ARGV[index], ARGV[index + 1]) end \
+    return #ARGV / 2";
+
+fn to_members(values: Vec<String>) -> RedisResult<Vec<String>> {
+    Ok(values)
+}
+
+/// Converts every value on its own, as converting the entire list using redis-rs silently drops values which can't
+/// be converted (for example binary values), which would pair the rest of the values with the wrong fields, members
+/// or keys.
+pub(crate) fn from_values<T: redis::FromRedisValue>(
+    values: &[redis::Value],
+) -> RedisResult<Vec<T>> {
+    values
+        .iter()
+        .map(|value| match redis::from_redis_value(value) {
+            Ok(value) => Ok(value),
+            Err(error) => Err(RedisError {
+                info: ErrorInfo::RedisError(error),
+            }),
+        })
+        .collect()
+}
+
+pub(crate) fn to_field_values(values: Vec<String>) -> RedisResult<Vec<(String, String)>> {
+    let mut output = vec![];
+
+    let mut iter = values.into_iter();
+    while let Some(field) = iter.next() {
+        match iter.next() {
+            Some(value) => output.push((field, value)),
+            None => {
+                return Err(RedisError {
+                    info: ErrorInfo::Description(types::PARSE_FAILED),
+                })
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+fn to_scored_members(values: Vec<String>) -> RedisResult<Vec<(String, f64)>> {
+    let mut output = vec![];
+
+    let mut iter = values.into_iter();
+    while let Some(member) = iter.next() {
+        match iter.next() {
+            Some(score) => match f64::from_str(&score) {
+                Ok(score_value) => output.push((member, score_value)),
+                _ => {
+                    return Err(RedisError {
+                        info: ErrorInfo::Description(types::PARSE_FAILED),
+                    })
+                }
+            },
+            None => {
+                return Err(RedisError {
+                    info: ErrorInfo::Description(types::PARSE_FAILED),
+                })
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+fn create_range_args(
+    key: &str,
+    min: String,
+    max: String,
+    withscores: bool,
+    limit: Option<(isize, isize)>,
+) -> Vec<String> {
+    let mut args = vec![key.to_string(), min, max];
+
+    if withscores {
+        args.push("WITHSCORES".to_string());
+    }
+
+    if let Some((offset, count)) = limit {
+        args.push("LIMIT".to_string());
+        args.push(offset.to_string());
+        args.push(count.to_string());
+    }
+
+    args
+}
+
+fn create_script_args<K: RedisBinaryArg, A: RedisBinaryArg>(
+    script: &str,
+    keys: &[K],
+    args: &[A],
+) -> Vec<Vec<u8>> {
+    let mut output = vec![
+        script.as_bytes().to_vec(),
+        keys.len().to_string().into_bytes(),
+    ];
+
+    output.extend(keys.iter().map(|key| key.to_bytes()));
+    output.extend(args.iter().map(|arg| arg.to_bytes()));
+
+    output
+}
+
+fn get_map_value<T: redis::FromRedisValue>(
+    map: &HashMap<String, redis::Value>,
+    field: &str,
+) -> RedisResult<T> {
+    match map.get(field) {
+        Some(value) => match redis::from_redis_value(value) {
+            Ok(output) => Ok(output),
+            Err(error) => Err(RedisError {
+                info: ErrorInfo::RedisError(error),
+            }),
+        },
+        None => Err(RedisError {
+            info: ErrorInfo::Description(types::PARSE_FAILED),
+        }),
+    }
+}
+
+fn to_function_libraries(
+    values: Vec<HashMap<String, redis::Value>>,
+) -> RedisResult<Vec<FunctionLibrary>> {
+    let mut output = vec![];
+
+    for library in values {
+        let mut functions = vec![];
+        for function in get_map_value::<Vec<HashMap<String, redis::Value>>>(&library, "functions")?
+        {
+            functions.push(FunctionInfo {
+                name: get_map_value(&function, "name")?,
+                description: get_map_value(&function, "description")?,
+                flags: get_map_value(&function, "flags")?,
+            });
+        }
+
+        let code = if library.contains_key("library_code") {
+            get_map_value(&library, "library_code")?
+        } else {
+            None
+        };
+
+        output.push(FunctionLibrary {
+            name: get_map_value(&library, "library_name")?,
+            engine: get_map_value(&library, "engine")?,
+            functions,
+            code,
+        });
+    }
+
+    Ok(output)
+}
+
+/// Returns the library name defined in the library code shebang line (for example: `#!lua name=mylib`).
+fn get_library_name(code: &str) -> Option<&str> {
+    match code.lines().next() {
+        Some(line) if line.starts_with("#!") => line
+            .split_whitespace()
+            .filter(|token| token.starts_with("name="))
+            .map(|token| &token["name=".len()..])
+            .next(),
+        _ => None,
+    }
+}
+
+fn create_streams_args(streams: Vec<(&str, &str)>) -> Vec<String> {
+    let mut args = vec!["STREAMS".to_string()];
+
+    let (keys, ids): (Vec<&str>, Vec<&str>) = streams.into_iter().unzip();
+    args.extend(keys.into_iter().map(|key| key.to_string()));
+    args.extend(ids.into_iter().map(|id| id.to_string()));
+
+    args
+}
+
+fn create_key_value_args<'a, T: RedisBinaryArg, I: IntoIterator<Item = (&'a str, T)>>(
+    pairs: I,
+) -> Vec<Vec<u8>> {
+    let mut args = vec![];
+
+    for (key, value) in pairs {
+        args.push(key.as_bytes().to_vec());
+        args.push(value.to_bytes());
+    }
+
+    args
+}
+
+/// Returns the SET command result.<br>
+/// Without the GET option, redis replies OK once written and nil otherwise.<br>
+/// With the GET option, the previous value (or nil) is returned instead, so whether the value was written depends
+/// on the NX/XX condition.
+fn to_set_result(output: Option<Vec<u8>>, options: &SetOptions) -> SetResult {
+    if options.get {
+        let written = if options.nx {
+            output.is_none()
+        } else if options.xx {
+            output.is_some()
+        } else {
+            true
+        };
+
+        SetResult {
+            written,
+            previous: output,
+        }
+    } else {
+        SetResult {
+            written: output.is_some(),
+            previous: None,
+        }
+    }
+}
+
+fn to_typed_value<T: FromStr>(value: String) -> RedisResult<T> {
+    match T::from_str(&value) {
+        Ok(typed_value) => Ok(typed_value),
+        _ => Err(RedisError {
+            info: ErrorInfo::Description(types::PARSE_FAILED),
+        }),
+    }
+}
+
+fn to_optional_typed_values<T: FromStr>(values: Vec<redis::Value>) -> RedisResult<Vec<Option<T>>> {
+    from_values::<Option<String>>(&values)?
+        .into_iter()
+        .map(|value| match value {
+            Some(value) => Ok(Some(to_typed_value(value)?)),
+            None => Ok(None),
+        })
+        .collect()
+}
+
+/// Converts the per field replies of the hash field expiration commands using the provided conversion function.
+fn to_field_statuses<T>(values: Vec<i64>, convert: fn(i64) -> Option<T>) -> RedisResult<Vec<T>> {
+    values
+        .into_iter()
+        .map(|value| match convert(value) {
+            Some(status) => Ok(status),
+            None => Err(RedisError {
+                info: ErrorInfo::Description(types::PARSE_FAILED),
+            }),
+        })
+        .collect()
+}
+
+fn create_hash_fields_args(key: &str, options: Vec<String>, fields: Vec<&str>) -> Vec<String> {
+    let mut args = vec![key.to_string()];
+    args.extend(options);
+    args.push("FIELDS".to_string());
+    args.push(fields.len().to_string());
+    args.extend(fields.into_iter().map(|field| field.to_string()));
+
+    args
+}
+
+/// Returns the blocking commands timeout argument, which is defined in seconds (a zero timeout blocks forever).<br>
+/// The timeout is rounded up to whole milliseconds, so a non zero timeout never turns into blocking forever.<br>
+/// Whole seconds are sent as integers, as fractional timeouts are only supported by redis 6.0 and above.
+fn to_block_timeout_arg(timeout: Duration) -> String {
+    let millis = (timeout.as_nanos() + 999_999) / 1_000_000;
+    let (seconds, millis) = (millis / 1000, millis % 1000);
+
+    if millis == 0 {
+        seconds.to_string()
+    } else {
+        format!("{}.{:03}", seconds, millis)
+    }
+}
+
+fn create_blocking_pop_args(keys: Vec<&str>, timeout: Duration) -> Vec<String> {
+    let mut args: Vec<String> = keys.into_iter().map(|key| key.to_string()).collect();
+    args.push(to_block_timeout_arg(timeout));
+
+    args
+}
 
 /// Defines the redis commands exposed by the redis client.
 impl Client {
@@ -71,6 +367,24 @@ impl Client {
         self.run_command_from_string_response("GET", vec![key])
     }
 
+    /// See redis [GET](https://redis.io/commands/get) command.<br>
+    /// Unlike the get function, a missing key is returned as None instead of an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// match client.get_opt::<i64>("my_key") {
+    ///     Ok(Some(value)) => println!("Read value from Redis: {}", value),
+    ///     Ok(None) => println!("Key not found"),
+    ///     Err(error) => println!("Unable to get value from Redis: {}", error)
+    /// }
+    /// ```
+    ///
+    pub fn get_opt<T: FromStr>(self: &mut Client, key: &str) -> RedisResult<Option<T>> {
+        self.run_command_from_optional_string_response("GET", vec![key])
+    }
+
     /// See redis [GET](https://redis.io/commands/get) command.<br>
     /// This function will always return a String response.
     ///
@@ -88,6 +402,27 @@ impl Client {
         self.run_command_string_response("GET", vec![key])
     }
 
+    /// See redis [GET](https://redis.io/commands/get) command.<br>
+    /// This function returns the raw value bytes, without any string conversion, or None in case of a nil response.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// let value: &[u8] = &[0, 1, 2, 255];
+    /// client.set("my_binary_key", value).unwrap();
+    ///
+    /// match client.get_bytes("my_binary_key") {
+    ///     Ok(Some(value)) => println!("Read {} bytes from Redis", value.len()),
+    ///     Ok(None) => println!("Value not found in Redis"),
+    ///     Err(error) => println!("Unable to get value from Redis: {}", error)
+    /// }
+    /// ```
+    ///
+    pub fn get_bytes(self: &mut Client, key: &str) -> RedisResult<Option<Vec<u8>>> {
+        self.run_command::<Option<Vec<u8>>>("GET", vec![key])
+    }
+
     /// See redis [SET](https://redis.io/commands/set) command.
     ///
     /// # Example
@@ -100,8 +435,104 @@ impl Client {
     /// }
     /// ```
     ///
-    pub fn set<T: RedisArg>(self: &mut Client, key: &str, value: T) -> RedisEmptyResult {
-        self.run_command_empty_response("SET", vec![key, &value.to_string()])
+    pub fn set<T: RedisBinaryArg>(self: &mut Client, key: &str, value: T) -> RedisEmptyResult {
+        self.run_binary_command::<()>("SET", vec![key.as_bytes(), &value.to_bytes()])
+    }
+
+    /// See redis [MGET](https://redis.io/commands/mget) command.<br>
+    /// Returns the values in the same order as the provided keys, with None for missing keys.<br>
+    /// In case any of the values can not be converted (for example binary values), an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// match client.mget::<i64>(vec!["my_key1", "my_key2"]) {
+    ///     Ok(values) => println!("Read values from Redis: {:?}", values),
+    ///     Err(error) => println!("Unable to get values from Redis: {}", error)
+    /// }
+    /// ```
+    ///
+    pub fn mget<T: FromStr>(self: &mut Client, keys: Vec<&str>) -> RedisResult<Vec<Option<T>>> {
+        let values = self.run_command::<Vec<redis::Value>>("MGET", keys)?;
+
+        to_optional_typed_values(values)
+    }
+
+    /// See redis [MSET](https://redis.io/commands/mset) command.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// match client.mset(vec![("my_key1", 1), ("my_key2", 2)]) {
+    ///     Err(error) => println!("Unable to set values in Redis: {}", error),
+    ///     _ => println!("Values set in Redis")
+    /// }
+    /// ```
+    ///
+    pub fn mset<'a, T: RedisBinaryArg, I: IntoIterator<Item = (&'a str, T)>>(
+        self: &mut Client,
+        pairs: I,
+    ) -> RedisEmptyResult {
+        self.run_binary_command::<()>("MSET", create_key_value_args(pairs))
+    }
+
+    /// See redis [MSETNX](https://redis.io/commands/msetnx) command.<br>
+    /// Returns true in case all the keys were set, or false in case none were set as at least one key already
+    /// exists.
+    pub fn msetnx<'a, T: RedisBinaryArg, I: IntoIterator<Item = (&'a str, T)>>(
+        self: &mut Client,
+        pairs: I,
+    ) -> RedisBoolResult {
+        self.run_binary_command::<bool>("MSETNX", create_key_value_args(pairs))
+    }
+
+    /// See redis [SET](https://redis.io/commands/set) command.<br>
+    /// Sets the value with the provided expiration and condition options, returning whether the value was written
+    /// and (if the GET option is set) the previous value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// let options = simple_redis::types::SetOptions {
+    ///     expiration: Some(simple_redis::types::SetExpiration::Millis(30000)),
+    ///     nx: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// match client.set_with("my_lock", "owner", options) {
+    ///     Ok(result) if result.written => println!("Lock acquired"),
+    ///     Ok(_) => println!("Lock already held"),
+    ///     Err(error) => println!("Unable to set value in Redis: {}", error)
+    /// }
+    /// ```
+    ///
+    pub fn set_with<T: RedisBinaryArg>(
+        self: &mut Client,
+        key: &str,
+        value: T,
+        options: SetOptions,
+    ) -> RedisResult<SetResult> {
+        let mut args = vec![key.as_bytes().to_vec(), value.to_bytes()];
+        args.extend(options.to_args().into_iter().map(|arg| arg.into_bytes()));
+
+        // the reply is not converted to a string, so a binary previous value can't fail the call once the value
+        // was already written
+        let output = match self.run_binary_command::<redis::Value>("SET", args)? {
+            redis::Value::Nil => None,
+            redis::Value::Okay => Some(b"OK".to_vec()),
+            redis::Value::Status(status) => Some(status.into_bytes()),
+            redis::Value::Data(data) => Some(data),
+            _ => {
+                return Err(RedisError {
+                    info: ErrorInfo::Description(types::PARSE_FAILED),
+                })
+            }
+        };
+
+        Ok(to_set_result(output, &options))
     }
 
     /// See redis [SETEX](https://redis.io/commands/setex) command.
@@ -116,26 +547,51 @@ impl Client {
     /// }
     /// ```
     ///
-    pub fn setex<T: RedisArg>(&mut self, key: &str, value: T, seconds: usize) -> RedisEmptyResult {
-        self.run_command_empty_response(
+    pub fn setex<T: RedisBinaryArg>(
+        &mut self,
+        key: &str,
+        value: T,
+        seconds: usize,
+    ) -> RedisEmptyResult {
+        self.run_binary_command::<()>(
             "SETEX",
-            vec![key, &*seconds.to_string(), &value.to_string()],
+            vec![
+                key.as_bytes(),
+                seconds.to_string().as_bytes(),
+                &value.to_bytes(),
+            ],
         )
     }
 
     /// See redis [SETNX](https://redis.io/commands/setnx) command.
-    pub fn setnx<T: RedisArg>(&mut self, key: &str, value: T) -> RedisEmptyResult {
-        self.run_command_empty_response("SETNX", vec![key, &value.to_string()])
+    pub fn setnx<T: RedisBinaryArg>(&mut self, key: &str, value: T) -> RedisEmptyResult {
+        self.run_binary_command::<()>("SETNX", vec![key.as_bytes(), &value.to_bytes()])
     }
 
     /// See redis [GETSET](https://redis.io/commands/getset) command.
-    pub fn getset<T: RedisArg, V: FromStr>(&mut self, key: &str, value: T) -> RedisResult<V> {
-        self.run_command_from_string_response::<V>("GETSET", vec![key, &value.to_string()])
+    pub fn getset<T: RedisBinaryArg, V: FromStr>(&mut self, key: &str, value: T) -> RedisResult<V> {
+        self.run_binary_command_from_string_response::<V>(
+            "GETSET",
+            vec![key.as_bytes(), &value.to_bytes()],
+        )
     }
 
     /// See redis [GETSET](https://redis.io/commands/getset) command.
-    pub fn getset_string<T: RedisArg>(&mut self, key: &str, value: T) -> RedisStringResult {
-        self.run_command_string_response("GETSET", vec![key, &value.to_string()])
+    pub fn getset_string<T: RedisBinaryArg>(&mut self, key: &str, value: T) -> RedisStringResult {
+        self.run_binary_command::<String>("GETSET", vec![key.as_bytes(), &value.to_bytes()])
+    }
+
+    /// See redis [GETSET](https://redis.io/commands/getset) command.<br>
+    /// Returns None in case the key did not exist.
+    pub fn getset_opt<T: RedisBinaryArg, V: FromStr>(
+        &mut self,
+        key: &str,
+        value: T,
+    ) -> RedisResult<Option<V>> {
+        self.run_binary_command_from_optional_string_response::<V>(
+            "GETSET",
+            vec![key.as_bytes(), &value.to_bytes()],
+        )
     }
 
     /// See redis [DEL](https://redis.io/commands/del) command.
@@ -143,11 +599,35 @@ impl Client {
         self.run_command_empty_response("DEL", vec![key])
     }
 
+    /// See redis [DEL](https://redis.io/commands/del) command.<br>
+    /// Returns the amount of deleted keys.
+    pub fn del_keys(&mut self, keys: Vec<&str>) -> RedisResult<i32> {
+        self.run_command::<i32>("DEL", keys)
+    }
+
+    /// See redis [UNLINK](https://redis.io/commands/unlink) command.<br>
+    /// Returns the amount of unlinked keys (the memory is reclaimed in the background).
+    pub fn unlink(&mut self, keys: Vec<&str>) -> RedisResult<i32> {
+        self.run_command::<i32>("UNLINK", keys)
+    }
+
     /// See redis [EXISTS](https://redis.io/commands/exists) command.
     pub fn exists(&mut self, key: &str) -> RedisBoolResult {
         self.run_command_bool_response("EXISTS", vec![key])
     }
 
+    /// See redis [EXISTS](https://redis.io/commands/exists) command.<br>
+    /// Returns the amount of existing keys (a key provided multiple times is counted multiple times).
+    pub fn exists_keys(&mut self, keys: Vec<&str>) -> RedisResult<i32> {
+        self.run_command::<i32>("EXISTS", keys)
+    }
+
+    /// See redis [TOUCH](https://redis.io/commands/touch) command.<br>
+    /// Returns the amount of touched keys.
+    pub fn touch(&mut self, keys: Vec<&str>) -> RedisResult<i32> {
+        self.run_command::<i32>("TOUCH", keys)
+    }
+
     /// See redis [EXPIRE](https://redis.io/commands/expire) command.
     pub fn expire(&mut self, key: &str, seconds: usize) -> RedisEmptyResult {
         self.run_command_empty_response("EXPIRE", vec![key, &*seconds.to_string()])
@@ -185,12 +665,12 @@ impl Client {
 
     /// See redis [INCRBY](https://redis.io/commands/incrby) command.
     pub fn incrby<T: RedisArg>(&mut self, key: &str, value: T) -> RedisResult<i64> {
-        self.run_command::<i64>("INCRBY", vec![key, &*value.to_string()])
+        self.run_binary_command::<i64>("INCRBY", vec![key.as_bytes(), &value.to_bytes()])
     }
 
     /// See redis [INCRBYFLOAT](https://redis.io/commands/incrbyfloat) command.
     pub fn incrbyfloat<T: RedisArg>(&mut self, key: &str, value: T) -> RedisResult<f64> {
-        self.run_command::<f64>("INCRBYFLOAT", vec![key, &*value.to_string()])
+        self.run_binary_command::<f64>("INCRBYFLOAT", vec![key.as_bytes(), &value.to_bytes()])
     }
 
     /// See redis [STRLEN](https://redis.io/commands/strlen) command.
@@ -203,6 +683,29 @@ impl Client {
         self.run_command::<Vec<String>>("KEYS", vec![pattern])
     }
 
+    /// See redis [SCAN](https://redis.io/commands/scan) command.<br>
+    /// Returns an iterator over all keys, which fetches the next keys from redis as needed.<br>
+    /// Unlike the KEYS command, this does not block the redis server.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// let mut options = simple_redis::types::ScanOptions::default();
+    /// options.pattern = Some("my_prefix_*".to_string());
+    ///
+    /// for key in client.scan(options) {
+    ///     match key {
+    ///         Ok(key) => println!("Found key: {}", key),
+    ///         Err(error) => println!("Unable to scan keys: {}", error),
+    ///     }
+    /// }
+    /// ```
+    ///
+    pub fn scan(self: &mut Client, options: ScanOptions) -> ScanIterator<String> {
+        scan::create(self, "SCAN", None, options, to_members)
+    }
+
     /// See redis [HGET](https://redis.io/commands/hget) command.
     pub fn hget<T: FromStr>(self: &mut Client, key: &str, field: &str) -> RedisResult<T> {
         self.run_command_from_string_response("HGET", vec![key, field])
@@ -213,6 +716,22 @@ impl Client {
         self.run_command_string_response("HGET", vec![key, field])
     }
 
+    /// See redis [HGET](https://redis.io/commands/hget) command.<br>
+    /// Returns None in case the key or field do not exist.
+    pub fn hget_opt<T: FromStr>(
+        self: &mut Client,
+        key: &str,
+        field: &str,
+    ) -> RedisResult<Option<T>> {
+        self.run_command_from_optional_string_response("HGET", vec![key, field])
+    }
+
+    /// See redis [HGET](https://redis.io/commands/hget) command.<br>
+    /// This function returns the raw value bytes, without any string conversion, or None in case of a nil response.
+    pub fn hget_bytes(self: &mut Client, key: &str, field: &str) -> RedisResult<Option<Vec<u8>>> {
+        self.run_command::<Option<Vec<u8>>>("HGET", vec![key, field])
+    }
+
     /// See redis [HGETALL](https://redis.io/commands/hgetall) command.
     ///
     /// # Example
@@ -235,23 +754,29 @@ impl Client {
     }
 
     /// See redis [HSET](https://redis.io/commands/hset) command.
-    pub fn hset<T: RedisArg>(
+    pub fn hset<T: RedisBinaryArg>(
         self: &mut Client,
         key: &str,
         field: &str,
         value: T,
     ) -> RedisEmptyResult {
-        self.run_command_empty_response("HSET", vec![key, field, &value.to_string()])
+        self.run_binary_command::<()>(
+            "HSET",
+            vec![key.as_bytes(), field.as_bytes(), &value.to_bytes()],
+        )
     }
 
     /// See redis [HSETNX](https://redis.io/commands/hsetnx) command.
-    pub fn hsetnx<T: RedisArg>(
+    pub fn hsetnx<T: RedisBinaryArg>(
         self: &mut Client,
         key: &str,
         field: &str,
         value: T,
     ) -> RedisEmptyResult {
-        self.run_command_empty_response("HSETNX", vec![key, field, &value.to_string()])
+        self.run_binary_command::<()>(
+            "HSETNX",
+            vec![key.as_bytes(), field.as_bytes(), &value.to_bytes()],
+        )
     }
 
     /// See redis [HDEL](https://redis.io/commands/hdel) command.
@@ -274,123 +799,1582 @@ impl Client {
         self.run_command::<Vec<String>>("HVALS", vec![key])
     }
 
-    /// See redis [LSET](https://redis.io/commands/lset) command.
-    pub fn lset<T: RedisArg>(
+    /// See redis [HSET](https://redis.io/commands/hset) command.<br>
+    /// Returns the amount of fields which were added (fields which already exist are updated but not counted).
+    pub fn hset_fields<'a, T: RedisBinaryArg, I: IntoIterator<Item = (&'a str, T)>>(
         self: &mut Client,
         key: &str,
-        index: isize,
-        value: T,
-    ) -> RedisEmptyResult {
-        self.run_command_empty_response("LSET", vec![key, &index.to_string(), &value.to_string()])
+        fields: I,
+    ) -> RedisResult<i32> {
+        let mut args = vec![key.as_bytes().to_vec()];
+        args.extend(create_key_value_args(fields));
+
+        self.run_binary_command::<i32>("HSET", args)
     }
 
-    /// See redis [HGET](https://redis.io/commands/lindex) command.
-    pub fn lindex<T: FromStr>(self: &mut Client, key: &str, index: isize) -> RedisResult<T> {
-        self.run_command_from_string_response("LINDEX", vec![key, &index.to_string()])
+    /// See redis [HMGET](https://redis.io/commands/hmget) command.<br>
+    /// Returns the values in the same order as the provided fields, with None for missing fields.
+    pub fn hmget<T: FromStr>(
+        self: &mut Client,
+        key: &str,
+        fields: Vec<&str>,
+    ) -> RedisResult<Vec<Option<T>>> {
+        let mut args = vec![key];
+        args.extend(fields);
+
+        let values = self.run_command::<Vec<redis::Value>>("HMGET", args)?;
+        to_optional_typed_values(values)
     }
 
-    /// See redis [HGET](https://redis.io/commands/lindex) command.
-    pub fn lindex_string(self: &mut Client, key: &str, index: isize) -> RedisStringResult {
-        self.run_command_string_response("LINDEX", vec![key, &index.to_string()])
+    /// See redis [HDEL](https://redis.io/commands/hdel) command.<br>
+    /// Returns the amount of removed fields.
+    pub fn hdel_fields(self: &mut Client, key: &str, fields: Vec<&str>) -> RedisResult<i32> {
+        let mut args = vec![key];
+        args.extend(fields);
+
+        self.run_command::<i32>("HDEL", args)
     }
 
-    /// See redis [LLEN](https://redis.io/commands/llen) command.
-    pub fn llen(self: &mut Client, key: &str) -> RedisResult<i32> {
-        self.run_command::<i32>("LLEN", vec![key])
+    /// See redis [HGETDEL](https://redis.io/commands/hgetdel) command.<br>
+    /// Returns the values of the removed fields in the same order as the provided fields, with None for missing
+    /// fields.
+    pub fn hgetdel<T: FromStr>(
+        self: &mut Client,
+        key: &str,
+        fields: Vec<&str>,
+    ) -> RedisResult<Vec<Option<T>>> {
+        let args = create_hash_fields_args(key, vec![], fields);
+
+        let values = self.run_binary_command::<Vec<redis::Value>>("HGETDEL", args)?;
+        to_optional_typed_values(values)
     }
 
-    /// See redis [LPOP](https://redis.io/commands/lpop) command.
-    pub fn lpop<T: FromStr>(self: &mut Client, key: &str) -> RedisResult<T> {
-        self.run_command_from_string_response("LPOP", vec![key])
+    /// See redis [HINCRBY](https://redis.io/commands/hincrby) command.<br>
+    /// Returns the field value after the increment.
+    pub fn hincrby(self: &mut Client, key: &str, field: &str, increment: i64) -> RedisResult<i64> {
+        self.run_command::<i64>("HINCRBY", vec![key, field, &increment.to_string()])
     }
 
-    /// See redis [LPUSH](https://redis.io/commands/lpush) command.
-    pub fn lpush<T: RedisArg>(self: &mut Client, key: &str, value: T) -> RedisEmptyResult {
-        self.run_command_empty_response("LPUSH", vec![key, &value.to_string()])
+    /// See redis [HINCRBYFLOAT](https://redis.io/commands/hincrbyfloat) command.<br>
+    /// Returns the field value after the increment.
+    pub fn hincrbyfloat(
+        self: &mut Client,
+        key: &str,
+        field: &str,
+        increment: f64,
+    ) -> RedisResult<f64> {
+        self.run_command_from_string_response(
+            "HINCRBYFLOAT",
+            vec![key, field, &increment.to_string()],
+        )
     }
 
-    /// See redis [LPUSHX](https://redis.io/commands/lpushx) command.
-    pub fn lpushx<T: RedisArg>(self: &mut Client, key: &str, value: T) -> RedisEmptyResult {
-        self.run_command_empty_response("LPUSHX", vec![key, &value.to_string()])
+    /// See redis [HLEN](https://redis.io/commands/hlen) command.
+    pub fn hlen(self: &mut Client, key: &str) -> RedisResult<i32> {
+        self.run_command::<i32>("HLEN", vec![key])
     }
 
-    /// See redis [LRANGE](https://redis.io/commands/lrange) command.
-    pub fn lrange(
+    /// See redis [HSTRLEN](https://redis.io/commands/hstrlen) command.<br>
+    /// Returns 0 in case the key or field do not exist.
+    pub fn hstrlen(self: &mut Client, key: &str, field: &str) -> RedisResult<i32> {
+        self.run_command::<i32>("HSTRLEN", vec![key, field])
+    }
+
+    /// See redis [HRANDFIELD](https://redis.io/commands/hrandfield) command.<br>
+    /// Returns None in case the hash is empty.
+    pub fn hrandfield(self: &mut Client, key: &str) -> RedisResult<Option<String>> {
+        self.run_command::<Option<String>>("HRANDFIELD", vec![key])
+    }
+
+    /// See redis [HRANDFIELD](https://redis.io/commands/hrandfield) command.<br>
+    /// Returns up to count distinct random fields, or exactly count fields which may repeat in case count is
+    /// negative.
+    pub fn hrandfield_count(
         self: &mut Client,
         key: &str,
-        start: isize,
-        stop: isize,
+        count: isize,
     ) -> RedisResult<Vec<String>> {
-        self.run_command::<Vec<String>>("LRANGE", vec![key, &start.to_string(), &stop.to_string()])
+        self.run_command::<Vec<String>>("HRANDFIELD", vec![key, &count.to_string()])
     }
 
-    /// See redis [LREM](https://redis.io/commands/lrem) command.
-    pub fn lrem<T: RedisArg>(
+    /// See redis [HRANDFIELD](https://redis.io/commands/hrandfield) command.<br>
+    /// Same as hrandfield_count, but returns the fields together with their values.
+    pub fn hrandfield_withvalues(
         self: &mut Client,
         key: &str,
         count: isize,
-        value: T,
-    ) -> RedisEmptyResult {
-        self.run_command_empty_response("LREM", vec![key, &count.to_string(), &value.to_string()])
-    }
+    ) -> RedisResult<Vec<(String, String)>> {
+        let values = self.run_command::<Vec<redis::Value>>(
+            "HRANDFIELD",
+            vec![key, &count.to_string(), "WITHVALUES"],
+        )?;
 
-    /// See redis [LTRIM](https://redis.io/commands/ltrim) command.
-    pub fn ltrim(self: &mut Client, key: &str, start: isize, stop: isize) -> RedisEmptyResult {
-        self.run_command_empty_response("LTRIM", vec![key, &start.to_string(), &stop.to_string()])
+        to_field_values(from_values(&values)?)
     }
 
-    /// See redis [RPOP](https://redis.io/commands/rpop) command.
-    pub fn rpop<T: FromStr>(self: &mut Client, key: &str) -> RedisResult<T> {
-        self.run_command_from_string_response("RPOP", vec![key])
+    /// See redis [HEXPIRE](https://redis.io/commands/hexpire) command.<br>
+    /// Sets the expiration of the provided fields in seconds, returning the status of each field in the same order
+    /// as the provided fields.
+    pub fn hexpire(
+        self: &mut Client,
+        key: &str,
+        seconds: usize,
+        condition: Option<ExpireCondition>,
+        fields: Vec<&str>,
+    ) -> RedisResult<Vec<HashFieldExpireStatus>> {
+        self.run_hash_field_expire_command("HEXPIRE", key, seconds, condition, fields)
     }
 
-    /// See redis [RPUSH](https://redis.io/commands/rpush) command.
-    pub fn rpush<T: RedisArg>(self: &mut Client, key: &str, value: T) -> RedisEmptyResult {
-        self.run_command_empty_response("RPUSH", vec![key, &value.to_string()])
+    /// See redis [HPEXPIRE](https://redis.io/commands/hpexpire) command.<br>
+    /// Sets the expiration of the provided fields in milliseconds, returning the status of each field in the same
+    /// order as the provided fields.
+    pub fn hpexpire(
+        self: &mut Client,
+        key: &str,
+        millis: usize,
+        condition: Option<ExpireCondition>,
+        fields: Vec<&str>,
+    ) -> RedisResult<Vec<HashFieldExpireStatus>> {
+        self.run_hash_field_expire_command("HPEXPIRE", key, millis, condition, fields)
     }
 
-    /// See redis [RPUSHX](https://redis.io/commands/rpushx) command.
-    pub fn rpushx<T: RedisArg>(self: &mut Client, key: &str, value: T) -> RedisEmptyResult {
-        self.run_command_empty_response("RPUSHX", vec![key, &value.to_string()])
+    fn run_hash_field_expire_command(
+        self: &mut Client,
+        command: &str,
+        key: &str,
+        expiration: usize,
+        condition: Option<ExpireCondition>,
+        fields: Vec<&str>,
+    ) -> RedisResult<Vec<HashFieldExpireStatus>> {
+        let mut options = vec![expiration.to_string()];
+        if let Some(condition) = condition {
+            options.push(condition.to_arg().to_string());
+        }
+        let args = create_hash_fields_args(key, options, fields);
+
+        let values = self.run_binary_command::<Vec<i64>>(command, args)?;
+        to_field_statuses(values, HashFieldExpireStatus::from_reply)
     }
 
-    /// See redis [SADD](https://redis.io/commands/sadd) command.
-    pub fn sadd(self: &mut Client, key: &str, member: &str) -> RedisResult<i32> {
-        self.run_command::<i32>("SADD", vec![key, member])
+    /// See redis [HTTL](https://redis.io/commands/httl) command.<br>
+    /// Returns the remaining time to live in seconds of each field in the same order as the provided fields.
+    pub fn httl(self: &mut Client, key: &str, fields: Vec<&str>) -> RedisResult<Vec<HashFieldTtl>> {
+        let args = create_hash_fields_args(key, vec![], fields);
+
+        let values = self.run_binary_command::<Vec<i64>>("HTTL", args)?;
+        to_field_statuses(values, HashFieldTtl::from_reply)
     }
 
-    /// See redis [SCARD](https://redis.io/commands/scard) command.
-    pub fn scard(self: &mut Client, key: &str) -> RedisResult<i32> {
-        self.run_command::<i32>("SCARD", vec![key])
+    /// See redis [HPTTL](https://redis.io/commands/hpttl) command.<br>
+    /// Returns the remaining time to live in milliseconds of each field in the same order as the provided fields.
+    pub fn hpttl(
+        self: &mut Client,
+        key: &str,
+        fields: Vec<&str>,
+    ) -> RedisResult<Vec<HashFieldTtl>> {
+        let args = create_hash_fields_args(key, vec![], fields);
+
+        let values = self.run_binary_command::<Vec<i64>>("HPTTL", args)?;
+        to_field_statuses(values, HashFieldTtl::from_reply)
     }
 
-    /// See redis [SDIFF](https://redis.io/commands/sdiff) command.
-    pub fn sdiff(self: &mut Client, keys: Vec<&str>) -> RedisResult<Vec<String>> {
-        self.run_command::<Vec<String>>("SDIFF", keys)
+    /// See redis [HPERSIST](https://redis.io/commands/hpersist) command.<br>
+    /// Removes the expiration of the provided fields, returning the status of each field in the same order as the
+    /// provided fields.
+    pub fn hpersist(
+        self: &mut Client,
+        key: &str,
+        fields: Vec<&str>,
+    ) -> RedisResult<Vec<HashFieldPersistStatus>> {
+        let args = create_hash_fields_args(key, vec![], fields);
+
+        let values = self.run_binary_command::<Vec<i64>>("HPERSIST", args)?;
+        to_field_statuses(values, HashFieldPersistStatus::from_reply)
     }
 
-    /// See redis [SISMEMBER](https://redis.io/commands/sismember) command.
-    pub fn sismember(self: &mut Client, key: &str, member: &str) -> RedisBoolResult {
-        self.run_command("SISMEMBER", vec![key, member])
+    /// See redis [DEL](https://redis.io/commands/del) and [HSET](https://redis.io/commands/hset) commands.<br>
+    /// Replaces the hash with the provided struct fields (see hash::RedisHash), returning the amount of stored fields.
+    /// <br>
+    /// Existing hash fields which are not returned by the struct (for example optional fields set to None) are
+    /// removed, and in case the struct returns no fields, the hash is deleted.<br>
+    /// The hash is replaced atomically using a lua script, which requires redis 2.6 and above.
+    pub fn hset_struct<T: RedisHash>(self: &mut Client, key: &str, value: &T) -> RedisResult<i32> {
+        let fields = value.to_redis_hash();
+        let args = create_key_value_args(
+            fields
+                .iter()
+                .map(|&(ref field, ref value)| (field.as_str(), value.as_str())),
+        );
+
+        self.eval::<i32, &str, Vec<u8>>(HSET_STRUCT_SCRIPT, &[key], &args)
     }
 
-    /// See redis [SMEMBERS](https://redis.io/commands/smembers) command.
-    pub fn smembers(self: &mut Client, key: &str) -> RedisResult<Vec<String>> {
-        self.run_command::<Vec<String>>("SMEMBERS", vec![key])
+    /// See redis [HGETALL](https://redis.io/commands/hgetall) command.<br>
+    /// Returns the struct stored in the hash (see hash::RedisHash), or a nil error in case the hash does not exist.
+    pub fn hgetall_struct<T: RedisHash>(self: &mut Client, key: &str) -> RedisResult<T> {
+        match self.hgetall_struct_opt(key)? {
+            Some(value) => Ok(value),
+            None => Err(RedisError {
+                info: ErrorInfo::Description(types::HASH_NOT_FOUND),
+            }),
+        }
     }
 
-    /// See redis [SMOVE](https://redis.io/commands/smove) command.
-    pub fn smove(
+    /// See redis [HGETALL](https://redis.io/commands/hgetall) command.<br>
+    /// Returns the struct stored in the hash (see hash::RedisHash), or None in case the hash does not exist.
+    pub fn hgetall_struct_opt<T: RedisHash>(
+        self: &mut Client,
+        key: &str,
+    ) -> RedisResult<Option<T>> {
+        let fields = self.hgetall(key)?;
+
+        if fields.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(T::from_redis_hash(&fields)?))
+        }
+    }
+
+    /// See redis [HSCAN](https://redis.io/commands/hscan) command.<br>
+    /// Returns an iterator over all (field, value) pairs of the hash.
+    pub fn hscan(
+        self: &mut Client,
+        key: &str,
+        options: ScanOptions,
+    ) -> ScanIterator<(String, String)> {
+        scan::create(self, "HSCAN", Some(key), options, to_field_values)
+    }
+
+    /// See redis [LSET](https://redis.io/commands/lset) command.
+    pub fn lset<T: RedisBinaryArg>(
+        self: &mut Client,
+        key: &str,
+        index: isize,
+        value: T,
+    ) -> RedisEmptyResult {
+        self.run_binary_command::<()>(
+            "LSET",
+            vec![
+                key.as_bytes(),
+                index.to_string().as_bytes(),
+                &value.to_bytes(),
+            ],
+        )
+    }
+
+    /// See redis [HGET](https://redis.io/commands/lindex) command.
+    pub fn lindex<T: FromStr>(self: &mut Client, key: &str, index: isize) -> RedisResult<T> {
+        self.run_command_from_string_response("LINDEX", vec![key, &index.to_string()])
+    }
+
+    /// See redis [HGET](https://redis.io/commands/lindex) command.
+    pub fn lindex_string(self: &mut Client, key: &str, index: isize) -> RedisStringResult {
+        self.run_command_string_response("LINDEX", vec![key, &index.to_string()])
+    }
+
+    /// See redis [LINDEX](https://redis.io/commands/lindex) command.<br>
+    /// Returns None in case the index is out of range.
+    pub fn lindex_opt<T: FromStr>(
+        self: &mut Client,
+        key: &str,
+        index: isize,
+    ) -> RedisResult<Option<T>> {
+        self.run_command_from_optional_string_response("LINDEX", vec![key, &index.to_string()])
+    }
+
+    /// See redis [LINDEX](https://redis.io/commands/lindex) command.<br>
+    /// This function returns the raw value bytes, without any string conversion, or None in case of a nil response.
+    pub fn lindex_bytes(
+        self: &mut Client,
+        key: &str,
+        index: isize,
+    ) -> RedisResult<Option<Vec<u8>>> {
+        self.run_command::<Option<Vec<u8>>>("LINDEX", vec![key, &index.to_string()])
+    }
+
+    /// See redis [LLEN](https://redis.io/commands/llen) command.
+    pub fn llen(self: &mut Client, key: &str) -> RedisResult<i32> {
+        self.run_command::<i32>("LLEN", vec![key])
+    }
+
+    /// See redis [BLPOP](https://redis.io/commands/blpop) command.<br>
+    /// Returns the popped key and value, or None in case no value was available within the timeout
+    /// (a zero timeout blocks forever, fractions of a second require redis 6.0 and above).
+    pub fn blpop<T: FromStr>(
+        self: &mut Client,
+        keys: Vec<&str>,
+        timeout: Duration,
+    ) -> RedisResult<Option<(String, T)>> {
+        self.run_blocking_pop_command("BLPOP", keys, timeout)
+    }
+
+    /// See redis [BRPOP](https://redis.io/commands/brpop) command.<br>
+    /// Returns the popped key and value, or None in case no value was available within the timeout
+    /// (a zero timeout blocks forever, fractions of a second require redis 6.0 and above).
+    pub fn brpop<T: FromStr>(
+        self: &mut Client,
+        keys: Vec<&str>,
+        timeout: Duration,
+    ) -> RedisResult<Option<(String, T)>> {
+        self.run_blocking_pop_command("BRPOP", keys, timeout)
+    }
+
+    fn run_blocking_pop_command<T: FromStr>(
+        self: &mut Client,
+        command: &str,
+        keys: Vec<&str>,
+        timeout: Duration,
+    ) -> RedisResult<Option<(String, T)>> {
+        let args = create_blocking_pop_args(keys, timeout);
+
+        match self.run_blocking_command::<Option<(String, String)>>(command, args, Some(timeout))? {
+            Some((key, value)) => Ok(Some((key, to_typed_value(value)?))),
+            None => Ok(None),
+        }
+    }
+
+    /// See redis [BLMOVE](https://redis.io/commands/blmove) command.<br>
+    /// Returns the moved value, or None in case no value was available within the timeout
+    /// (a zero timeout blocks forever, fractions of a second require redis 6.0 and above).
+    pub fn blmove<T: FromStr>(
+        self: &mut Client,
+        source: &str,
+        destination: &str,
+        from: ListDirection,
+        to: ListDirection,
+        timeout: Duration,
+    ) -> RedisResult<Option<T>> {
+        let args = vec![
+            source.to_string(),
+            destination.to_string(),
+            from.to_arg().to_string(),
+            to.to_arg().to_string(),
+            to_block_timeout_arg(timeout),
+        ];
+
+        match self.run_blocking_command::<Option<String>>("BLMOVE", args, Some(timeout))? {
+            Some(value) => Ok(Some(to_typed_value(value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// See redis [BRPOPLPUSH](https://redis.io/commands/brpoplpush) command.<br>
+    /// Returns the moved value, or None in case no value was available within the timeout
+    /// (a zero timeout blocks forever, fractions of a second require redis 6.0 and above).
+    pub fn brpoplpush<T: FromStr>(
+        self: &mut Client,
+        source: &str,
+        destination: &str,
+        timeout: Duration,
+    ) -> RedisResult<Option<T>> {
+        let args = vec![
+            source.to_string(),
+            destination.to_string(),
+            to_block_timeout_arg(timeout),
+        ];
+
+        match self.run_blocking_command::<Option<String>>("BRPOPLPUSH", args, Some(timeout))? {
+            Some(value) => Ok(Some(to_typed_value(value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// See redis [LPOP](https://redis.io/commands/lpop) command.
+    pub fn lpop<T: FromStr>(self: &mut Client, key: &str) -> RedisResult<T> {
+        self.run_command_from_string_response("LPOP", vec![key])
+    }
+
+    /// See redis [LPOP](https://redis.io/commands/lpop) command.<br>
+    /// Returns None in case the list is empty.
+    pub fn lpop_opt<T: FromStr>(self: &mut Client, key: &str) -> RedisResult<Option<T>> {
+        self.run_command_from_optional_string_response("LPOP", vec![key])
+    }
+
+    /// See redis [LPOP](https://redis.io/commands/lpop) command.<br>
+    /// This function returns the raw value bytes, without any string conversion, or None in case of a nil response.
+    pub fn lpop_bytes(self: &mut Client, key: &str) -> RedisResult<Option<Vec<u8>>> {
+        self.run_command::<Option<Vec<u8>>>("LPOP", vec![key])
+    }
+
+    /// See redis [LPUSH](https://redis.io/commands/lpush) command.
+    pub fn lpush<T: RedisBinaryArg>(self: &mut Client, key: &str, value: T) -> RedisEmptyResult {
+        self.run_binary_command::<()>("LPUSH", vec![key.as_bytes(), &value.to_bytes()])
+    }
+
+    /// See redis [LPUSHX](https://redis.io/commands/lpushx) command.
+    pub fn lpushx<T: RedisBinaryArg>(self: &mut Client, key: &str, value: T) -> RedisEmptyResult {
+        self.run_binary_command::<()>("LPUSHX", vec![key.as_bytes(), &value.to_bytes()])
+    }
+
+    /// See redis [LRANGE](https://redis.io/commands/lrange) command.
+    pub fn lrange(
+        self: &mut Client,
+        key: &str,
+        start: isize,
+        stop: isize,
+    ) -> RedisResult<Vec<String>> {
+        self.run_command::<Vec<String>>("LRANGE", vec![key, &start.to_string(), &stop.to_string()])
+    }
+
+    /// See redis [LREM](https://redis.io/commands/lrem) command.
+    pub fn lrem<T: RedisBinaryArg>(
+        self: &mut Client,
+        key: &str,
+        count: isize,
+        value: T,
+    ) -> RedisEmptyResult {
+        self.run_binary_command::<()>(
+            "LREM",
+            vec![
+                key.as_bytes(),
+                count.to_string().as_bytes(),
+                &value.to_bytes(),
+            ],
+        )
+    }
+
+    /// See redis [LTRIM](https://redis.io/commands/ltrim) command.
+    pub fn ltrim(self: &mut Client, key: &str, start: isize, stop: isize) -> RedisEmptyResult {
+        self.run_command_empty_response("LTRIM", vec![key, &start.to_string(), &stop.to_string()])
+    }
+
+    /// See redis [RPOP](https://redis.io/commands/rpop) command.
+    pub fn rpop<T: FromStr>(self: &mut Client, key: &str) -> RedisResult<T> {
+        self.run_command_from_string_response("RPOP", vec![key])
+    }
+
+    /// See redis [RPOP](https://redis.io/commands/rpop) command.<br>
+    /// Returns None in case the list is empty.
+    pub fn rpop_opt<T: FromStr>(self: &mut Client, key: &str) -> RedisResult<Option<T>> {
+        self.run_command_from_optional_string_response("RPOP", vec![key])
+    }
+
+    /// See redis [RPOP](https://redis.io/commands/rpop) command.<br>
+    /// This function returns the raw value bytes, without any string conversion, or None in case of a nil response.
+    pub fn rpop_bytes(self: &mut Client, key: &str) -> RedisResult<Option<Vec<u8>>> {
+        self.run_command::<Option<Vec<u8>>>("RPOP", vec![key])
+    }
+
+    /// See redis [RPUSH](https://redis.io/commands/rpush) command.
+    pub fn rpush<T: RedisBinaryArg>(self: &mut Client, key: &str, value: T) -> RedisEmptyResult {
+        self.run_binary_command::<()>("RPUSH", vec![key.as_bytes(), &value.to_bytes()])
+    }
+
+    /// See redis [RPUSHX](https://redis.io/commands/rpushx) command.
+    pub fn rpushx<T: RedisBinaryArg>(self: &mut Client, key: &str, value: T) -> RedisEmptyResult {
+        self.run_binary_command::<()>("RPUSHX", vec![key.as_bytes(), &value.to_bytes()])
+    }
+
+    /// See redis [SADD](https://redis.io/commands/sadd) command.
+    pub fn sadd(self: &mut Client, key: &str, member: &str) -> RedisResult<i32> {
+        self.run_command::<i32>("SADD", vec![key, member])
+    }
+
+    /// See redis [SADD](https://redis.io/commands/sadd) command.<br>
+    /// Returns the amount of members which were added (members which already exist in the set are ignored).
+    pub fn sadd_members(self: &mut Client, key: &str, members: Vec<&str>) -> RedisResult<i32> {
+        let mut args = vec![key];
+        args.extend(members);
+
+        self.run_command::<i32>("SADD", args)
+    }
+
+    /// See redis [SCARD](https://redis.io/commands/scard) command.
+    pub fn scard(self: &mut Client, key: &str) -> RedisResult<i32> {
+        self.run_command::<i32>("SCARD", vec![key])
+    }
+
+    /// See redis [SDIFF](https://redis.io/commands/sdiff) command.
+    pub fn sdiff(self: &mut Client, keys: Vec<&str>) -> RedisResult<Vec<String>> {
+        self.run_command::<Vec<String>>("SDIFF", keys)
+    }
+
+    /// See redis [SDIFFSTORE](https://redis.io/commands/sdiffstore) command.<br>
+    /// Returns the amount of members in the destination set.
+    pub fn sdiffstore(
+        self: &mut Client,
+        destination_key: &str,
+        keys: Vec<&str>,
+    ) -> RedisResult<i32> {
+        let mut args = vec![destination_key];
+        args.extend(keys);
+
+        self.run_command::<i32>("SDIFFSTORE", args)
+    }
+
+    /// See redis [SINTER](https://redis.io/commands/sinter) command.
+    pub fn sinter(self: &mut Client, keys: Vec<&str>) -> RedisResult<Vec<String>> {
+        self.run_command::<Vec<String>>("SINTER", keys)
+    }
+
+    /// See redis [SINTERCARD](https://redis.io/commands/sintercard) command.<br>
+    /// Returns the amount of members in the intersection, stopping once the optional limit is reached.
+    pub fn sintercard(
+        self: &mut Client,
+        keys: Vec<&str>,
+        limit: Option<usize>,
+    ) -> RedisResult<i32> {
+        let mut args = vec![keys.len().to_string()];
+        args.extend(keys.into_iter().map(|key| key.to_string()));
+        if let Some(limit) = limit {
+            args.push("LIMIT".to_string());
+            args.push(limit.to_string());
+        }
+
+        self.run_binary_command::<i32>("SINTERCARD", args)
+    }
+
+    /// See redis [SINTERSTORE](https://redis.io/commands/sinterstore) command.<br>
+    /// Returns the amount of members in the destination set.
+    pub fn sinterstore(
+        self: &mut Client,
+        destination_key: &str,
+        keys: Vec<&str>,
+    ) -> RedisResult<i32> {
+        let mut args = vec![destination_key];
+        args.extend(keys);
+
+        self.run_command::<i32>("SINTERSTORE", args)
+    }
+
+    /// See redis [SISMEMBER](https://redis.io/commands/sismember) command.
+    pub fn sismember(self: &mut Client, key: &str, member: &str) -> RedisBoolResult {
+        self.run_command("SISMEMBER", vec![key, member])
+    }
+
+    /// See redis [SMISMEMBER](https://redis.io/commands/smismember) command.<br>
+    /// Returns for each of the provided members (in the same order) whether it is a member of the set.
+    pub fn smismember(self: &mut Client, key: &str, members: Vec<&str>) -> RedisResult<Vec<bool>> {
+        let mut args = vec![key];
+        args.extend(members);
+
+        self.run_command::<Vec<bool>>("SMISMEMBER", args)
+    }
+
+    /// See redis [SMEMBERS](https://redis.io/commands/smembers) command.
+    pub fn smembers(self: &mut Client, key: &str) -> RedisResult<Vec<String>> {
+        self.run_command::<Vec<String>>("SMEMBERS", vec![key])
+    }
+
+    /// See redis [SSCAN](https://redis.io/commands/sscan) command.<br>
+    /// Returns an iterator over all members of the set.
+    pub fn sscan(self: &mut Client, key: &str, options: ScanOptions) -> ScanIterator<String> {
+        scan::create(self, "SSCAN", Some(key), options, to_members)
+    }
+
+    /// See redis [SMOVE](https://redis.io/commands/smove) command.<br>
+    /// Returns true in case the member was moved, or false in case it is not a member of the source set.
+    pub fn smove(
         self: &mut Client,
         source_key: &str,
         destination_key: &str,
         member: &str,
-    ) -> RedisEmptyResult {
+    ) -> RedisBoolResult {
         self.run_command("SMOVE", vec![source_key, destination_key, member])
     }
 
-    /// See redis [SREM](https://redis.io/commands/srem) command.
-    pub fn srem(self: &mut Client, key: &str, member: &str) -> RedisEmptyResult {
-        self.run_command("SREM", vec![key, member])
+    /// See redis [SPOP](https://redis.io/commands/spop) command.<br>
+    /// Returns None in case the set is empty.
+    pub fn spop<T: FromStr>(self: &mut Client, key: &str) -> RedisResult<Option<T>> {
+        self.run_command_from_optional_string_response("SPOP", vec![key])
+    }
+
+    /// See redis [SPOP](https://redis.io/commands/spop) command.<br>
+    /// Removes and returns up to count random members.
+    pub fn spop_count(self: &mut Client, key: &str, count: usize) -> RedisResult<Vec<String>> {
+        self.run_command::<Vec<String>>("SPOP", vec![key, &count.to_string()])
+    }
+
+    /// See redis [SRANDMEMBER](https://redis.io/commands/srandmember) command.<br>
+    /// Returns None in case the set is empty.
+    pub fn srandmember<T: FromStr>(self: &mut Client, key: &str) -> RedisResult<Option<T>> {
+        self.run_command_from_optional_string_response("SRANDMEMBER", vec![key])
+    }
+
+    /// See redis [SRANDMEMBER](https://redis.io/commands/srandmember) command.<br>
+    /// Returns up to count distinct random members, or exactly count members which may repeat in case count is
+    /// negative.
+    pub fn srandmember_count(
+        self: &mut Client,
+        key: &str,
+        count: isize,
+    ) -> RedisResult<Vec<String>> {
+        self.run_command::<Vec<String>>("SRANDMEMBER", vec![key, &count.to_string()])
+    }
+
+    /// See redis [SREM](https://redis.io/commands/srem) command.<br>
+    /// Returns the amount of removed members.
+    pub fn srem(self: &mut Client, key: &str, member: &str) -> RedisResult<i32> {
+        self.run_command::<i32>("SREM", vec![key, member])
+    }
+
+    /// See redis [SREM](https://redis.io/commands/srem) command.<br>
+    /// Returns the amount of removed members (members which do not exist in the set are ignored).
+    pub fn srem_members(self: &mut Client, key: &str, members: Vec<&str>) -> RedisResult<i32> {
+        let mut args = vec![key];
+        args.extend(members);
+
+        self.run_command::<i32>("SREM", args)
+    }
+
+    /// See redis [SUNION](https://redis.io/commands/sunion) command.
+    pub fn sunion(self: &mut Client, keys: Vec<&str>) -> RedisResult<Vec<String>> {
+        self.run_command::<Vec<String>>("SUNION", keys)
+    }
+
+    /// See redis [SUNIONSTORE](https://redis.io/commands/sunionstore) command.<br>
+    /// Returns the amount of members in the destination set.
+    pub fn sunionstore(
+        self: &mut Client,
+        destination_key: &str,
+        keys: Vec<&str>,
+    ) -> RedisResult<i32> {
+        let mut args = vec![destination_key];
+        args.extend(keys);
+
+        self.run_command::<i32>("SUNIONSTORE", args)
+    }
+
+    /// See redis [ZADD](https://redis.io/commands/zadd) command.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// match client.zadd("my_leaderboard", 15.5, "player1") {
+    ///     Ok(added) => println!("Added {} members", added),
+    ///     Err(error) => println!("Unable to add member: {}", error)
+    /// }
+    /// ```
+    ///
+    pub fn zadd(self: &mut Client, key: &str, score: f64, member: &str) -> RedisResult<i32> {
+        self.run_command::<i32>("ZADD", vec![key, &score.to_string(), member])
+    }
+
+    /// See redis [ZADD](https://redis.io/commands/zadd) command.<br>
+    /// Adds all provided (score, member) pairs based on the provided options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// let mut options = simple_redis::types::ZAddOptions::default();
+    /// options.gt = true;
+    /// options.ch = true;
+    ///
+    /// match client.zadd_with_options("my_leaderboard", vec![(20.0, "player1"), (3.0, "player2")], options) {
+    ///     Ok(changed) => println!("Changed {} members", changed),
+    ///     Err(error) => println!("Unable to add members: {}", error)
+    /// }
+    /// ```
+    ///
+    pub fn zadd_with_options(
+        self: &mut Client,
+        key: &str,
+        members: Vec<(f64, &str)>,
+        options: ZAddOptions,
+    ) -> RedisResult<i32> {
+        let mut args = vec![key.to_string()];
+        args.extend(options.to_args());
+        for (score, member) in members {
+            args.push(score.to_string());
+            args.push(member.to_string());
+        }
+
+        self.run_binary_command::<i32>("ZADD", args)
+    }
+
+    /// See redis [ZADD](https://redis.io/commands/zadd) command.<br>
+    /// Increments the member score (INCR mode) and returns the new score or None in case the operation was
+    /// aborted due to the provided options.
+    pub fn zadd_incr(
+        self: &mut Client,
+        key: &str,
+        increment: f64,
+        member: &str,
+        options: ZAddOptions,
+    ) -> RedisResult<Option<f64>> {
+        let mut args = vec![key.to_string()];
+        args.extend(options.to_args());
+        args.push("INCR".to_string());
+        args.push(increment.to_string());
+        args.push(member.to_string());
+
+        self.run_binary_command::<Option<f64>>("ZADD", args)
+    }
+
+    /// See redis [ZINCRBY](https://redis.io/commands/zincrby) command.
+    pub fn zincrby(self: &mut Client, key: &str, increment: f64, member: &str) -> RedisResult<f64> {
+        self.run_command::<f64>("ZINCRBY", vec![key, &increment.to_string(), member])
+    }
+
+    /// See redis [ZCARD](https://redis.io/commands/zcard) command.
+    pub fn zcard(self: &mut Client, key: &str) -> RedisResult<i32> {
+        self.run_command::<i32>("ZCARD", vec![key])
+    }
+
+    /// See redis [ZCOUNT](https://redis.io/commands/zcount) command.
+    pub fn zcount(
+        self: &mut Client,
+        key: &str,
+        min: ScoreBound,
+        max: ScoreBound,
+    ) -> RedisResult<i32> {
+        self.run_command::<i32>("ZCOUNT", vec![key, &min.to_arg(), &max.to_arg()])
+    }
+
+    /// See redis [ZLEXCOUNT](https://redis.io/commands/zlexcount) command.
+    pub fn zlexcount(
+        self: &mut Client,
+        key: &str,
+        min: LexBound,
+        max: LexBound,
+    ) -> RedisResult<i32> {
+        self.run_command::<i32>("ZLEXCOUNT", vec![key, &min.to_arg(), &max.to_arg()])
+    }
+
+    /// See redis [ZSCORE](https://redis.io/commands/zscore) command.
+    pub fn zscore(self: &mut Client, key: &str, member: &str) -> RedisResult<Option<f64>> {
+        self.run_command::<Option<f64>>("ZSCORE", vec![key, member])
+    }
+
+    /// See redis [ZMSCORE](https://redis.io/commands/zmscore) command.
+    pub fn zmscore(
+        self: &mut Client,
+        key: &str,
+        members: Vec<&str>,
+    ) -> RedisResult<Vec<Option<f64>>> {
+        let mut args = vec![key];
+        args.extend(members);
+
+        self.run_command::<Vec<Option<f64>>>("ZMSCORE", args)
+    }
+
+    /// See redis [ZRANK](https://redis.io/commands/zrank) command.
+    pub fn zrank(self: &mut Client, key: &str, member: &str) -> RedisResult<Option<i32>> {
+        self.run_command::<Option<i32>>("ZRANK", vec![key, member])
+    }
+
+    /// See redis [ZREVRANK](https://redis.io/commands/zrevrank) command.
+    pub fn zrevrank(self: &mut Client, key: &str, member: &str) -> RedisResult<Option<i32>> {
+        self.run_command::<Option<i32>>("ZREVRANK", vec![key, member])
+    }
+
+    /// See redis [ZREM](https://redis.io/commands/zrem) command.
+    pub fn zrem(self: &mut Client, key: &str, members: Vec<&str>) -> RedisResult<i32> {
+        let mut args = vec![key];
+        args.extend(members);
+
+        self.run_command::<i32>("ZREM", args)
+    }
+
+    /// See redis [BZPOPMIN](https://redis.io/commands/bzpopmin) command.<br>
+    /// Returns the key, member and score of the popped member, or None in case no member was available within the
+    /// timeout (a zero timeout blocks forever, fractions of a second require redis 6.0 and above).
+    pub fn bzpopmin(
+        self: &mut Client,
+        keys: Vec<&str>,
+        timeout: Duration,
+    ) -> RedisResult<Option<(String, String, f64)>> {
+        self.run_blocking_zpop_command("BZPOPMIN", keys, timeout)
+    }
+
+    /// See redis [BZPOPMAX](https://redis.io/commands/bzpopmax) command.<br>
+    /// Returns the key, member and score of the popped member, or None in case no member was available within the
+    /// timeout (a zero timeout blocks forever, fractions of a second require redis 6.0 and above).
+    pub fn bzpopmax(
+        self: &mut Client,
+        keys: Vec<&str>,
+        timeout: Duration,
+    ) -> RedisResult<Option<(String, String, f64)>> {
+        self.run_blocking_zpop_command("BZPOPMAX", keys, timeout)
+    }
+
+    fn run_blocking_zpop_command(
+        self: &mut Client,
+        command: &str,
+        keys: Vec<&str>,
+        timeout: Duration,
+    ) -> RedisResult<Option<(String, String, f64)>> {
+        let args = create_blocking_pop_args(keys, timeout);
+
+        match self.run_blocking_command::<Option<(String, String, String)>>(
+            command,
+            args,
+            Some(timeout),
+        )? {
+            Some((key, member, score)) => Ok(Some((key, member, to_typed_value(score)?))),
+            None => Ok(None),
+        }
+    }
+
+    /// See redis [ZRANGE](https://redis.io/commands/zrange) command.
+    pub fn zrange(
+        self: &mut Client,
+        key: &str,
+        start: isize,
+        stop: isize,
+    ) -> RedisResult<Vec<String>> {
+        self.run_command::<Vec<String>>("ZRANGE", vec![key, &start.to_string(), &stop.to_string()])
+    }
+
+    /// See redis [ZRANGE](https://redis.io/commands/zrange) command.<br>
+    /// Returns the members together with their scores.
+    pub fn zrange_withscores(
+        self: &mut Client,
+        key: &str,
+        start: isize,
+        stop: isize,
+    ) -> RedisResult<Vec<(String, f64)>> {
+        let args = create_range_args(key, start.to_string(), stop.to_string(), true, None);
+
+        match self.run_binary_command::<Vec<redis::Value>>("ZRANGE", args) {
+            Ok(values) => from_values(&values).and_then(to_scored_members),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// See redis [ZREVRANGE](https://redis.io/commands/zrevrange) command.
+    pub fn zrevrange(
+        self: &mut Client,
+        key: &str,
+        start: isize,
+        stop: isize,
+    ) -> RedisResult<Vec<String>> {
+        self.run_command::<Vec<String>>(
+            "ZREVRANGE",
+            vec![key, &start.to_string(), &stop.to_string()],
+        )
+    }
+
+    /// See redis [ZREVRANGE](https://redis.io/commands/zrevrange) command.<br>
+    /// Returns the members together with their scores.
+    pub fn zrevrange_withscores(
+        self: &mut Client,
+        key: &str,
+        start: isize,
+        stop: isize,
+    ) -> RedisResult<Vec<(String, f64)>> {
+        let args = create_range_args(key, start.to_string(), stop.to_string(), true, None);
+
+        match self.run_binary_command::<Vec<redis::Value>>("ZREVRANGE", args) {
+            Ok(values) => from_values(&values).and_then(to_scored_members),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// See redis [ZRANGEBYSCORE](https://redis.io/commands/zrangebyscore) command.<br>
+    /// The optional limit is provided as (offset, count).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// use simple_redis::types::ScoreBound;
+    ///
+    /// match client.zrangebyscore("my_jobs", ScoreBound::NegInfinity, ScoreBound::Exclusive(1000.0), Some((0, 10))) {
+    ///     Ok(jobs) => println!("Ready jobs: {:?}", jobs),
+    ///     Err(error) => println!("Unable to read jobs: {}", error)
+    /// }
+    /// ```
+    ///
+    pub fn zrangebyscore(
+        self: &mut Client,
+        key: &str,
+        min: ScoreBound,
+        max: ScoreBound,
+        limit: Option<(isize, isize)>,
+    ) -> RedisResult<Vec<String>> {
+        let args = create_range_args(key, min.to_arg(), max.to_arg(), false, limit);
+
+        self.run_binary_command::<Vec<String>>("ZRANGEBYSCORE", args)
+    }
+
+    /// See redis [ZRANGEBYSCORE](https://redis.io/commands/zrangebyscore) command.<br>
+    /// Returns the members together with their scores.
+    pub fn zrangebyscore_withscores(
+        self: &mut Client,
+        key: &str,
+        min: ScoreBound,
+        max: ScoreBound,
+        limit: Option<(isize, isize)>,
+    ) -> RedisResult<Vec<(String, f64)>> {
+        let args = create_range_args(key, min.to_arg(), max.to_arg(), true, limit);
+
+        match self.run_binary_command::<Vec<redis::Value>>("ZRANGEBYSCORE", args) {
+            Ok(values) => from_values(&values).and_then(to_scored_members),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// See redis [ZREVRANGEBYSCORE](https://redis.io/commands/zrevrangebyscore) command.
+    pub fn zrevrangebyscore(
+        self: &mut Client,
+        key: &str,
+        max: ScoreBound,
+        min: ScoreBound,
+        limit: Option<(isize, isize)>,
+    ) -> RedisResult<Vec<String>> {
+        let args = create_range_args(key, max.to_arg(), min.to_arg(), false, limit);
+
+        self.run_binary_command::<Vec<String>>("ZREVRANGEBYSCORE", args)
+    }
+
+    /// See redis [ZREVRANGEBYSCORE](https://redis.io/commands/zrevrangebyscore) command.<br>
+    /// Returns the members together with their scores.
+    pub fn zrevrangebyscore_withscores(
+        self: &mut Client,
+        key: &str,
+        max: ScoreBound,
+        min: ScoreBound,
+        limit: Option<(isize, isize)>,
+    ) -> RedisResult<Vec<(String, f64)>> {
+        let args = create_range_args(key, max.to_arg(), min.to_arg(), true, limit);
+
+        match self.run_binary_command::<Vec<redis::Value>>("ZREVRANGEBYSCORE", args) {
+            Ok(values) => from_values(&values).and_then(to_scored_members),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// See redis [ZRANGEBYLEX](https://redis.io/commands/zrangebylex) command.
+    pub fn zrangebylex(
+        self: &mut Client,
+        key: &str,
+        min: LexBound,
+        max: LexBound,
+        limit: Option<(isize, isize)>,
+    ) -> RedisResult<Vec<String>> {
+        let args = create_range_args(key, min.to_arg(), max.to_arg(), false, limit);
+
+        self.run_binary_command::<Vec<String>>("ZRANGEBYLEX", args)
+    }
+
+    /// See redis [ZREVRANGEBYLEX](https://redis.io/commands/zrevrangebylex) command.
+    pub fn zrevrangebylex(
+        self: &mut Client,
+        key: &str,
+        max: LexBound,
+        min: LexBound,
+        limit: Option<(isize, isize)>,
+    ) -> RedisResult<Vec<String>> {
+        let args = create_range_args(key, max.to_arg(), min.to_arg(), false, limit);
+
+        self.run_binary_command::<Vec<String>>("ZREVRANGEBYLEX", args)
+    }
+
+    /// See redis [ZREMRANGEBYRANK](https://redis.io/commands/zremrangebyrank) command.
+    pub fn zremrangebyrank(
+        self: &mut Client,
+        key: &str,
+        start: isize,
+        stop: isize,
+    ) -> RedisResult<i32> {
+        self.run_command::<i32>(
+            "ZREMRANGEBYRANK",
+            vec![key, &start.to_string(), &stop.to_string()],
+        )
+    }
+
+    /// See redis [ZREMRANGEBYSCORE](https://redis.io/commands/zremrangebyscore) command.
+    pub fn zremrangebyscore(
+        self: &mut Client,
+        key: &str,
+        min: ScoreBound,
+        max: ScoreBound,
+    ) -> RedisResult<i32> {
+        self.run_command::<i32>("ZREMRANGEBYSCORE", vec![key, &min.to_arg(), &max.to_arg()])
+    }
+
+    /// See redis [ZREMRANGEBYLEX](https://redis.io/commands/zremrangebylex) command.
+    pub fn zremrangebylex(
+        self: &mut Client,
+        key: &str,
+        min: LexBound,
+        max: LexBound,
+    ) -> RedisResult<i32> {
+        self.run_command::<i32>("ZREMRANGEBYLEX", vec![key, &min.to_arg(), &max.to_arg()])
+    }
+
+    /// See redis [ZPOPMIN](https://redis.io/commands/zpopmin) command.<br>
+    /// In case any popped member is not a valid string, an error is returned (the members are still removed).
+    pub fn zpopmin(self: &mut Client, key: &str, count: usize) -> RedisResult<Vec<(String, f64)>> {
+        match self.run_command::<Vec<redis::Value>>("ZPOPMIN", vec![key, &count.to_string()]) {
+            Ok(values) => from_values(&values).and_then(to_scored_members),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// See redis [ZPOPMAX](https://redis.io/commands/zpopmax) command.<br>
+    /// In case any popped member is not a valid string, an error is returned (the members are still removed).
+    pub fn zpopmax(self: &mut Client, key: &str, count: usize) -> RedisResult<Vec<(String, f64)>> {
+        match self.run_command::<Vec<redis::Value>>("ZPOPMAX", vec![key, &count.to_string()]) {
+            Ok(values) => from_values(&values).and_then(to_scored_members),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// See redis [ZSCAN](https://redis.io/commands/zscan) command.<br>
+    /// Returns an iterator over all (member, score) pairs of the sorted set.
+    pub fn zscan(
+        self: &mut Client,
+        key: &str,
+        options: ScanOptions,
+    ) -> ScanIterator<(String, f64)> {
+        scan::create(self, "ZSCAN", Some(key), options, to_scored_members)
+    }
+
+    /// See redis [ZRANDMEMBER](https://redis.io/commands/zrandmember) command.<br>
+    /// A negative count allows the same member to be returned multiple times.
+    pub fn zrandmember(self: &mut Client, key: &str, count: isize) -> RedisResult<Vec<String>> {
+        self.run_command::<Vec<String>>("ZRANDMEMBER", vec![key, &count.to_string()])
+    }
+
+    /// See redis [ZUNIONSTORE](https://redis.io/commands/zunionstore) command.
+    pub fn zunionstore(self: &mut Client, destination: &str, keys: Vec<&str>) -> RedisResult<i32> {
+        let count = keys.len().to_string();
+        let mut args = vec![destination, &count];
+        args.extend(keys);
+
+        self.run_command::<i32>("ZUNIONSTORE", args)
+    }
+
+    /// See redis [ZINTERSTORE](https://redis.io/commands/zinterstore) command.
+    pub fn zinterstore(self: &mut Client, destination: &str, keys: Vec<&str>) -> RedisResult<i32> {
+        let count = keys.len().to_string();
+        let mut args = vec![destination, &count];
+        args.extend(keys);
+
+        self.run_command::<i32>("ZINTERSTORE", args)
+    }
+
+    /// See redis [ZDIFFSTORE](https://redis.io/commands/zdiffstore) command.
+    pub fn zdiffstore(self: &mut Client, destination: &str, keys: Vec<&str>) -> RedisResult<i32> {
+        let count = keys.len().to_string();
+        let mut args = vec![destination, &count];
+        args.extend(keys);
+
+        self.run_command::<i32>("ZDIFFSTORE", args)
+    }
+
+    /// See redis [EVAL](https://redis.io/commands/eval) command.<br>
+    /// In order to prevent sending the entire script on every invocation, see the script::Script struct.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// let value: String = client
+    ///     .eval("return ARGV[1]", &Vec::<&str>::new(), &["my_value"])
+    ///     .unwrap();
+    /// assert_eq!(value, "my_value");
+    /// ```
+    pub fn eval<T: redis::FromRedisValue, K: RedisBinaryArg, A: RedisBinaryArg>(
+        self: &mut Client,
+        script: &str,
+        keys: &[K],
+        args: &[A],
+    ) -> RedisResult<T> {
+        self.run_binary_command::<T>("EVAL", create_script_args(script, keys, args))
+    }
+
+    /// See redis [EVALSHA](https://redis.io/commands/evalsha) command.
+    pub fn evalsha<T: redis::FromRedisValue, K: RedisBinaryArg, A: RedisBinaryArg>(
+        self: &mut Client,
+        sha1: &str,
+        keys: &[K],
+        args: &[A],
+    ) -> RedisResult<T> {
+        self.run_binary_command::<T>("EVALSHA", create_script_args(sha1, keys, args))
+    }
+
+    /// See redis [SCRIPT LOAD](https://redis.io/commands/script-load) command.<br>
+    /// Returns the SHA1 digest of the loaded script.
+    pub fn script_load(self: &mut Client, script: &str) -> RedisStringResult {
+        self.run_command_string_response("SCRIPT", vec!["LOAD", script])
+    }
+
+    /// See redis [SCRIPT EXISTS](https://redis.io/commands/script-exists) command.
+    pub fn script_exists(self: &mut Client, sha1s: Vec<&str>) -> RedisResult<Vec<bool>> {
+        let mut args = vec!["EXISTS"];
+        args.extend(sha1s);
+
+        self.run_command::<Vec<bool>>("SCRIPT", args)
+    }
+
+    /// See redis [SCRIPT FLUSH](https://redis.io/commands/script-flush) command.
+    pub fn script_flush(self: &mut Client) -> RedisEmptyResult {
+        self.run_command_empty_response("SCRIPT", vec!["FLUSH"])
+    }
+
+    /// See redis [FUNCTION LOAD](https://redis.io/commands/function-load) command.<br>
+    /// Returns the name of the loaded library.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The library code, starting with the library shebang line (for example: `#!lua name=mylib`)
+    /// * `replace` - If true, an existing library with the same name is replaced
+    pub fn function_load(self: &mut Client, code: &str, replace: bool) -> RedisStringResult {
+        let mut args = vec!["LOAD"];
+        if replace {
+            args.push("REPLACE");
+        }
+        args.push(code);
+
+        self.run_command_string_response("FUNCTION", args)
+    }
+
+    /// Loads the provided library (see redis [FUNCTION LOAD](https://redis.io/commands/function-load) command),
+    /// only in case it is not loaded yet or the loaded library code is different (for example a library embedded
+    /// in the application binary, which was changed in a new application version).<br>
+    /// Returns true if the library was loaded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// const LIBRARY: &str = "#!lua name=my_library
+    /// redis.register_function('my_echo', function(keys, args) return args[1] end)";
+    ///
+    /// match client.function_load_if_changed(LIBRARY) {
+    ///     Ok(true) => println!("Library loaded"),
+    ///     Ok(false) => println!("Library already loaded"),
+    ///     Err(error) => println!("Unable to load library: {}", error),
+    /// }
+    /// ```
+    pub fn function_load_if_changed(self: &mut Client, code: &str) -> RedisBoolResult {
+        let name = match get_library_name(code) {
+            Some(name) => name,
+            None => {
+                return Err(RedisError {
+                    info: ErrorInfo::Description("Library name not found in the library code."),
+                })
+            }
+        };
+
+        let loaded = self.function_list(Some(name), true)?.iter().any(|library| {
+            library.name == name && library.code.as_ref().map(String::as_str) == Some(code)
+        });
+
+        if loaded {
+            Ok(false)
+        } else {
+            self.function_load(code, true)?;
+
+            Ok(true)
+        }
+    }
+
+    /// See redis [FUNCTION DELETE](https://redis.io/commands/function-delete) command.
+    pub fn function_delete(self: &mut Client, library: &str) -> RedisEmptyResult {
+        self.run_command_empty_response("FUNCTION", vec!["DELETE", library])
+    }
+
+    /// See redis [FUNCTION FLUSH](https://redis.io/commands/function-flush) command.
+    pub fn function_flush(self: &mut Client) -> RedisEmptyResult {
+        self.run_command_empty_response("FUNCTION", vec!["FLUSH"])
+    }
+
+    /// See redis [FUNCTION LIST](https://redis.io/commands/function-list) command.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - If provided, only libraries matching this pattern are returned
+    /// * `with_code` - If true, the libraries code is also returned
+    pub fn function_list(
+        self: &mut Client,
+        pattern: Option<&str>,
+        with_code: bool,
+    ) -> RedisResult<Vec<FunctionLibrary>> {
+        let mut args = vec!["LIST"];
+        if let Some(pattern) = pattern {
+            args.push("LIBRARYNAME");
+            args.push(pattern);
+        }
+        if with_code {
+            args.push("WITHCODE");
+        }
+
+        match self.run_command::<Vec<HashMap<String, redis::Value>>>("FUNCTION", args) {
+            Ok(values) => to_function_libraries(values),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// See redis [FUNCTION DUMP](https://redis.io/commands/function-dump) command.<br>
+    /// Returns the serialized payload of all libraries, which can be restored using function_restore.
+    pub fn function_dump(self: &mut Client) -> RedisResult<Vec<u8>> {
+        self.run_command::<Vec<u8>>("FUNCTION", vec!["DUMP"])
+    }
+
+    /// See redis [FUNCTION RESTORE](https://redis.io/commands/function-restore) command.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - The serialized payload returned by function_dump
+    /// * `policy` - Defines how the restored libraries are merged with the existing libraries (redis defaults to
+    /// APPEND)
+    pub fn function_restore(
+        self: &mut Client,
+        payload: &[u8],
+        policy: Option<FunctionRestorePolicy>,
+    ) -> RedisEmptyResult {
+        let mut args: Vec<&[u8]> = vec![b"RESTORE", payload];
+        if let Some(ref policy) = policy {
+            args.push(policy.to_arg().as_bytes());
+        }
+
+        self.run_binary_command::<()>("FUNCTION", args)
+    }
+
+    /// See redis [FCALL](https://redis.io/commands/fcall) command.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// # client
+    /// #     .function_load_if_changed(
+    /// #         "#!lua name=my_library\nredis.register_function('my_echo', function(keys, args) return args[1] end)",
+    /// #     )
+    /// #     .unwrap();
+    /// let value: String = client
+    ///     .fcall("my_echo", &Vec::<&str>::new(), &["my_value"])
+    ///     .unwrap();
+    /// assert_eq!(value, "my_value");
+    /// ```
+    pub fn fcall<T: redis::FromRedisValue, K: RedisBinaryArg, A: RedisBinaryArg>(
+        self: &mut Client,
+        function: &str,
+        keys: &[K],
+        args: &[A],
+    ) -> RedisResult<T> {
+        self.run_binary_command::<T>("FCALL", create_script_args(function, keys, args))
+    }
+
+    /// See redis [FCALL_RO](https://redis.io/commands/fcall_ro) command.<br>
+    /// Only functions flagged with no-writes can be invoked using this command.
+    pub fn fcall_ro<T: redis::FromRedisValue, K: RedisBinaryArg, A: RedisBinaryArg>(
+        self: &mut Client,
+        function: &str,
+        keys: &[K],
+        args: &[A],
+    ) -> RedisResult<T> {
+        self.run_binary_command::<T>("FCALL_RO", create_script_args(function, keys, args))
+    }
+
+    /// See redis [XADD](https://redis.io/commands/xadd) command.<br>
+    /// Returns the ID of the added entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The stream key
+    /// * `id` - The entry ID or `*` to auto generate the ID
+    /// * `fields` - The entry fields and values
+    /// * `trim` - If provided, the stream is trimmed after adding the entry
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// use simple_redis::types::StreamTrim;
+    ///
+    /// match client.xadd("my_stream", "*", &[("temperature", 21)], Some(StreamTrim::ApproxMaxLen(1000))) {
+    ///     Ok(id) => println!("Added entry: {}", id),
+    ///     Err(error) => println!("Unable to add entry: {}", error),
+    /// }
+    /// ```
+    pub fn xadd<T: RedisBinaryArg>(
+        self: &mut Client,
+        key: &str,
+        id: &str,
+        fields: &[(&str, T)],
+        trim: Option<StreamTrim>,
+    ) -> RedisStringResult {
+        let mut args = vec![key.as_bytes().to_vec()];
+        if let Some(ref trim) = trim {
+            args.extend(trim.to_args().into_iter().map(String::into_bytes));
+        }
+        args.push(id.as_bytes().to_vec());
+        for &(ref field, ref value) in fields {
+            args.push(field.as_bytes().to_vec());
+            args.push(value.to_bytes());
+        }
+
+        self.run_binary_command::<String>("XADD", args)
+    }
+
+    /// See redis [XLEN](https://redis.io/commands/xlen) command.
+    pub fn xlen(self: &mut Client, key: &str) -> RedisResult<i32> {
+        self.run_command::<i32>("XLEN", vec![key])
+    }
+
+    /// See redis [XRANGE](https://redis.io/commands/xrange) command.<br>
+    /// The start and end IDs can be `-` and `+` for the lowest and highest possible IDs.
+    pub fn xrange(
+        self: &mut Client,
+        key: &str,
+        start: &str,
+        end: &str,
+        count: Option<usize>,
+    ) -> RedisResult<Vec<StreamEntry>> {
+        let mut args = vec![key.to_string(), start.to_string(), end.to_string()];
+        if let Some(count) = count {
+            args.push("COUNT".to_string());
+            args.push(count.to_string());
+        }
+
+        let value = self.run_binary_command::<redis::Value>("XRANGE", args)?;
+        stream::to_entries(&value)
+    }
+
+    /// See redis [XREVRANGE](https://redis.io/commands/xrevrange) command.<br>
+    /// The entries are returned in reverse order, starting from the end ID.
+    pub fn xrevrange(
+        self: &mut Client,
+        key: &str,
+        end: &str,
+        start: &str,
+        count: Option<usize>,
+    ) -> RedisResult<Vec<StreamEntry>> {
+        let mut args = vec![key.to_string(), end.to_string(), start.to_string()];
+        if let Some(count) = count {
+            args.push("COUNT".to_string());
+            args.push(count.to_string());
+        }
+
+        let value = self.run_binary_command::<redis::Value>("XREVRANGE", args)?;
+        stream::to_entries(&value)
+    }
+
+    /// See redis [XREAD](https://redis.io/commands/xread) command.<br>
+    /// Returns the entries of every stream with new entries (empty in case the block timeout elapsed).
+    ///
+    /// # Arguments
+    ///
+    /// * `streams` - The stream keys and the ID to read after (`$` for entries added after the read started)
+    /// * `options` - The read options
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// use simple_redis::types::StreamReadOptions;
+    ///
+    /// let mut options = StreamReadOptions::default();
+    /// options.count = Some(10);
+    ///
+    /// match client.xread(vec![("my_stream", "0")], options) {
+    ///     Ok(streams) => for (key, entries) in streams {
+    ///         println!("Read {} entries from stream: {}", entries.len(), key);
+    ///     },
+    ///     Err(error) => println!("Unable to read entries: {}", error),
+    /// }
+    /// ```
+    pub fn xread(
+        self: &mut Client,
+        streams: Vec<(&str, &str)>,
+        options: StreamReadOptions,
+    ) -> RedisResult<Vec<(String, Vec<StreamEntry>)>> {
+        let mut args = options.to_args();
+        args.extend(create_streams_args(streams));
+
+        let value = self.run_blocking_command::<redis::Value>("XREAD", args, options.block)?;
+        stream::to_streams(&value)
+    }
+
+    /// See redis [XTRIM](https://redis.io/commands/xtrim) command.<br>
+    /// Returns the amount of evicted entries.
+    pub fn xtrim(self: &mut Client, key: &str, trim: StreamTrim) -> RedisResult<i32> {
+        let mut args = vec![key.to_string()];
+        args.extend(trim.to_args());
+
+        self.run_binary_command::<i32>("XTRIM", args)
+    }
+
+    /// See redis [XDEL](https://redis.io/commands/xdel) command.<br>
+    /// Returns the amount of deleted entries.
+    pub fn xdel(self: &mut Client, key: &str, ids: Vec<&str>) -> RedisResult<i32> {
+        let mut args = vec![key];
+        args.extend(ids);
+
+        self.run_command::<i32>("XDEL", args)
+    }
+
+    /// See redis [XGROUP CREATE](https://redis.io/commands/xgroup-create) command.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The stream key
+    /// * `group` - The consumer group name
+    /// * `id` - The last delivered entry ID (`$` to only deliver new entries, `0` to deliver the entire stream)
+    /// * `mkstream` - If true, the stream is created in case it does not exist
+    pub fn xgroup_create(
+        self: &mut Client,
+        key: &str,
+        group: &str,
+        id: &str,
+        mkstream: bool,
+    ) -> RedisEmptyResult {
+        let mut args = vec!["CREATE", key, group, id];
+        if mkstream {
+            args.push("MKSTREAM");
+        }
+
+        self.run_command_empty_response("XGROUP", args)
+    }
+
+    /// See redis [XGROUP DESTROY](https://redis.io/commands/xgroup-destroy) command.<br>
+    /// Returns true if the consumer group existed.
+    pub fn xgroup_destroy(self: &mut Client, key: &str, group: &str) -> RedisBoolResult {
+        self.run_command_bool_response("XGROUP", vec!["DESTROY", key, group])
+    }
+
+    /// See redis [XREADGROUP](https://redis.io/commands/xreadgroup) command.<br>
+    /// Returns the entries of every stream with entries (empty in case the block timeout elapsed).
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The consumer group name
+    /// * `consumer` - The consumer name
+    /// * `streams` - The stream keys and the ID to read after (`>` for new entries, any other ID for the consumer
+    /// pending entries)
+    /// * `options` - The read options
+    pub fn xreadgroup(
+        self: &mut Client,
+        group: &str,
+        consumer: &str,
+        streams: Vec<(&str, &str)>,
+        options: StreamReadOptions,
+    ) -> RedisResult<Vec<(String, Vec<StreamEntry>)>> {
+        let mut args = vec!["GROUP".to_string(), group.to_string(), consumer.to_string()];
+        args.extend(options.to_args());
+        if options.noack {
+            args.push("NOACK".to_string());
+        }
+        args.extend(create_streams_args(streams));
+
+        let value = self.run_blocking_command::<redis::Value>("XREADGROUP", args, options.block)?;
+        stream::to_streams(&value)
+    }
+
+    /// Returns a reader which reads the stream entries as a member of the provided consumer group, starting with
+    /// the consumer pending entries (see stream::StreamGroupReader).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// use simple_redis::types::StreamReadOptions;
+    /// use std::time::Duration;
+    ///
+    /// # client.del("my_queue").unwrap();
+    /// client.xgroup_create("my_queue", "workers", "0", true).unwrap();
+    ///
+    /// let mut options = StreamReadOptions::default();
+    /// options.block = Some(Duration::from_millis(100));
+    ///
+    /// let mut reader = client.stream_group_reader("my_queue", "workers", "worker_1", options);
+    /// for entry in reader.read().unwrap() {
+    ///     println!("Processing entry: {}", entry.id);
+    ///     reader.ack(vec![&entry.id]).unwrap();
+    /// }
+    /// ```
+    pub fn stream_group_reader(
+        self: &mut Client,
+        key: &str,
+        group: &str,
+        consumer: &str,
+        options: StreamReadOptions,
+    ) -> StreamGroupReader {
+        stream::create_group_reader(self, key, group, consumer, options)
+    }
+
+    /// See redis [XACK](https://redis.io/commands/xack) command.<br>
+    /// Returns the amount of acknowledged entries.
+    pub fn xack(self: &mut Client, key: &str, group: &str, ids: Vec<&str>) -> RedisResult<i32> {
+        let mut args = vec![key, group];
+        args.extend(ids);
+
+        self.run_command::<i32>("XACK", args)
+    }
+
+    /// See redis [XPENDING](https://redis.io/commands/xpending) command.<br>
+    /// Returns the summary of the consumer group pending entries.
+    pub fn xpending(
+        self: &mut Client,
+        key: &str,
+        group: &str,
+    ) -> RedisResult<StreamPendingSummary> {
+        let value = self.run_command::<redis::Value>("XPENDING", vec![key, group])?;
+        stream::to_pending_summary(&value)
+    }
+
+    /// See redis [XPENDING](https://redis.io/commands/xpending) command.<br>
+    /// Returns the consumer group pending entries in the provided range (optionally only of the provided consumer).
+    pub fn xpending_range(
+        self: &mut Client,
+        key: &str,
+        group: &str,
+        start: &str,
+        end: &str,
+        count: usize,
+        consumer: Option<&str>,
+    ) -> RedisResult<Vec<StreamPendingEntry>> {
+        let count = count.to_string();
+        let mut args = vec![key, group, start, end, &count];
+        if let Some(consumer) = consumer {
+            args.push(consumer);
+        }
+
+        let value = self.run_command::<redis::Value>("XPENDING", args)?;
+        stream::to_pending_entries(&value)
+    }
+
+    /// See redis [XCLAIM](https://redis.io/commands/xclaim) command.<br>
+    /// Changes the owner of the provided pending entries, in case they were idle for at least the provided time
+    /// (in millies), and returns the claimed entries.
+    pub fn xclaim(
+        self: &mut Client,
+        key: &str,
+        group: &str,
+        consumer: &str,
+        min_idle_time: u64,
+        ids: Vec<&str>,
+    ) -> RedisResult<Vec<StreamEntry>> {
+        let min_idle_time = min_idle_time.to_string();
+        let mut args = vec![key, group, consumer, &min_idle_time];
+        args.extend(ids);
+
+        let value = self.run_command::<redis::Value>("XCLAIM", args)?;
+        stream::to_entries(&value)
+    }
+
+    /// See redis [XAUTOCLAIM](https://redis.io/commands/xautoclaim) command.<br>
+    /// Changes the owner of pending entries which were idle for at least the provided time (in millies), scanning
+    /// the pending entries from the provided start ID.
+    pub fn xautoclaim(
+        self: &mut Client,
+        key: &str,
+        group: &str,
+        consumer: &str,
+        min_idle_time: u64,
+        start: &str,
+        count: Option<usize>,
+    ) -> RedisResult<StreamAutoClaimResult> {
+        let mut args = vec![
+            key.to_string(),
+            group.to_string(),
+            consumer.to_string(),
+            min_idle_time.to_string(),
+            start.to_string(),
+        ];
+        if let Some(count) = count {
+            args.push("COUNT".to_string());
+            args.push(count.to_string());
+        }
+
+        let value = self.run_binary_command::<redis::Value>("XAUTOCLAIM", args)?;
+        stream::to_autoclaim_result(&value)
     }
 }
@@ -1,3 +1,4 @@
+use super::*;
 use client;
 
 #[test]
@@ -39,3 +40,286 @@ fn set_get() {
     let value = client.get_string("set_get").unwrap();
     assert_eq!(value, "my_value");
 }
+
+#[test]
+fn to_scored_members_valid() {
+    let output = to_scored_members(vec![
+        "a".to_string(),
+        "1.5".to_string(),
+        "b".to_string(),
+        "inf".to_string(),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        output,
+        vec![
+            ("a".to_string(), 1.5),
+            ("b".to_string(), ::std::f64::INFINITY)
+        ]
+    );
+}
+
+#[test]
+fn to_scored_members_invalid() {
+    assert!(to_scored_members(vec!["a".to_string()]).is_err());
+    assert!(to_scored_members(vec!["a".to_string(), "bad".to_string()]).is_err());
+}
+
+#[test]
+fn to_scored_members_binary_member() {
+    let values = vec![
+        redis::Value::Data(vec![255, 0]),
+        redis::Value::Data(b"1".to_vec()),
+        redis::Value::Data(b"b".to_vec()),
+        redis::Value::Data(b"2".to_vec()),
+    ];
+
+    // the binary member fails the conversion instead of pairing the members with the wrong scores
+    assert!(from_values(&values).and_then(to_scored_members).is_err());
+}
+
+#[test]
+fn to_field_values_valid() {
+    let output = to_field_values(vec!["a".to_string(), "1".to_string()]).unwrap();
+
+    assert_eq!(output, vec![("a".to_string(), "1".to_string())]);
+}
+
+#[test]
+fn from_values_binary() {
+    let values = vec![
+        redis::Value::Data(b"a".to_vec()),
+        redis::Value::Data(vec![255, 0]),
+        redis::Value::Data(b"b".to_vec()),
+        redis::Value::Data(b"2".to_vec()),
+    ];
+
+    // the binary value fails the conversion instead of pairing the fields with the wrong values
+    assert!(from_values::<String>(&values).is_err());
+
+    let values = vec![
+        redis::Value::Data(b"a".to_vec()),
+        redis::Value::Status("1".to_string()),
+    ];
+    assert_eq!(from_values::<String>(&values).unwrap(), vec!["a", "1"]);
+}
+
+#[test]
+fn to_field_values_invalid() {
+    assert!(to_field_values(vec!["a".to_string()]).is_err());
+}
+
+#[test]
+fn create_script_args_valid() {
+    let output = create_script_args("return 1", &["a", "b"], &[1, 2]);
+
+    assert_eq!(
+        output,
+        vec![
+            b"return 1".to_vec(),
+            b"2".to_vec(),
+            b"a".to_vec(),
+            b"b".to_vec(),
+            b"1".to_vec(),
+            b"2".to_vec()
+        ]
+    );
+}
+
+#[test]
+fn get_library_name_valid() {
+    assert_eq!(
+        get_library_name("#!lua name=mylib\nredis.register_function('f', function() end)"),
+        Some("mylib")
+    );
+    assert_eq!(get_library_name("#!lua  name=mylib other=1"), Some("mylib"));
+}
+
+#[test]
+fn get_library_name_missing() {
+    assert_eq!(
+        get_library_name("redis.register_function('f', function() end)"),
+        None
+    );
+    assert_eq!(get_library_name("#!lua\nreturn 1"), None);
+    assert_eq!(get_library_name(""), None);
+}
+
+#[test]
+fn to_function_libraries_valid() {
+    let values: Vec<HashMap<String, redis::Value>> = redis::from_redis_value(
+        &redis::parse_redis_value(
+            b"*1\r\n*8\r\n$12\r\nlibrary_name\r\n$5\r\nmylib\r\n$6\r\nengine\r\n$3\r\nLUA\r\n$9\r\nfunctions\r\n*1\r\n*6\r\n$4\r\nname\r\n$4\r\nmyfn\r\n$11\r\ndescription\r\n$-1\r\n$5\r\nflags\r\n*1\r\n$9\r\nno-writes\r\n$12\r\nlibrary_code\r\n$4\r\ncode\r\n",
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    let output = to_function_libraries(values).unwrap();
+    assert_eq!(
+        output,
+        vec![FunctionLibrary {
+            name: "mylib".to_string(),
+            engine: "LUA".to_string(),
+            functions: vec![FunctionInfo {
+                name: "myfn".to_string(),
+                description: None,
+                flags: vec!["no-writes".to_string()],
+            }],
+            code: Some("code".to_string()),
+        }]
+    );
+}
+
+#[test]
+fn to_function_libraries_invalid() {
+    let mut library = HashMap::new();
+    library.insert(
+        "library_name".to_string(),
+        redis::Value::Data(b"mylib".to_vec()),
+    );
+
+    assert!(to_function_libraries(vec![library]).is_err());
+}
+
+#[test]
+fn create_streams_args_valid() {
+    let args = create_streams_args(vec![("a", "0"), ("b", "$")]);
+
+    assert_eq!(args, vec!["STREAMS", "a", "b", "0", "$"]);
+}
+
+#[test]
+fn to_optional_typed_values_valid() {
+    let values = vec![
+        redis::Value::Data(b"1".to_vec()),
+        redis::Value::Nil,
+        redis::Value::Data(b"3".to_vec()),
+    ];
+
+    let output = to_optional_typed_values::<i32>(values).unwrap();
+    assert_eq!(output, vec![Some(1), None, Some(3)]);
+}
+
+#[test]
+fn to_optional_typed_values_binary() {
+    // the binary value fails the conversion instead of shifting the rest of the values
+    let values = vec![
+        redis::Value::Data(vec![255, 0]),
+        redis::Value::Data(b"value".to_vec()),
+    ];
+
+    assert!(to_optional_typed_values::<String>(values).is_err());
+}
+
+#[test]
+fn to_block_timeout_arg_values() {
+    assert_eq!(to_block_timeout_arg(Duration::from_secs(0)), "0");
+    assert_eq!(to_block_timeout_arg(Duration::from_secs(5)), "5");
+    assert_eq!(to_block_timeout_arg(Duration::from_millis(1500)), "1.500");
+    assert_eq!(to_block_timeout_arg(Duration::from_millis(20)), "0.020");
+    assert_eq!(to_block_timeout_arg(Duration::from_micros(500)), "0.001");
+    assert_eq!(to_block_timeout_arg(Duration::from_nanos(1)), "0.001");
+    assert_eq!(
+        to_block_timeout_arg(Duration::from_micros(2000001)),
+        "2.001"
+    );
+    assert_eq!(
+        to_block_timeout_arg(Duration::from_micros(999_999_999)),
+        "1000"
+    );
+}
+
+#[test]
+fn create_blocking_pop_args_valid() {
+    let args = create_blocking_pop_args(vec!["a", "b"], Duration::from_millis(250));
+
+    assert_eq!(args, vec!["a", "b", "0.250"]);
+}
+
+#[test]
+fn create_key_value_args_valid() {
+    let args = create_key_value_args(vec![("a", 1), ("b", 2)]);
+
+    assert_eq!(
+        args,
+        vec![b"a".to_vec(), b"1".to_vec(), b"b".to_vec(), b"2".to_vec()]
+    );
+}
+
+#[test]
+fn to_set_result_without_get() {
+    let options = SetOptions::default();
+
+    assert_eq!(
+        to_set_result(Some(b"OK".to_vec()), &options),
+        SetResult {
+            written: true,
+            previous: None,
+        }
+    );
+    assert_eq!(
+        to_set_result(None, &options),
+        SetResult {
+            written: false,
+            previous: None,
+        }
+    );
+}
+
+#[test]
+fn to_set_result_with_get() {
+    let mut options = SetOptions::default();
+    options.get = true;
+
+    assert_eq!(
+        to_set_result(None, &options),
+        SetResult {
+            written: true,
+            previous: None,
+        }
+    );
+
+    options.nx = true;
+    assert!(to_set_result(None, &options).written);
+    assert!(!to_set_result(Some(b"old".to_vec()), &options).written);
+
+    options.nx = false;
+    options.xx = true;
+    assert!(!to_set_result(None, &options).written);
+    assert_eq!(
+        to_set_result(Some(b"old".to_vec()), &options),
+        SetResult {
+            written: true,
+            previous: Some(b"old".to_vec()),
+        }
+    );
+}
+
+#[test]
+fn create_hash_fields_args_valid() {
+    let args = create_hash_fields_args("key", vec!["10".to_string()], vec!["a", "b"]);
+
+    assert_eq!(args, vec!["key", "10", "FIELDS", "2", "a", "b"]);
+}
+
+#[test]
+fn to_field_statuses_valid() {
+    let statuses = to_field_statuses(vec![-2, 0, 1, 2], HashFieldExpireStatus::from_reply).unwrap();
+
+    assert_eq!(
+        statuses,
+        vec![
+            HashFieldExpireStatus::NoField,
+            HashFieldExpireStatus::ConditionNotMet,
+            HashFieldExpireStatus::Set,
+            HashFieldExpireStatus::Deleted,
+        ]
+    );
+}
+
+#[test]
+fn to_field_statuses_invalid() {
+    assert!(to_field_statuses(vec![1, 5], HashFieldExpireStatus::from_reply).is_err());
+}
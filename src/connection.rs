@@ -8,24 +8,439 @@
 mod connection_test;
 
 use redis;
+use sentinel;
+use socket;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
 use std::option::Option;
-use types::{ErrorInfo, RedisEmptyResult, RedisError, RedisResult};
+use std::thread;
+use std::time::{Duration, Instant};
+#[cfg(feature = "tls")]
+use tls;
+use types;
+use types::{ErrorInfo, ErrorKind, RedisEmptyResult, RedisError, RedisResult, RetryPolicy};
+
+/// The scheme of TLS connection strings
+const TLS_SCHEME: &'static str = "rediss://";
+
+/// Commands which may change the data in a different way when invoked more than once
+static NON_IDEMPOTENT_COMMANDS: &'static [&'static str] = &[
+    "APPEND",
+    "BLMOVE",
+    "BLPOP",
+    "BRPOP",
+    "BRPOPLPUSH",
+    "BZPOPMAX",
+    "BZPOPMIN",
+    "DECR",
+    "DECRBY",
+    "EVAL",
+    "EVALSHA",
+    "EXEC",
+    "FCALL",
+    "GETDEL",
+    "GETSET",
+    "HGETDEL",
+    "HINCRBY",
+    "HINCRBYFLOAT",
+    "HSETNX",
+    "INCR",
+    "INCRBY",
+    "INCRBYFLOAT",
+    "LINSERT",
+    "LMOVE",
+    "LPOP",
+    "LPUSH",
+    "LPUSHX",
+    "LREM",
+    "LTRIM",
+    "MSETNX",
+    "PUBLISH",
+    "RPOP",
+    "RPOPLPUSH",
+    "RPUSH",
+    "RPUSHX",
+    "SETNX",
+    "SPOP",
+    "XADD",
+    "XAUTOCLAIM",
+    "XCLAIM",
+    "XREADGROUP",
+    "ZINCRBY",
+    "ZPOPMAX",
+    "ZPOPMIN",
+];
+
+/// The options which may precede the scores of a ZADD command
+static ZADD_OPTIONS: &'static [&'static str] = &["NX", "XX", "GT", "LT", "CH", "INCR"];
+
+/// Commands which accept an expiration condition (NX/XX/GT/LT) following the key and the expiration
+static CONDITIONAL_EXPIRE_COMMANDS: &'static [&'static str] = &[
+    "EXPIRE",
+    "EXPIREAT",
+    "HEXPIRE",
+    "HEXPIREAT",
+    "HPEXPIRE",
+    "HPEXPIREAT",
+    "PEXPIRE",
+    "PEXPIREAT",
+];
+
+/// The expiration conditions
+static EXPIRE_CONDITIONS: &'static [&'static str] = &["NX", "XX", "GT", "LT"];
+
+/// Commands which never modify the data and may be invoked on replicas
+static READ_ONLY_COMMANDS: &'static [&'static str] = &[
+    "BITCOUNT",
+    "BITPOS",
+    "DBSIZE",
+    "DUMP",
+    "EXISTS",
+    "FCALL_RO",
+    "GEODIST",
+    "GEOHASH",
+    "GEOPOS",
+    "GET",
+    "GETBIT",
+    "GETRANGE",
+    "HEXISTS",
+    "HGET",
+    "HGETALL",
+    "HKEYS",
+    "HLEN",
+    "HMGET",
+    "HPTTL",
+    "HRANDFIELD",
+    "HSTRLEN",
+    "HTTL",
+    "HVALS",
+    "KEYS",
+    "LINDEX",
+    "LLEN",
+    "LPOS",
+    "LRANGE",
+    "MGET",
+    "PFCOUNT",
+    "PTTL",
+    "RANDOMKEY",
+    "SCARD",
+    "SDIFF",
+    "SINTER",
+    "SINTERCARD",
+    "SISMEMBER",
+    "SMEMBERS",
+    "SMISMEMBER",
+    "SRANDMEMBER",
+    "STRLEN",
+    "SUNION",
+    "TTL",
+    "TYPE",
+    "XLEN",
+    "XPENDING",
+    "XRANGE",
+    "XREAD",
+    "XREVRANGE",
+    "ZCARD",
+    "ZCOUNT",
+    "ZLEXCOUNT",
+    "ZMSCORE",
+    "ZRANDMEMBER",
+    "ZRANGE",
+    "ZRANGEBYLEX",
+    "ZRANGEBYSCORE",
+    "ZRANK",
+    "ZREVRANGE",
+    "ZREVRANGEBYLEX",
+    "ZREVRANGEBYSCORE",
+    "ZREVRANK",
+    "ZSCORE",
+];
+
+/// Holds the options applied on every new connection.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConnectionOptions {
+    /// The max time to wait for the connection to be established
+    pub(crate) connect_timeout: Option<Duration>,
+    /// The default read timeout of the connection
+    pub(crate) read_timeout: Option<Duration>,
+    /// The default write timeout of the connection
+    pub(crate) write_timeout: Option<Duration>,
+    /// If true, TCP_NODELAY is set on every TCP connection
+    pub(crate) tcp_nodelay: bool,
+    /// If set, TCP keepalive is enabled on every TCP connection using this idle time before probing
+    pub(crate) tcp_keepalive: Option<Duration>,
+    /// The client name set on every connection (see redis [CLIENT SETNAME](https://redis.io/commands/client-setname))
+    pub(crate) client_name: Option<String>,
+    /// If set, the server address is resolved via redis sentinel on every new connection
+    pub(crate) sentinel: Option<sentinel::SentinelConfig>,
+    /// If set, connections are created using TLS
+    #[cfg(feature = "tls")]
+    pub(crate) tls: Option<tls::TlsOptions>,
+}
+
+impl ConnectionOptions {
+    /// Returns true if connections are created using TLS.
+    #[cfg(feature = "tls")]
+    pub(crate) fn is_tls(self: &ConnectionOptions) -> bool {
+        self.tls.is_some()
+    }
+
+    /// Returns true if connections are created using TLS.
+    #[cfg(not(feature = "tls"))]
+    pub(crate) fn is_tls(self: &ConnectionOptions) -> bool {
+        false
+    }
+}
 
 /// The redis client which enables to invoke redis operations.
 pub(crate) struct Connection {
     /// Holds the current redis connection
-    connection: Option<redis::Connection>,
+    connection: Option<socket::Connection>,
+    /// Incremented every time a new connection is created
+    generation: u64,
+    /// Defines how failed commands are retried
+    retry_policy: RetryPolicy,
+    /// The options applied on every new connection
+    options: ConnectionOptions,
+    /// If set, commands fail with a timeout error in case they are not completed within this duration
+    command_timeout: Option<Duration>,
+    /// If set, connections which were not used for longer than this interval are verified before being used
+    health_check_interval: Option<Duration>,
+    /// The last time the connection was used
+    last_used: Instant,
+}
+
+/// Returns true if invoking the provided command more than once has the same effect as invoking it once.
+pub(crate) fn is_idempotent_command(command: &str) -> bool {
+    let command = command.to_uppercase();
+
+    !NON_IDEMPOTENT_COMMANDS.contains(&command.as_str())
+}
+
+/// Returns true if invoking the provided command with the provided arguments more than once has the same effect
+/// as invoking it once.<br>
+/// Unlike a plain SET, a conditional SET (NX/XX) or a SET which returns the previous value (GET) reports a different
+/// result once replayed, a ZADD with INCR increments the score again, and a conditional expiration (for example
+/// HEXPIRE with NX) reports the condition was not met.
+pub(crate) fn is_idempotent_invocation<A: AsRef<[u8]>>(command: &str, args: &[A]) -> bool {
+    if !is_idempotent_command(command) {
+        return false;
+    }
+
+    if command.eq_ignore_ascii_case("SET") {
+        // the first arguments are the key and value, so they are never treated as options
+        return !args.iter().skip(2).any(|arg| {
+            let arg = arg.as_ref();
+            arg.eq_ignore_ascii_case(b"NX")
+                || arg.eq_ignore_ascii_case(b"XX")
+                || arg.eq_ignore_ascii_case(b"GET")
+        });
+    }
+
+    if command.eq_ignore_ascii_case("ZADD") {
+        // the options are placed between the key and the first score, so members are never treated as options
+        return !args
+            .iter()
+            .skip(1)
+            .take_while(|arg| {
+                let arg = arg.as_ref();
+                ZADD_OPTIONS
+                    .iter()
+                    .any(|option| arg.eq_ignore_ascii_case(option.as_bytes()))
+            })
+            .any(|arg| arg.as_ref().eq_ignore_ascii_case(b"INCR"));
+    }
+
+    if CONDITIONAL_EXPIRE_COMMANDS.contains(&command.to_uppercase().as_str()) {
+        // the condition follows the key and the expiration and precedes the hash fields (if any)
+        return !args
+            .iter()
+            .skip(2)
+            .take_while(|arg| !arg.as_ref().eq_ignore_ascii_case(b"FIELDS"))
+            .any(|arg| {
+                let arg = arg.as_ref();
+                EXPIRE_CONDITIONS
+                    .iter()
+                    .any(|condition| arg.eq_ignore_ascii_case(condition.as_bytes()))
+            });
+    }
+
+    true
+}
+
+/// Returns true if the provided command never modifies the data.
+pub(crate) fn is_read_only_command(command: &str) -> bool {
+    let command = command.to_uppercase();
+
+    READ_ONLY_COMMANDS.contains(&command.as_str())
+}
+
+/// Returns the delay to wait before the provided retry attempt (starting from 1).
+fn get_retry_delay(retry_policy: &RetryPolicy, retry: u32) -> Duration {
+    let backoff = retry_policy.get_backoff(retry);
+
+    if retry_policy.jitter {
+        // keep at least half of the backoff and randomize the rest
+        let millis = backoff.as_secs() * 1000 + u64::from(backoff.subsec_millis());
+        let half = millis / 2;
+
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u32(retry);
+        let random = hasher.finish();
+
+        Duration::from_millis(half + random % (millis - half + 1))
+    } else {
+        backoff
+    }
+}
+
+/// Returns true if the provided connection string requires a TLS connection.
+pub(crate) fn is_tls_url(connection_string: &str) -> bool {
+    connection_string.starts_with(TLS_SCHEME)
+}
+
+/// Parses the connection string into the redis connection info.<br>
+/// Unix socket urls without a host (for example `unix:///var/run/redis.sock`) are not supported by the underlying
+/// redis url parsing, so they are converted to the equivalent url with the localhost host.
+/// TLS urls (`rediss://`) are parsed as the equivalent plain url, as TLS is handled by this library.
+pub(crate) fn parse_connection_info(connection_string: &str) -> RedisResult<redis::ConnectionInfo> {
+    let mut normalized = connection_string.to_string();
+
+    if is_tls_url(connection_string) {
+        if !cfg!(feature = "tls") {
+            return Err(RedisError {
+                info: ErrorInfo::RedisError(redis::RedisError::from((
+                    redis::ErrorKind::InvalidClientConfig,
+                    "TLS connections require the tls feature.",
+                ))),
+            });
+        }
+
+        normalized = format!("redis://{}", &connection_string[TLS_SCHEME.len()..]);
+    }
+
+    for scheme in &["unix://", "redis+unix://"] {
+        if connection_string.starts_with(scheme)
+            && connection_string[scheme.len()..].starts_with('/')
+        {
+            normalized = format!("{}localhost{}", scheme, &connection_string[scheme.len()..]);
+            break;
+        }
+    }
+
+    match redis::IntoConnectionInfo::into_connection_info(normalized.as_str()) {
+        Ok(connection_info) => Ok(connection_info),
+        Err(error) => Err(RedisError {
+            info: ErrorInfo::RedisError(error),
+        }),
+    }
+}
+
+/// Creates a new redis connection to the provided server (or the server resolved via the sentinels) and applies
+/// the provided options on it.
+pub(crate) fn connect(
+    connection_info: &redis::ConnectionInfo,
+    options: &ConnectionOptions,
+) -> redis::RedisResult<socket::Connection> {
+    match options.sentinel {
+        Some(ref sentinel_config) => sentinel::connect(sentinel_config, options),
+        None => socket::connect(connection_info, options),
+    }
+}
+
+/// Creates a new redis pubsub connection to the provided server (or the master resolved via the sentinels) and
+/// applies the provided options on it.
+pub(crate) fn connect_pubsub(
+    connection_info: &redis::ConnectionInfo,
+    options: &ConnectionOptions,
+) -> redis::RedisResult<socket::PubSub> {
+    match options.sentinel {
+        Some(ref sentinel_config) => sentinel::connect_pubsub(sentinel_config, options),
+        None => socket::connect_pubsub(connection_info, options),
+    }
+}
+
+/// Invokes the provided function with the connection read and write timeouts set to the provided timeouts.<br>
+/// Once done, the default timeouts are restored and the returned flag is false in case they could not be restored.
+fn run_with_timeout<T, F>(
+    connection: &socket::Connection,
+    timeouts: Option<(Option<Duration>, Option<Duration>)>,
+    default_timeouts: (Option<Duration>, Option<Duration>),
+    func: &mut F,
+) -> (RedisResult<T>, bool)
+where
+    F: FnMut(&socket::Connection) -> RedisResult<T>,
+{
+    match timeouts {
+        Some((read_timeout, write_timeout)) => {
+            let (default_read_timeout, default_write_timeout) = default_timeouts;
+
+            let updated = connection
+                .set_read_timeout(read_timeout)
+                .and_then(|_| connection.set_write_timeout(write_timeout));
+            let result = match updated {
+                Ok(_) => func(connection),
+                Err(error) => Err(RedisError {
+                    info: ErrorInfo::RedisError(error),
+                }),
+            };
+
+            let restored = connection
+                .set_read_timeout(default_read_timeout)
+                .and_then(|_| connection.set_write_timeout(default_write_timeout))
+                .is_ok();
+
+            match result {
+                Err(ref error) if error.kind() == ErrorKind::Timeout => (
+                    Err(RedisError {
+                        info: ErrorInfo::TimeoutError("Timeout while waiting for redis response."),
+                    }),
+                    restored,
+                ),
+                _ => (result, restored),
+            }
+        }
+        None => (func(connection), true),
+    }
+}
+
+/// Returns the connection timeouts to use for a single invocation, or None to use the default timeouts.<br>
+/// In case a command timeout is defined, the remaining time is used for both timeouts.<br>
+/// Otherwise, commands which block on the server side (for example BLPOP) extend the default read timeout by the
+/// block time, as the server legitimately does not respond while blocking (a block time of zero blocks forever).
+fn get_invocation_timeouts(
+    default_timeouts: (Option<Duration>, Option<Duration>),
+    block: Option<Duration>,
+    remaining: Option<Duration>,
+) -> Option<(Option<Duration>, Option<Duration>)> {
+    let (read_timeout, write_timeout) = default_timeouts;
+
+    match (remaining, block) {
+        (Some(remaining), _) => Some((Some(remaining), Some(remaining))),
+        (None, Some(block)) if block == Duration::from_secs(0) => Some((None, write_timeout)),
+        (None, Some(block)) => Some((read_timeout.map(|timeout| timeout + block), write_timeout)),
+        (None, None) => None,
+    }
 }
 
-/// If the client connection is not open or not valid, this function will create
-/// a new redis connection and modify the client to store this new connection.
-fn open_connection(connection: &mut Connection, client: &redis::Client) -> RedisEmptyResult {
-    let output: RedisEmptyResult;
+/// If the client connection is not created or found to be broken by the periodic health check, this function
+/// will create a new redis connection and modify the client to store this new connection.<br>
+/// Otherwise, the connection is assumed to be valid and broken connections are detected by the command IO errors.
+fn open_connection(
+    connection: &mut Connection,
+    connection_info: &redis::ConnectionInfo,
+) -> RedisEmptyResult {
+    connection.drop_closed_connection();
+
+    if connection.is_health_check_due() && !connection.is_connection_open() {
+        connection.close();
+    }
 
-    if !connection.is_connection_open() {
-        output = match client.get_connection() {
+    let output = if connection.connection.is_none() {
+        match connect(connection_info, &connection.options) {
             Ok(redis_connection) => {
                 connection.connection = Some(redis_connection);
+                connection.generation = connection.generation + 1;
                 Ok(())
             }
             Err(error) => Err(RedisError {
@@ -33,7 +448,11 @@ fn open_connection(connection: &mut Connection, client: &redis::Client) -> Redis
             }),
         }
     } else {
-        output = Ok(());
+        Ok(())
+    };
+
+    if output.is_ok() {
+        connection.last_used = Instant::now();
     }
 
     output
@@ -58,16 +477,213 @@ impl Connection {
         open
     }
 
+    /// Returns true if the connection exists and was not used for longer than the health check interval.
+    fn is_health_check_due(self: &Connection) -> bool {
+        match self.health_check_interval {
+            Some(interval) => self.connection.is_some() && self.last_used.elapsed() >= interval,
+            None => false,
+        }
+    }
+
+    /// Sets the health check interval (None to disable the health check).<br>
+    /// Connections which were not used for longer than this interval are verified using PING before being used.
+    pub(crate) fn set_health_check_interval(
+        self: &mut Connection,
+        health_check_interval: Option<Duration>,
+    ) {
+        self.health_check_interval = health_check_interval;
+    }
+
+    /// Sets the max duration of every command invocation (None for no timeout).<br>
+    /// Commands which are not completed within this duration fail with a timeout error and the connection is
+    /// dropped, as the late response may still be sent by redis.
+    pub(crate) fn set_command_timeout(self: &mut Connection, command_timeout: Option<Duration>) {
+        self.command_timeout = command_timeout;
+    }
+
+    /// Returns the max duration of every command invocation (None for no timeout).
+    pub(crate) fn get_command_timeout(self: &Connection) -> Option<Duration> {
+        self.command_timeout
+    }
+
+    /// Restores the default read and write timeouts of the currently stored connection.<br>
+    /// In case they could not be restored, the connection is dropped.
+    pub(crate) fn restore_timeouts(self: &mut Connection) {
+        let restored = match self.connection {
+            Some(ref redis_connection) => redis_connection
+                .set_read_timeout(self.options.read_timeout)
+                .and_then(|_| redis_connection.set_write_timeout(self.options.write_timeout))
+                .is_ok(),
+            None => true,
+        };
+
+        if !restored {
+            self.close();
+        }
+    }
+
+    /// Returns true if a connection was created, regardless if it is still valid.
+    pub(crate) fn is_connection_created(self: &Connection) -> bool {
+        self.connection.is_some()
+    }
+
+    /// Returns the connection generation which changes every time a new connection is created.<br>
+    /// This enables to detect reconnections between operations which depend on the connection state.
+    pub(crate) fn get_generation(self: &Connection) -> u64 {
+        self.generation
+    }
+
+    /// Returns the currently stored connection without validating it.<br>
+    /// Unlike get_redis_connection, this function will never reconnect, which is required for operations that
+    /// depend on the connection state (for example transactions).
+    pub(crate) fn get_open_redis_connection(self: &Connection) -> RedisResult<&socket::Connection> {
+        match self.connection {
+            Some(ref redis_connection) => Ok(redis_connection),
+            None => Err(RedisError {
+                info: ErrorInfo::Description(types::CONNECTION_NOT_AVAILABLE),
+            }),
+        }
+    }
+
+    /// Drops the currently stored connection.<br>
+    /// A new connection will be created by the next operation.
+    pub(crate) fn close(self: &mut Connection) {
+        self.connection = None;
+    }
+
+    /// Drops the currently stored connection in case it was closed due to an IO or protocol error (for example
+    /// after an error in the middle of a response).
+    pub(crate) fn drop_closed_connection(self: &mut Connection) {
+        let closed = match self.connection {
+            Some(ref redis_connection) => redis_connection.is_closed(),
+            None => false,
+        };
+
+        if closed {
+            self.close();
+        }
+    }
+
+    /// Invokes the provided function with a valid connection, reconnecting and retrying the invocation based
+    /// on the retry policy.<br>
+    /// Failures to connect are always retryable as nothing was sent to redis, while failures of the function
+    /// itself are only retried if the invocation is idempotent or the policy allows retrying non idempotent
+    /// invocations.<br>
+    /// Commands which may block on the server side provide the block time (zero to block forever), which extends
+    /// the connection read timeout so the blocking command is not considered to be a broken connection.
+    pub(crate) fn run<T, F>(
+        self: &mut Connection,
+        connection_info: &redis::ConnectionInfo,
+        idempotent: bool,
+        block: Option<Duration>,
+        mut func: F,
+    ) -> RedisResult<T>
+    where
+        F: FnMut(&socket::Connection) -> RedisResult<T>,
+    {
+        let mut attempt = 1;
+        let mut replayed = false;
+        let deadline = self.command_timeout.map(|timeout| Instant::now() + timeout);
+        let default_timeouts = (self.options.read_timeout, self.options.write_timeout);
+
+        loop {
+            let remaining = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Err(RedisError {
+                            info: ErrorInfo::TimeoutError(
+                                "Timeout while waiting for redis response.",
+                            ),
+                        });
+                    }
+
+                    Some(deadline - now)
+                }
+                None => None,
+            };
+            let timeouts = get_invocation_timeouts(default_timeouts, block, remaining);
+
+            let reused = self.connection.is_some();
+            let generation = self.generation;
+
+            let (result, sent, restored) = match self.get_redis_connection(connection_info) {
+                Ok(connection) => {
+                    let (result, restored) =
+                        run_with_timeout(connection, timeouts, default_timeouts, &mut func);
+                    (result, true, restored)
+                }
+                Err(error) => (Err(error), false, true),
+            };
+
+            // the connection timeouts are unknown, so a new connection is created on next invocation
+            if !restored {
+                self.close();
+            }
+
+            self.drop_closed_connection();
+
+            match result {
+                Err(error) => {
+                    // a read only error means the connection is to a replica (for example after a failover)
+                    if error.kind() == ErrorKind::ReadOnly {
+                        self.close();
+                    }
+
+                    // the connection state is unknown, so a new connection is created on next invocation
+                    if error.is_connection_error() {
+                        self.close();
+
+                        // connections are not verified before use, so a connection which was closed while idle
+                        // is only detected once used, in which case the invocation is replayed on a new connection
+                        let stale = sent && reused && generation == self.generation;
+                        let io_error = error.kind() == ErrorKind::Io;
+                        if stale && io_error && idempotent && !replayed {
+                            replayed = true;
+                            continue;
+                        }
+                    }
+
+                    let retry = attempt < self.retry_policy.max_attempts
+                        && self.retry_policy.is_retryable(&error)
+                        && (!sent || idempotent || self.retry_policy.retry_non_idempotent);
+
+                    if !retry {
+                        return Err(error);
+                    }
+
+                    thread::sleep(get_retry_delay(&self.retry_policy, attempt));
+                    attempt = attempt + 1;
+                }
+                output => return output,
+            }
+        }
+    }
+
+    /// Returns a valid connection, verifying a previously created connection (using PING) before it is used.<br>
+    /// Operations which can't be invoked again on a new connection once started (for example transactions) must
+    /// use this function, as connections are otherwise only verified by the periodic health check.
+    pub(crate) fn get_verified_redis_connection(
+        self: &mut Connection,
+        connection_info: &redis::ConnectionInfo,
+    ) -> RedisResult<&socket::Connection> {
+        if self.connection.is_some() && !self.is_connection_open() {
+            self.close();
+        }
+
+        self.get_redis_connection(connection_info)
+    }
+
     pub(crate) fn get_redis_connection(
         self: &mut Connection,
-        client: &redis::Client,
-    ) -> RedisResult<&redis::Connection> {
-        match open_connection(self, client) {
+        connection_info: &redis::ConnectionInfo,
+    ) -> RedisResult<&socket::Connection> {
+        match open_connection(self, connection_info) {
             Err(error) => Err(error),
             _ => match self.connection {
                 Some(ref redis_connection) => Ok(redis_connection),
                 None => Err(RedisError {
-                    info: ErrorInfo::Description("Redis connection not available."),
+                    info: ErrorInfo::Description(types::CONNECTION_NOT_AVAILABLE),
                 }),
             },
         }
@@ -75,6 +691,14 @@ impl Connection {
 }
 
 /// Creates and returns a new connection
-pub(crate) fn create() -> Connection {
-    Connection { connection: None }
+pub(crate) fn create(retry_policy: RetryPolicy, options: ConnectionOptions) -> Connection {
+    Connection {
+        connection: None,
+        generation: 0,
+        retry_policy,
+        options,
+        command_timeout: None,
+        health_check_interval: None,
+        last_used: Instant::now(),
+    }
 }
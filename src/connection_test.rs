@@ -2,6 +2,243 @@ use super::*;
 
 #[test]
 fn create_check_state() {
-    let connection = create();
+    let connection = create(RetryPolicy::default(), ConnectionOptions::default());
     assert!(!connection.is_connection_open());
+    assert_eq!(connection.get_generation(), 0);
+}
+
+#[test]
+fn is_idempotent_command_check() {
+    assert!(is_idempotent_command("GET"));
+    assert!(is_idempotent_command("set"));
+    assert!(is_idempotent_command("DEL"));
+    assert!(!is_idempotent_command("INCR"));
+    assert!(!is_idempotent_command("lpush"));
+    assert!(!is_idempotent_command("EVALSHA"));
+}
+
+#[test]
+fn is_idempotent_invocation_check() {
+    assert!(is_idempotent_invocation("SET", &["key", "value"]));
+    assert!(is_idempotent_invocation(
+        "set",
+        &["key", "value", "EX", "10"]
+    ));
+    assert!(is_idempotent_invocation("SET", &["nx", "get"]));
+    assert!(!is_idempotent_invocation("SET", &["key", "value", "nx"]));
+    assert!(!is_idempotent_invocation("SET", &["key", "value", "XX"]));
+    assert!(!is_idempotent_invocation("SET", &["key", "value", "GET"]));
+    assert!(is_idempotent_invocation("GET", &["key"]));
+    assert!(!is_idempotent_invocation("INCR", &["key"]));
+    assert!(!is_idempotent_invocation(
+        "HSETNX",
+        &["key", "field", "value"]
+    ));
+    assert!(!is_idempotent_invocation("LREM", &["key", "1", "value"]));
+    assert!(!is_idempotent_invocation("LTRIM", &["key", "1", "-1"]));
+    assert!(is_idempotent_invocation("ZADD", &["key", "1", "member"]));
+    assert!(is_idempotent_invocation("ZADD", &["key", "1", "incr"]));
+    assert!(!is_idempotent_invocation(
+        "ZADD",
+        &["key", "XX", "INCR", "1", "member"]
+    ));
+    assert!(!is_idempotent_invocation(
+        "zadd",
+        &["key", "incr", "1", "member"]
+    ));
+    assert!(is_idempotent_invocation(
+        "HEXPIRE",
+        &["key", "10", "FIELDS", "2", "NX", "b"]
+    ));
+    assert!(!is_idempotent_invocation(
+        "HEXPIRE",
+        &["key", "10", "NX", "FIELDS", "1", "a"]
+    ));
+    assert!(!is_idempotent_invocation(
+        "hpexpire",
+        &["key", "100", "gt", "FIELDS", "1", "a"]
+    ));
+    assert!(is_idempotent_invocation("EXPIRE", &["key", "10"]));
+    assert!(!is_idempotent_invocation("EXPIRE", &["key", "10", "XX"]));
+}
+
+#[test]
+fn get_retry_delay_no_jitter() {
+    let mut retry_policy = RetryPolicy::default();
+    retry_policy.jitter = false;
+    retry_policy.initial_backoff = Duration::from_millis(100);
+    retry_policy.max_backoff = Duration::from_millis(500);
+
+    assert_eq!(
+        get_retry_delay(&retry_policy, 1),
+        Duration::from_millis(100)
+    );
+    assert_eq!(
+        get_retry_delay(&retry_policy, 2),
+        Duration::from_millis(200)
+    );
+    assert_eq!(
+        get_retry_delay(&retry_policy, 3),
+        Duration::from_millis(400)
+    );
+    assert_eq!(
+        get_retry_delay(&retry_policy, 4),
+        Duration::from_millis(500)
+    );
+    assert_eq!(
+        get_retry_delay(&retry_policy, 20),
+        Duration::from_millis(500)
+    );
+}
+
+#[test]
+fn get_retry_delay_with_jitter() {
+    let mut retry_policy = RetryPolicy::default();
+    retry_policy.jitter = true;
+    retry_policy.initial_backoff = Duration::from_millis(100);
+    retry_policy.max_backoff = Duration::from_millis(500);
+
+    for retry in 1..10 {
+        let backoff = retry_policy.get_backoff(retry);
+        let delay = get_retry_delay(&retry_policy, retry);
+
+        assert!(delay <= backoff);
+        assert!(delay >= backoff / 2);
+    }
+}
+
+#[test]
+fn run_connection_error_retries() {
+    let connection_info = parse_connection_info("redis://127.0.0.1:1/").unwrap();
+    let mut retry_policy = RetryPolicy::default();
+    retry_policy.max_attempts = 3;
+    retry_policy.initial_backoff = Duration::from_millis(1);
+    let mut connection = create(retry_policy, ConnectionOptions::default());
+
+    let result: RedisResult<()> = connection.run(&connection_info, false, None, |_| Ok(()));
+
+    assert!(result.is_err());
+    assert!(result.unwrap_err().is_connection_error());
+    assert!(!connection.is_connection_created());
+}
+
+#[test]
+fn get_invocation_timeouts_defaults() {
+    let defaults = (Some(Duration::from_secs(1)), Some(Duration::from_secs(2)));
+
+    assert_eq!(get_invocation_timeouts(defaults, None, None), None);
+}
+
+#[test]
+fn get_invocation_timeouts_blocking() {
+    let defaults = (Some(Duration::from_secs(1)), Some(Duration::from_secs(2)));
+
+    assert_eq!(
+        get_invocation_timeouts(defaults, Some(Duration::from_secs(5)), None),
+        Some((Some(Duration::from_secs(6)), Some(Duration::from_secs(2))))
+    );
+    assert_eq!(
+        get_invocation_timeouts(defaults, Some(Duration::from_secs(0)), None),
+        Some((None, Some(Duration::from_secs(2))))
+    );
+    assert_eq!(
+        get_invocation_timeouts((None, None), Some(Duration::from_secs(5)), None),
+        Some((None, None))
+    );
+}
+
+#[test]
+fn get_invocation_timeouts_command_timeout() {
+    let defaults = (Some(Duration::from_secs(1)), Some(Duration::from_secs(2)));
+    let remaining = Some(Duration::from_secs(3));
+
+    assert_eq!(
+        get_invocation_timeouts(defaults, None, remaining),
+        Some((remaining, remaining))
+    );
+    assert_eq!(
+        get_invocation_timeouts(defaults, Some(Duration::from_secs(5)), remaining),
+        Some((remaining, remaining))
+    );
+}
+
+#[test]
+fn is_health_check_due_check() {
+    let mut connection = create(RetryPolicy::default(), ConnectionOptions::default());
+    assert!(!connection.is_health_check_due());
+
+    connection.set_health_check_interval(Some(Duration::from_secs(0)));
+    // no connection to verify
+    assert!(!connection.is_health_check_due());
+
+    connection.set_health_check_interval(None);
+    assert!(!connection.is_health_check_due());
+}
+
+#[test]
+fn parse_connection_info_tcp() {
+    let connection_info = parse_connection_info("redis://127.0.0.1:6380/2").unwrap();
+
+    assert_eq!(
+        *connection_info.addr,
+        redis::ConnectionAddr::Tcp("127.0.0.1".to_string(), 6380)
+    );
+    assert_eq!(connection_info.db, 2);
+}
+
+#[test]
+fn parse_connection_info_invalid() {
+    assert!(parse_connection_info("test/bad/url").is_err());
+}
+
+#[test]
+#[cfg(unix)]
+fn parse_connection_info_unix() {
+    let connection_info = parse_connection_info("unix:///tmp/redis.sock?db=3").unwrap();
+    assert_eq!(
+        *connection_info.addr,
+        redis::ConnectionAddr::Unix(std::path::PathBuf::from("/tmp/redis.sock"))
+    );
+    assert_eq!(connection_info.db, 3);
+
+    let connection_info = parse_connection_info("redis+unix:///tmp/redis.sock").unwrap();
+    assert_eq!(
+        *connection_info.addr,
+        redis::ConnectionAddr::Unix(std::path::PathBuf::from("/tmp/redis.sock"))
+    );
+    assert_eq!(connection_info.db, 0);
+
+    let connection_info = parse_connection_info("unix://localhost/tmp/redis.sock").unwrap();
+    assert_eq!(
+        *connection_info.addr,
+        redis::ConnectionAddr::Unix(std::path::PathBuf::from("/tmp/redis.sock"))
+    );
+}
+
+#[test]
+#[cfg(feature = "tls")]
+fn parse_connection_info_tls() {
+    assert!(is_tls_url("rediss://127.0.0.1:6380/1"));
+
+    let connection_info = parse_connection_info("rediss://127.0.0.1:6380/1").unwrap();
+    assert_eq!(
+        *connection_info.addr,
+        redis::ConnectionAddr::Tcp("127.0.0.1".to_string(), 6380)
+    );
+    assert_eq!(connection_info.db, 1);
+}
+
+#[test]
+#[cfg(not(feature = "tls"))]
+fn parse_connection_info_tls_disabled() {
+    let error = parse_connection_info("rediss://127.0.0.1:6380/1").unwrap_err();
+    assert_eq!(error.kind(), ::types::ErrorKind::ClientConfig);
+}
+
+#[test]
+fn is_read_only_command_check() {
+    assert!(is_read_only_command("GET"));
+    assert!(is_read_only_command("zrange"));
+    assert!(!is_read_only_command("SET"));
+    assert!(!is_read_only_command("SCAN"));
 }
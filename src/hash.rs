@@ -0,0 +1,152 @@
+//! # hash
+//!
+//! Enables storing structs as redis hashes, converting each struct field to and from a hash field.
+//!
+
+#[cfg(test)]
+#[path = "./hash_test.rs"]
+mod hash_test;
+
+use redis;
+use std::collections::HashMap;
+use std::str::FromStr;
+use types::{ErrorInfo, RedisError, RedisResult};
+
+/// Defines a struct which is stored as a redis hash (see the hset_struct and hgetall_struct client functions).<br>
+/// The trait can be implemented manually using the get_field and get_optional_field functions, or generated
+/// using the redis_hash macro in case all struct fields implement ToString and FromStr.<br>
+/// No derive macro is provided, so structs with optional fields or generic structs must implement the trait
+/// manually (as in the example below).<br>
+/// Storing a struct replaces the entire hash, so optional fields which are None are removed from the hash.
+///
+/// # Example
+///
+/// ```
+/// use simple_redis::hash::{get_field, get_optional_field, RedisHash};
+/// use simple_redis::types::RedisResult;
+/// use std::collections::HashMap;
+///
+/// struct User {
+///     name: String,
+///     age: u32,
+///     nickname: Option<String>,
+/// }
+///
+/// impl RedisHash for User {
+///     fn to_redis_hash(&self) -> Vec<(String, String)> {
+///         let mut fields = vec![
+///             ("name".to_string(), self.name.clone()),
+///             ("age".to_string(), self.age.to_string()),
+///         ];
+///         if let Some(ref nickname) = self.nickname {
+///             fields.push(("nickname".to_string(), nickname.clone()));
+///         }
+///
+///         fields
+///     }
+///
+///     fn from_redis_hash(fields: &HashMap<String, String>) -> RedisResult<User> {
+///         Ok(User {
+///             name: get_field(fields, "name")?,
+///             age: get_field(fields, "age")?,
+///             nickname: get_optional_field(fields, "nickname")?,
+///         })
+///     }
+/// }
+/// ```
+pub trait RedisHash: Sized {
+    /// Returns the hash (field, value) pairs representing this struct.
+    fn to_redis_hash(&self) -> Vec<(String, String)>;
+
+    /// Creates a new struct from the provided hash fields.
+    fn from_redis_hash(fields: &HashMap<String, String>) -> RedisResult<Self>;
+}
+
+fn create_field_error(description: &'static str, name: &str) -> RedisError {
+    RedisError {
+        info: ErrorInfo::RedisError(redis::RedisError::from((
+            redis::ErrorKind::TypeError,
+            description,
+            name.to_string(),
+        ))),
+    }
+}
+
+/// Returns the typed value of the requested hash field.<br>
+/// In case the field is missing or can't be parsed, the returned error holds the field name.
+pub fn get_field<T: FromStr>(fields: &HashMap<String, String>, name: &str) -> RedisResult<T> {
+    match get_optional_field(fields, name)? {
+        Some(value) => Ok(value),
+        None => Err(create_field_error("Missing hash field", name)),
+    }
+}
+
+/// Returns the typed value of the requested hash field or None in case the field is missing.<br>
+/// In case the field can't be parsed, the returned error holds the field name.
+pub fn get_optional_field<T: FromStr>(
+    fields: &HashMap<String, String>,
+    name: &str,
+) -> RedisResult<Option<T>> {
+    match fields.get(name) {
+        Some(value) => match T::from_str(value) {
+            Ok(typed_value) => Ok(Some(typed_value)),
+            _ => Err(create_field_error("Unable to parse hash field", name)),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Implements the RedisHash trait for the provided struct, using the struct field names as the hash field names.<br>
+/// All struct fields must be listed and implement ToString and FromStr.<br>
+/// This is a declarative macro and not a derive, so it does not support Option fields or generic structs, which
+/// should implement the trait manually using the get_optional_field function (see RedisHash).
+///
+/// # Example
+///
+/// ```
+/// #[macro_use]
+/// extern crate simple_redis;
+///
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// redis_hash!(User { name, age });
+///
+/// fn main() {
+/// #   let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+///     let user = User {
+///         name: "my_name".to_string(),
+///         age: 30,
+///     };
+///
+///     match client.hset_struct("my_user", &user) {
+///         Err(error) => println!("Unable to store user: {}", error),
+///         _ => println!("User stored"),
+///     }
+///
+///     match client.hgetall_struct::<User>("my_user") {
+///         Ok(user) => println!("Read user: {} {}", user.name, user.age),
+///         Err(error) => println!("Unable to read user: {}", error),
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! redis_hash {
+    ($name:ident { $($field:ident),* $(,)* }) => {
+        impl $crate::hash::RedisHash for $name {
+            fn to_redis_hash(&self) -> Vec<(String, String)> {
+                vec![$((stringify!($field).to_string(), self.$field.to_string())),*]
+            }
+
+            fn from_redis_hash(
+                fields: &::std::collections::HashMap<String, String>,
+            ) -> $crate::types::RedisResult<$name> {
+                Ok($name {
+                    $($field: $crate::hash::get_field(fields, stringify!($field))?,)*
+                })
+            }
+        }
+    };
+}
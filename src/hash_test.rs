@@ -0,0 +1,98 @@
+use super::*;
+use types::ErrorKind;
+
+#[derive(Debug, PartialEq)]
+struct TestUser {
+    name: String,
+    age: u32,
+    nickname: Option<String>,
+}
+
+impl RedisHash for TestUser {
+    fn to_redis_hash(&self) -> Vec<(String, String)> {
+        let mut fields = vec![
+            ("name".to_string(), self.name.clone()),
+            ("age".to_string(), self.age.to_string()),
+        ];
+        if let Some(ref nickname) = self.nickname {
+            fields.push(("nickname".to_string(), nickname.clone()));
+        }
+
+        fields
+    }
+
+    fn from_redis_hash(fields: &HashMap<String, String>) -> RedisResult<TestUser> {
+        Ok(TestUser {
+            name: get_field(fields, "name")?,
+            age: get_field(fields, "age")?,
+            nickname: get_optional_field(fields, "nickname")?,
+        })
+    }
+}
+
+fn create_fields(pairs: Vec<(&str, &str)>) -> HashMap<String, String> {
+    pairs
+        .into_iter()
+        .map(|(field, value)| (field.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn round_trip() {
+    let user = TestUser {
+        name: "test".to_string(),
+        age: 30,
+        nickname: Some("nick".to_string()),
+    };
+
+    let fields: HashMap<String, String> = user.to_redis_hash().into_iter().collect();
+    let output = TestUser::from_redis_hash(&fields).unwrap();
+
+    assert_eq!(output, user);
+}
+
+#[test]
+fn get_field_valid() {
+    let fields = create_fields(vec![("age", "30")]);
+
+    let age: u32 = get_field(&fields, "age").unwrap();
+    assert_eq!(age, 30);
+}
+
+#[test]
+fn get_field_missing() {
+    let fields = create_fields(vec![("name", "test")]);
+
+    let result = TestUser::from_redis_hash(&fields);
+
+    let error = result.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Parse);
+    assert_eq!(error.to_string(), "Missing hash field: age");
+}
+
+#[test]
+fn get_field_invalid() {
+    let fields = create_fields(vec![("name", "test"), ("age", "old")]);
+
+    let result = TestUser::from_redis_hash(&fields);
+
+    let error = result.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Parse);
+    assert_eq!(error.to_string(), "Unable to parse hash field: age");
+}
+
+#[test]
+fn get_optional_field_missing() {
+    let fields = create_fields(vec![]);
+
+    let value: Option<u32> = get_optional_field(&fields, "age").unwrap();
+    assert!(value.is_none());
+}
+
+#[test]
+fn get_optional_field_invalid() {
+    let fields = create_fields(vec![("age", "old")]);
+
+    let result: RedisResult<Option<u32>> = get_optional_field(&fields, "age");
+    assert!(result.is_err());
+}
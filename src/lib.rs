@@ -251,20 +251,42 @@
 #[path = "./lib_test.rs"]
 mod lib_test;
 
+#[cfg(feature = "tls")]
+extern crate native_tls;
 extern crate redis;
+extern crate socket2;
 
 pub mod client;
+pub mod cluster;
 mod commands;
 mod connection;
+pub mod hash;
+#[macro_use]
+pub mod pipeline;
+pub mod pool;
+pub mod scan;
+pub mod script;
+pub mod sentinel;
+mod socket;
+pub mod stream;
 mod subscriber;
+#[cfg(feature = "tls")]
+pub mod tls;
+pub mod transaction;
 pub mod types;
 
+/// Client Builder
+pub type ClientBuilder = client::ClientBuilder;
+
 /// Error Type
 pub type RedisError = types::RedisError;
 
 /// Error Info
 pub type ErrorInfo = types::ErrorInfo;
 
+/// Error Kind
+pub type ErrorKind = types::ErrorKind;
+
 /// PubSub message
 pub type Message = types::Message;
 
@@ -272,7 +294,8 @@ pub type Message = types::Message;
 pub type RedisResult<T> = types::RedisResult<T>;
 
 /// Constructs a new redis client.<br>
-/// The redis connection string must be in the following format: `redis://[:<passwd>@]<hostname>[:port][/<db>]`
+/// The redis connection string must be in the following format: `redis://[:<passwd>@]<hostname>[:port][/<db>]`<br>
+/// Unix domain sockets are supported (on unix platforms) using the following format: `unix:///<path>[?db=<db>]`
 ///
 /// # Arguments
 ///
@@ -0,0 +1,455 @@
+//! # pipeline
+//!
+//! Enables to send multiple commands to redis in a single round trip.
+//!
+
+#[cfg(test)]
+#[path = "./pipeline_test.rs"]
+mod pipeline_test;
+
+use client::Client;
+use redis;
+use types::{ErrorInfo, RedisArg, RedisBinaryArg, RedisError, RedisResult};
+
+/// Implements the command queuing functions for the provided type, which must provide a `binary_cmd` function
+/// which queues the requested command (for example pipelines and transactions).
+macro_rules! queue_commands {
+    ($name:ident) => {
+        impl<'a> $name<'a> {
+            /// See redis [ECHO](https://redis.io/commands/echo) command.
+            pub fn echo(&mut self, value: &str) -> &mut $name<'a> {
+                self.binary_cmd("ECHO", vec![value])
+            }
+
+            /// See redis [PUBLISH](https://redis.io/commands/publish) command.
+            pub fn publish(&mut self, channel: &str, message: &str) -> &mut $name<'a> {
+                self.binary_cmd("PUBLISH", vec![channel, message])
+            }
+
+            /// See redis [GET](https://redis.io/commands/get) command.
+            pub fn get(&mut self, key: &str) -> &mut $name<'a> {
+                self.binary_cmd("GET", vec![key])
+            }
+
+            /// See redis [SET](https://redis.io/commands/set) command.
+            pub fn set<T: RedisBinaryArg>(&mut self, key: &str, value: T) -> &mut $name<'a> {
+                self.binary_cmd("SET", vec![key.as_bytes(), &value.to_bytes()])
+            }
+
+            /// See redis [SETEX](https://redis.io/commands/setex) command.
+            pub fn setex<T: RedisBinaryArg>(
+                &mut self,
+                key: &str,
+                value: T,
+                seconds: usize,
+            ) -> &mut $name<'a> {
+                self.binary_cmd(
+                    "SETEX",
+                    vec![
+                        key.as_bytes(),
+                        seconds.to_string().as_bytes(),
+                        &value.to_bytes(),
+                    ],
+                )
+            }
+
+            /// See redis [SETNX](https://redis.io/commands/setnx) command.
+            pub fn setnx<T: RedisBinaryArg>(&mut self, key: &str, value: T) -> &mut $name<'a> {
+                self.binary_cmd("SETNX", vec![key.as_bytes(), &value.to_bytes()])
+            }
+
+            /// See redis [GETSET](https://redis.io/commands/getset) command.
+            pub fn getset<T: RedisBinaryArg>(&mut self, key: &str, value: T) -> &mut $name<'a> {
+                self.binary_cmd("GETSET", vec![key.as_bytes(), &value.to_bytes()])
+            }
+
+            /// See redis [DEL](https://redis.io/commands/del) command.
+            pub fn del(&mut self, key: &str) -> &mut $name<'a> {
+                self.binary_cmd("DEL", vec![key])
+            }
+
+            /// See redis [EXISTS](https://redis.io/commands/exists) command.
+            pub fn exists(&mut self, key: &str) -> &mut $name<'a> {
+                self.binary_cmd("EXISTS", vec![key])
+            }
+
+            /// See redis [EXPIRE](https://redis.io/commands/expire) command.
+            pub fn expire(&mut self, key: &str, seconds: usize) -> &mut $name<'a> {
+                self.binary_cmd("EXPIRE", vec![key, &*seconds.to_string()])
+            }
+
+            /// See redis [PEXPIRE](https://redis.io/commands/pexpire) command.
+            pub fn pexpire(&mut self, key: &str, millies: usize) -> &mut $name<'a> {
+                self.binary_cmd("PEXPIRE", vec![key, &*millies.to_string()])
+            }
+
+            /// See redis [PERSIST](https://redis.io/commands/persist) command.
+            pub fn persist(&mut self, key: &str) -> &mut $name<'a> {
+                self.binary_cmd("PERSIST", vec![key])
+            }
+
+            /// See redis [RENAME](https://redis.io/commands/rename) command.
+            pub fn rename(&mut self, key: &str, new_key: &str) -> &mut $name<'a> {
+                self.binary_cmd("RENAME", vec![key, new_key])
+            }
+
+            /// See redis [RENAMENX](https://redis.io/commands/renamenx) command.
+            pub fn renamenx(&mut self, key: &str, new_key: &str) -> &mut $name<'a> {
+                self.binary_cmd("RENAMENX", vec![key, new_key])
+            }
+
+            /// See redis [APPEND](https://redis.io/commands/append) command.
+            pub fn append(&mut self, key: &str, value: &str) -> &mut $name<'a> {
+                self.binary_cmd("APPEND", vec![key, value])
+            }
+
+            /// See redis [INCR](https://redis.io/commands/incr) command.
+            pub fn incr(&mut self, key: &str) -> &mut $name<'a> {
+                self.binary_cmd("INCR", vec![key])
+            }
+
+            /// See redis [INCRBY](https://redis.io/commands/incrby) command.
+            pub fn incrby<T: RedisArg>(&mut self, key: &str, value: T) -> &mut $name<'a> {
+                self.binary_cmd("INCRBY", vec![key.as_bytes(), &value.to_bytes()])
+            }
+
+            /// See redis [INCRBYFLOAT](https://redis.io/commands/incrbyfloat) command.
+            pub fn incrbyfloat<T: RedisArg>(&mut self, key: &str, value: T) -> &mut $name<'a> {
+                self.binary_cmd("INCRBYFLOAT", vec![key.as_bytes(), &value.to_bytes()])
+            }
+
+            /// See redis [STRLEN](https://redis.io/commands/strlen) command.
+            pub fn strlen(&mut self, key: &str) -> &mut $name<'a> {
+                self.binary_cmd("STRLEN", vec![key])
+            }
+
+            /// See redis [KEYS](https://redis.io/commands/keys) command.
+            pub fn keys(&mut self, pattern: &str) -> &mut $name<'a> {
+                self.binary_cmd("KEYS", vec![pattern])
+            }
+
+            /// See redis [HGET](https://redis.io/commands/hget) command.
+            pub fn hget(&mut self, key: &str, field: &str) -> &mut $name<'a> {
+                self.binary_cmd("HGET", vec![key, field])
+            }
+
+            /// See redis [HGETALL](https://redis.io/commands/hgetall) command.
+            pub fn hgetall(&mut self, key: &str) -> &mut $name<'a> {
+                self.binary_cmd("HGETALL", vec![key])
+            }
+
+            /// See redis [HSET](https://redis.io/commands/hset) command.
+            pub fn hset<T: RedisBinaryArg>(
+                &mut self,
+                key: &str,
+                field: &str,
+                value: T,
+            ) -> &mut $name<'a> {
+                self.binary_cmd(
+                    "HSET",
+                    vec![key.as_bytes(), field.as_bytes(), &value.to_bytes()],
+                )
+            }
+
+            /// See redis [HSETNX](https://redis.io/commands/hsetnx) command.
+            pub fn hsetnx<T: RedisBinaryArg>(
+                &mut self,
+                key: &str,
+                field: &str,
+                value: T,
+            ) -> &mut $name<'a> {
+                self.binary_cmd(
+                    "HSETNX",
+                    vec![key.as_bytes(), field.as_bytes(), &value.to_bytes()],
+                )
+            }
+
+            /// See redis [HDEL](https://redis.io/commands/hdel) command.
+            pub fn hdel(&mut self, key: &str, field: &str) -> &mut $name<'a> {
+                self.binary_cmd("HDEL", vec![key, field])
+            }
+
+            /// See redis [HEXISTS](https://redis.io/commands/hexists) command.
+            pub fn hexists(&mut self, key: &str, field: &str) -> &mut $name<'a> {
+                self.binary_cmd("HEXISTS", vec![key, field])
+            }
+
+            /// See redis [HKEYS](https://redis.io/commands/hkeys) command.
+            pub fn hkeys(&mut self, key: &str) -> &mut $name<'a> {
+                self.binary_cmd("HKEYS", vec![key])
+            }
+
+            /// See redis [HVALS](https://redis.io/commands/hvals) command.
+            pub fn hvals(&mut self, key: &str) -> &mut $name<'a> {
+                self.binary_cmd("HVALS", vec![key])
+            }
+
+            /// See redis [LSET](https://redis.io/commands/lset) command.
+            pub fn lset<T: RedisBinaryArg>(
+                &mut self,
+                key: &str,
+                index: isize,
+                value: T,
+            ) -> &mut $name<'a> {
+                self.binary_cmd(
+                    "LSET",
+                    vec![
+                        key.as_bytes(),
+                        index.to_string().as_bytes(),
+                        &value.to_bytes(),
+                    ],
+                )
+            }
+
+            /// See redis [LINDEX](https://redis.io/commands/lindex) command.
+            pub fn lindex(&mut self, key: &str, index: isize) -> &mut $name<'a> {
+                self.binary_cmd("LINDEX", vec![key, &index.to_string()])
+            }
+
+            /// See redis [LLEN](https://redis.io/commands/llen) command.
+            pub fn llen(&mut self, key: &str) -> &mut $name<'a> {
+                self.binary_cmd("LLEN", vec![key])
+            }
+
+            /// See redis [LPOP](https://redis.io/commands/lpop) command.
+            pub fn lpop(&mut self, key: &str) -> &mut $name<'a> {
+                self.binary_cmd("LPOP", vec![key])
+            }
+
+            /// See redis [LPUSH](https://redis.io/commands/lpush) command.
+            pub fn lpush<T: RedisBinaryArg>(&mut self, key: &str, value: T) -> &mut $name<'a> {
+                self.binary_cmd("LPUSH", vec![key.as_bytes(), &value.to_bytes()])
+            }
+
+            /// See redis [LPUSHX](https://redis.io/commands/lpushx) command.
+            pub fn lpushx<T: RedisBinaryArg>(&mut self, key: &str, value: T) -> &mut $name<'a> {
+                self.binary_cmd("LPUSHX", vec![key.as_bytes(), &value.to_bytes()])
+            }
+
+            /// See redis [LRANGE](https://redis.io/commands/lrange) command.
+            pub fn lrange(&mut self, key: &str, start: isize, stop: isize) -> &mut $name<'a> {
+                self.binary_cmd("LRANGE", vec![key, &start.to_string(), &stop.to_string()])
+            }
+
+            /// See redis [LREM](https://redis.io/commands/lrem) command.
+            pub fn lrem<T: RedisBinaryArg>(
+                &mut self,
+                key: &str,
+                count: isize,
+                value: T,
+            ) -> &mut $name<'a> {
+                self.binary_cmd(
+                    "LREM",
+                    vec![
+                        key.as_bytes(),
+                        count.to_string().as_bytes(),
+                        &value.to_bytes(),
+                    ],
+                )
+            }
+
+            /// See redis [LTRIM](https://redis.io/commands/ltrim) command.
+            pub fn ltrim(&mut self, key: &str, start: isize, stop: isize) -> &mut $name<'a> {
+                self.binary_cmd("LTRIM", vec![key, &start.to_string(), &stop.to_string()])
+            }
+
+            /// See redis [RPOP](https://redis.io/commands/rpop) command.
+            pub fn rpop(&mut self, key: &str) -> &mut $name<'a> {
+                self.binary_cmd("RPOP", vec![key])
+            }
+
+            /// See redis [RPUSH](https://redis.io/commands/rpush) command.
+            pub fn rpush<T: RedisBinaryArg>(&mut self, key: &str, value: T) -> &mut $name<'a> {
+                self.binary_cmd("RPUSH", vec![key.as_bytes(), &value.to_bytes()])
+            }
+
+            /// See redis [RPUSHX](https://redis.io/commands/rpushx) command.
+            pub fn rpushx<T: RedisBinaryArg>(&mut self, key: &str, value: T) -> &mut $name<'a> {
+                self.binary_cmd("RPUSHX", vec![key.as_bytes(), &value.to_bytes()])
+            }
+
+            /// See redis [SADD](https://redis.io/commands/sadd) command.
+            pub fn sadd(&mut self, key: &str, member: &str) -> &mut $name<'a> {
+                self.binary_cmd("SADD", vec![key, member])
+            }
+
+            /// See redis [SCARD](https://redis.io/commands/scard) command.
+            pub fn scard(&mut self, key: &str) -> &mut $name<'a> {
+                self.binary_cmd("SCARD", vec![key])
+            }
+
+            /// See redis [SDIFF](https://redis.io/commands/sdiff) command.
+            pub fn sdiff(&mut self, keys: Vec<&str>) -> &mut $name<'a> {
+                self.binary_cmd("SDIFF", keys)
+            }
+
+            /// See redis [SISMEMBER](https://redis.io/commands/sismember) command.
+            pub fn sismember(&mut self, key: &str, member: &str) -> &mut $name<'a> {
+                self.binary_cmd("SISMEMBER", vec![key, member])
+            }
+
+            /// See redis [SMEMBERS](https://redis.io/commands/smembers) command.
+            pub fn smembers(&mut self, key: &str) -> &mut $name<'a> {
+                self.binary_cmd("SMEMBERS", vec![key])
+            }
+
+            /// See redis [SMOVE](https://redis.io/commands/smove) command.
+            pub fn smove(
+                &mut self,
+                source_key: &str,
+                destination_key: &str,
+                member: &str,
+            ) -> &mut $name<'a> {
+                self.binary_cmd("SMOVE", vec![source_key, destination_key, member])
+            }
+
+            /// See redis [SREM](https://redis.io/commands/srem) command.
+            pub fn srem(&mut self, key: &str, member: &str) -> &mut $name<'a> {
+                self.binary_cmd("SREM", vec![key, member])
+            }
+        }
+    };
+}
+
+/// Queues redis commands and sends them all in a single write, reading all responses afterwards.
+pub struct Pipeline<'a> {
+    pub(crate) client: &'a mut Client,
+    commands: Vec<redis::Cmd>,
+}
+
+fn create_command(command: &str, args: Vec<impl AsRef<[u8]>>) -> redis::Cmd {
+    let mut cmd = redis::cmd(command);
+
+    for arg in args {
+        cmd.arg(arg.as_ref());
+    }
+
+    cmd
+}
+
+impl<'a> Pipeline<'a> {
+    /// Queues the requested command with the provided arguments.<br>
+    /// This is the pipeline equivalent of the client run_command function and can be used to queue operations
+    /// that are not directly exposed by the pipeline.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The Redis command, for example: `GET`
+    /// * `args` - Vector of arguments for the given command
+    pub fn cmd(&mut self, command: &str, args: Vec<&str>) -> &mut Pipeline<'a> {
+        self.binary_cmd(command, args)
+    }
+
+    /// Queues the requested command with the provided binary safe arguments.<br>
+    /// This is the pipeline equivalent of the client run_binary_command function.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The Redis command, for example: `SET`
+    /// * `args` - Vector of arguments (strings or bytes) for the given command
+    pub fn binary_cmd(&mut self, command: &str, args: Vec<impl AsRef<[u8]>>) -> &mut Pipeline<'a> {
+        self.commands.push(create_command(command, args));
+        self
+    }
+
+    /// Returns the amount of queued commands.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns true if no commands were queued.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Sends all queued commands and returns the typed responses.<br>
+    /// The responses are returned as a single redis bulk value, therefore the output type would usually be a tuple
+    /// (for commands of different response types) or a vector.<br>
+    /// In case any of the commands failed, the first error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// let (_, value): ((), i64) = client
+    ///     .pipeline()
+    ///     .set("pipeline_key", 10)
+    ///     .incr("pipeline_key")
+    ///     .execute()
+    ///     .unwrap();
+    /// assert_eq!(value, 11);
+    /// ```
+    pub fn execute<T: redis::FromRedisValue>(&mut self) -> RedisResult<T> {
+        let mut values = vec![];
+
+        for result in self.execute_all() {
+            match result {
+                Ok(value) => values.push(value),
+                Err(error) => return Err(error),
+            }
+        }
+
+        match redis::from_redis_value(&redis::Value::Bulk(values)) {
+            Ok(output) => Ok(output),
+            Err(error) => Err(RedisError {
+                info: ErrorInfo::RedisError(error),
+            }),
+        }
+    }
+
+    /// Sends all queued commands and returns the response (or error) of each command, in the order the commands
+    /// were queued.<br>
+    /// Once executed, the pipeline is cleared and can be reused to queue new commands.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// let results = client
+    ///     .pipeline()
+    ///     .set("pipeline_key", "text")
+    ///     .incr("pipeline_key")
+    ///     .execute_all();
+    /// assert!(results[0].is_ok());
+    /// assert!(results[1].is_err());
+    /// ```
+    pub fn execute_all(&mut self) -> Vec<RedisResult<redis::Value>> {
+        let (packed, count) = self.pack(false);
+
+        if count == 0 {
+            vec![]
+        } else {
+            self.client.run_packed_commands(&packed, count, true)
+        }
+    }
+
+    /// Packs and clears all queued commands, returning the packed commands and the amount of expected responses.<br>
+    /// If atomic, the commands are wrapped with MULTI and EXEC commands.
+    pub(crate) fn pack(&mut self, atomic: bool) -> (Vec<u8>, usize) {
+        let mut count = self.commands.len();
+
+        let mut packed = vec![];
+        if atomic {
+            packed.extend(redis::cmd("MULTI").get_packed_command());
+            count = count + 2;
+        }
+        for command in self.commands.drain(..) {
+            packed.extend(command.get_packed_command());
+        }
+        if atomic {
+            packed.extend(redis::cmd("EXEC").get_packed_command());
+        }
+
+        (packed, count)
+    }
+}
+
+queue_commands!(Pipeline);
+
+/// Creates and returns a new empty pipeline for the provided client
+pub(crate) fn create(client: &mut Client) -> Pipeline {
+    Pipeline {
+        client,
+        commands: vec![],
+    }
+}
@@ -0,0 +1,20 @@
+use super::*;
+use client;
+
+#[test]
+fn create_empty() {
+    let mut client = client::create("redis://127.0.0.1:6379/").unwrap();
+    let mut pipeline = create(&mut client);
+
+    assert!(pipeline.is_empty());
+    assert_eq!(pipeline.execute_all().len(), 0);
+}
+
+#[test]
+fn cmd_queue() {
+    let mut client = client::create("redis://127.0.0.1:6379/").unwrap();
+    let mut pipeline = create(&mut client);
+
+    pipeline.cmd("ECHO", vec!["test"]).set("key", 1).get("key");
+    assert_eq!(pipeline.len(), 3);
+}
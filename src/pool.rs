@@ -0,0 +1,344 @@
+//! # pool
+//!
+//! Implements a thread safe pool of redis clients.<br>
+//! Each pooled client owns its own connection, enabling multiple threads to invoke redis operations concurrently
+//! without any external locking.
+//!
+
+#[cfg(test)]
+#[path = "./pool_test.rs"]
+mod pool_test;
+
+use client::{Client, ClientBuilder};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use types::{ErrorInfo, RedisError, RedisResult};
+
+/// Holds the pool configuration.
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// The minimal amount of clients the pool keeps, even when idle
+    pub min_size: usize,
+    /// The maximal amount of clients the pool may create
+    pub max_size: usize,
+    /// The max time to wait for an available client (None for no timeout)
+    pub checkout_timeout: Option<Duration>,
+    /// Idle clients (beyond the min size) are evicted after being idle for this duration (None to never evict)
+    pub idle_timeout: Option<Duration>,
+    /// If true, clients are verified to have an open connection before being handed out
+    pub test_on_checkout: bool,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            min_size: 0,
+            max_size: 10,
+            checkout_timeout: Some(Duration::from_secs(30)),
+            idle_timeout: Some(Duration::from_secs(600)),
+            test_on_checkout: true,
+        }
+    }
+}
+
+struct IdleClient {
+    client: Client,
+    idle_since: Instant,
+}
+
+struct PoolState {
+    /// Clients which are currently not checked out
+    idle: Vec<IdleClient>,
+    /// The total amount of clients owned by the pool (idle and checked out)
+    size: usize,
+}
+
+struct PoolData {
+    /// Creates every pooled client
+    builder: ClientBuilder,
+    options: Options,
+    state: Mutex<PoolState>,
+    available: Condvar,
+}
+
+/// A thread safe pool of redis clients.<br>
+/// The pool can be cloned and shared between threads, all clones use the same underlying clients.
+#[derive(Clone)]
+pub struct Pool {
+    data: Arc<PoolData>,
+}
+
+/// A client checked out of the pool.<br>
+/// The client is returned to the pool once this value is dropped.
+pub struct PooledClient {
+    pool: Pool,
+    client: Option<Client>,
+}
+
+fn lock_state(data: &PoolData) -> MutexGuard<PoolState> {
+    match data.state.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+fn evict_idle(data: &PoolData, state: &mut PoolState) {
+    if let Some(idle_timeout) = data.options.idle_timeout {
+        let min_size = data.options.min_size;
+        let now = Instant::now();
+
+        // the oldest idle clients are at the start of the list
+        while state.size > min_size
+            && !state.idle.is_empty()
+            && now.duration_since(state.idle[0].idle_since) >= idle_timeout
+        {
+            state.idle.remove(0);
+            state.size = state.size - 1;
+        }
+    }
+}
+
+fn is_healthy(client: &Client) -> bool {
+    // clients which never connected will connect on first use
+    !client.is_connection_created() || client.is_connection_open()
+}
+
+impl Pool {
+    /// Checks out a client from the pool.<br>
+    /// If no client is available and the pool reached its max size, this function will block until a client
+    /// is returned to the pool or the checkout timeout is reached, in which case a TimeoutError is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let pool = simple_redis::pool::create("redis://127.0.0.1:6379/", simple_redis::pool::Options::default()).unwrap();
+    /// match pool.get() {
+    ///     Ok(mut client) => match client.echo("testing") {
+    ///         Ok(value) => assert_eq!(value, "testing"),
+    ///         _ => panic!("test error"),
+    ///     },
+    ///     Err(error) => println!("Unable to get client from pool: {}", error),
+    /// }
+    /// ```
+    pub fn get(self: &Pool) -> RedisResult<PooledClient> {
+        let data = &self.data;
+        let deadline = data
+            .options
+            .checkout_timeout
+            .map(|timeout| Instant::now() + timeout);
+
+        let mut state = lock_state(data);
+        loop {
+            evict_idle(data, &mut state);
+
+            if let Some(entry) = state.idle.pop() {
+                if !data.options.test_on_checkout {
+                    return Ok(PooledClient {
+                        pool: self.clone(),
+                        client: Some(entry.client),
+                    });
+                }
+
+                // the health check may block on the server, so it runs without holding the pool lock
+                drop(state);
+                if is_healthy(&entry.client) {
+                    return Ok(PooledClient {
+                        pool: self.clone(),
+                        client: Some(entry.client),
+                    });
+                }
+                drop(entry);
+
+                // replace the broken client with a new one
+                state = lock_state(data);
+                state.size = state.size - 1;
+                continue;
+            }
+
+            if state.size < data.options.max_size {
+                return match data.builder.build() {
+                    Ok(client) => {
+                        state.size = state.size + 1;
+
+                        Ok(PooledClient {
+                            pool: self.clone(),
+                            client: Some(client),
+                        })
+                    }
+                    Err(error) => Err(error),
+                };
+            }
+
+            state = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Err(RedisError {
+                            info: ErrorInfo::TimeoutError(
+                                "Timeout while waiting for a pooled client.",
+                            ),
+                        });
+                    }
+
+                    match data.available.wait_timeout(state, deadline - now) {
+                        Ok((guard, _)) => guard,
+                        Err(poisoned) => poisoned.into_inner().0,
+                    }
+                }
+                None => match data.available.wait(state) {
+                    Ok(guard) => guard,
+                    Err(poisoned) => poisoned.into_inner(),
+                },
+            };
+        }
+    }
+
+    /// Returns the total amount of clients currently owned by the pool (idle and checked out).
+    pub fn size(self: &Pool) -> usize {
+        lock_state(&self.data).size
+    }
+
+    /// Returns the amount of idle clients currently held by the pool.
+    pub fn idle_size(self: &Pool) -> usize {
+        lock_state(&self.data).idle.len()
+    }
+
+    fn release(self: &Pool, mut client: Client) {
+        let data = &self.data;
+
+        // pooled clients must not keep subscriptions of previous users
+        let reusable = client.unsubscribe_all().is_ok();
+
+        let mut state = lock_state(data);
+        if reusable {
+            state.idle.push(IdleClient {
+                client,
+                idle_since: Instant::now(),
+            });
+        } else {
+            state.size = state.size - 1;
+        }
+        evict_idle(data, &mut state);
+
+        data.available.notify_one();
+    }
+}
+
+impl Deref for PooledClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        match self.client {
+            Some(ref client) => client,
+            None => unreachable!(),
+        }
+    }
+}
+
+impl DerefMut for PooledClient {
+    fn deref_mut(&mut self) -> &mut Client {
+        match self.client {
+            Some(ref mut client) => client,
+            None => unreachable!(),
+        }
+    }
+}
+
+impl Drop for PooledClient {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            self.pool.release(client);
+        }
+    }
+}
+
+/// Constructs a new redis client pool.<br>
+/// The redis connection string must be in the following format: `redis://[:<passwd>@]<hostname>[:port][/<db>]`<br>
+/// Unix domain sockets are supported (on unix platforms) using the following format: `unix:///<path>[?db=<db>]`
+///
+/// # Arguments
+///
+/// * `connection_string` - The connection string in the format of: `redis://[:<passwd>@]<hostname>[:port][/<db>]`
+/// * `options` - The pool options
+///
+/// # Example
+///
+/// ```
+/// extern crate simple_redis;
+/// use simple_redis::pool::Options;
+///
+/// fn main() {
+///     let mut options = Options::default();
+///     options.max_size = 4;
+///
+///     match simple_redis::pool::create("redis://127.0.0.1:6379/", options) {
+///         Ok(pool) => println!("Created Redis Pool"),
+///         Err(error) => println!("Unable to create Redis pool: {}", error)
+///     }
+/// }
+/// ```
+pub fn create(connection_string: &str, options: Options) -> Result<Pool, RedisError> {
+    create_with_builder(ClientBuilder::new(connection_string), options)
+}
+
+/// Constructs a new redis client pool in which every client is built by the provided client builder, applying
+/// its configuration (for example timeouts, retry policy, TLS and health checks) on all pooled clients.
+///
+/// # Arguments
+///
+/// * `builder` - The builder used to create every pooled client
+/// * `options` - The pool options
+///
+/// # Example
+///
+/// ```
+/// extern crate simple_redis;
+/// use simple_redis::client::ClientBuilder;
+/// use simple_redis::pool::Options;
+/// use std::time::Duration;
+///
+/// fn main() {
+///     let builder = ClientBuilder::new("redis://127.0.0.1:6379/")
+///         .connect_timeout(Duration::from_secs(1))
+///         .read_timeout(Duration::from_secs(5));
+///
+///     match simple_redis::pool::create_with_builder(builder, Options::default()) {
+///         Ok(pool) => println!("Created Redis Pool"),
+///         Err(error) => println!("Unable to create Redis pool: {}", error)
+///     }
+/// }
+/// ```
+pub fn create_with_builder(builder: ClientBuilder, options: Options) -> Result<Pool, RedisError> {
+    if options.max_size == 0 || options.min_size > options.max_size {
+        return Err(RedisError {
+            info: ErrorInfo::Description("Invalid pool size."),
+        });
+    }
+
+    // validate the client configuration even when no clients are created in advance
+    if let Err(error) = builder.build() {
+        return Err(error);
+    }
+
+    let mut idle = vec![];
+    for _ in 0..options.min_size {
+        match builder.build() {
+            Ok(client) => idle.push(IdleClient {
+                client,
+                idle_since: Instant::now(),
+            }),
+            Err(error) => return Err(error),
+        }
+    }
+
+    let size = idle.len();
+    Ok(Pool {
+        data: Arc::new(PoolData {
+            builder,
+            options,
+            state: Mutex::new(PoolState { idle, size }),
+            available: Condvar::new(),
+        }),
+    })
+}
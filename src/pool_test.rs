@@ -0,0 +1,116 @@
+use super::*;
+use redis;
+use std::io::Write;
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+
+#[test]
+fn create_invalid_url() {
+    let result = create("test/bad/url", Options::default());
+    assert!(result.is_err());
+}
+
+#[test]
+fn create_invalid_size() {
+    let mut options = Options::default();
+    options.max_size = 0;
+    assert!(create("redis://127.0.0.1:6379/", options).is_err());
+
+    options.max_size = 2;
+    options.min_size = 3;
+    assert!(create("redis://127.0.0.1:6379/", options).is_err());
+}
+
+#[test]
+fn create_with_builder_invalid_url() {
+    let result = create_with_builder(ClientBuilder::new("test/bad/url"), Options::default());
+    assert!(result.is_err());
+}
+
+#[test]
+fn create_min_size() {
+    let mut options = Options::default();
+    options.min_size = 2;
+    let pool = create("redis://127.0.0.1:6379/", options).unwrap();
+    assert_eq!(pool.size(), 2);
+    assert_eq!(pool.idle_size(), 2);
+}
+
+#[test]
+fn get_checkout_timeout() {
+    let mut options = Options::default();
+    options.max_size = 1;
+    options.checkout_timeout = Some(Duration::from_millis(10));
+    let pool = create("redis://127.0.0.1:6379/", options).unwrap();
+
+    let client = pool.get().unwrap();
+    assert!(!client.is_connection_open());
+    assert_eq!(pool.size(), 1);
+    assert_eq!(pool.idle_size(), 0);
+
+    match pool.get() {
+        Err(error) => match error.info {
+            ErrorInfo::TimeoutError(_) => (),
+            _ => panic!("Invalid Error Type: {}", error),
+        },
+        _ => panic!("test error"),
+    };
+
+    drop(client);
+    assert_eq!(pool.idle_size(), 1);
+
+    assert!(pool.get().is_ok());
+    assert_eq!(pool.size(), 1);
+}
+
+#[test]
+fn release_evict_idle() {
+    let mut options = Options::default();
+    options.idle_timeout = Some(Duration::new(0, 0));
+    let pool = create("redis://127.0.0.1:6379/", options).unwrap();
+
+    let client = pool.get().unwrap();
+    assert_eq!(pool.size(), 1);
+
+    drop(client);
+    assert_eq!(pool.size(), 0);
+    assert_eq!(pool.idle_size(), 0);
+}
+
+#[test]
+fn create_with_builder_applies_options() {
+    // reports every received command and responds to the client name and ECHO commands
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+
+        while let Ok(request) = redis::Parser::new(&mut stream).parse_value() {
+            let args: Vec<String> = redis::from_redis_value(&request).unwrap();
+            let response = match args[0].as_str() {
+                "ECHO" => format!("${}\r\n{}\r\n", args[1].len(), args[1]),
+                _ => "+OK\r\n".to_string(),
+            };
+            sender.send(args).unwrap();
+            if stream.write_all(response.as_bytes()).is_err() {
+                break;
+            }
+        }
+    });
+
+    let builder = ClientBuilder::new(&format!("redis://127.0.0.1:{}/", port))
+        .client_name("pooled_client")
+        .connect_timeout(Duration::from_secs(5));
+    let pool = create_with_builder(builder, Options::default()).unwrap();
+
+    let mut client = pool.get().unwrap();
+    assert_eq!(client.echo("testing").unwrap(), "testing");
+
+    assert_eq!(
+        receiver.recv().unwrap(),
+        vec!["CLIENT", "SETNAME", "pooled_client"]
+    );
+    assert_eq!(receiver.recv().unwrap(), vec!["ECHO", "testing"]);
+}
@@ -0,0 +1,125 @@
+//! # scan
+//!
+//! Implements iterators over the cursor based SCAN family commands.
+//!
+
+#[cfg(test)]
+#[path = "./scan_test.rs"]
+mod scan_test;
+
+use client::Client;
+use commands;
+use redis;
+use std::collections::VecDeque;
+use types;
+use types::{ErrorInfo, RedisError, RedisResult, ScanOptions};
+
+/// Iterates over the elements returned by the SCAN family commands, fetching the next batch from redis
+/// when needed.<br>
+/// In case the connection is reset in the middle of the iteration, an error is returned and the iteration stops,
+/// as the cursor may no longer be valid for the new connection.
+pub struct ScanIterator<'a, T> {
+    client: &'a mut Client,
+    command: &'static str,
+    key: Option<String>,
+    options: ScanOptions,
+    cursor: u64,
+    generation: Option<u64>,
+    done: bool,
+    items: VecDeque<T>,
+    convert: fn(Vec<String>) -> RedisResult<Vec<T>>,
+}
+
+impl<'a, T> ScanIterator<'a, T> {
+    fn fetch(&mut self) -> RedisResult<()> {
+        let mut args = vec![];
+        if let Some(ref key) = self.key {
+            args.push(key.to_string());
+        }
+        args.push(self.cursor.to_string());
+        if let Some(ref pattern) = self.options.pattern {
+            args.push("MATCH".to_string());
+            args.push(pattern.to_string());
+        }
+        if let Some(count) = self.options.count {
+            args.push("COUNT".to_string());
+            args.push(count.to_string());
+        }
+        if self.key.is_none() {
+            if let Some(ref key_type) = self.options.key_type {
+                args.push("TYPE".to_string());
+                args.push(key_type.to_string());
+            }
+        }
+
+        let result = self
+            .client
+            .run_binary_command::<(u64, Vec<redis::Value>)>(self.command, args);
+
+        let generation = self.client.get_connection_generation();
+        match self.generation {
+            Some(previous_generation) if previous_generation != generation => {
+                return Err(RedisError {
+                    info: ErrorInfo::Description(types::SCAN_CONNECTION_RESET),
+                });
+            }
+            _ => self.generation = Some(generation),
+        }
+
+        match result {
+            Ok((cursor, values)) => match commands::from_values(&values).and_then(self.convert) {
+                Ok(items) => {
+                    self.cursor = cursor;
+                    self.done = cursor == 0;
+                    self.items.extend(items);
+
+                    Ok(())
+                }
+                Err(error) => Err(error),
+            },
+            Err(error) => Err(error),
+        }
+    }
+}
+
+impl<'a, T> Iterator for ScanIterator<'a, T> {
+    type Item = RedisResult<T>;
+
+    fn next(&mut self) -> Option<RedisResult<T>> {
+        loop {
+            if let Some(item) = self.items.pop_front() {
+                return Some(Ok(item));
+            }
+
+            if self.done {
+                return None;
+            }
+
+            if let Err(error) = self.fetch() {
+                self.done = true;
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
+/// Creates and returns a new scan iterator
+pub(crate) fn create<'a, T>(
+    client: &'a mut Client,
+    command: &'static str,
+    key: Option<&str>,
+    options: ScanOptions,
+    convert: fn(Vec<String>) -> RedisResult<Vec<T>>,
+) -> ScanIterator<'a, T> {
+    ScanIterator {
+        client,
+        command,
+        key: key.map(|value| value.to_string()),
+        options,
+        cursor: 0,
+        generation: None,
+        done: false,
+        items: VecDeque::new(),
+        convert,
+    }
+}
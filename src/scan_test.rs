@@ -0,0 +1,39 @@
+use super::*;
+use client;
+
+fn to_members(values: Vec<String>) -> RedisResult<Vec<String>> {
+    Ok(values)
+}
+
+#[test]
+fn create_check_state() {
+    let mut client = client::create("redis://127.0.0.1:6379/").unwrap();
+    let iterator = create(
+        &mut client,
+        "SSCAN",
+        Some("key"),
+        ScanOptions::default(),
+        to_members,
+    );
+
+    assert_eq!(iterator.cursor, 0);
+    assert!(iterator.generation.is_none());
+    assert!(!iterator.done);
+    assert!(iterator.items.is_empty());
+    assert_eq!(iterator.key, Some("key".to_string()));
+}
+
+#[test]
+fn next_connection_error() {
+    let mut client = client::create("redis://127.0.0.1:1/").unwrap();
+    let mut iterator = create(
+        &mut client,
+        "SCAN",
+        None,
+        ScanOptions::default(),
+        to_members,
+    );
+
+    assert!(iterator.next().unwrap().is_err());
+    assert!(iterator.next().is_none());
+}
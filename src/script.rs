@@ -0,0 +1,82 @@
+//! # script
+//!
+//! Enables invoking lua scripts, sending the entire script to redis only in case it was not already loaded.
+//!
+
+#[cfg(test)]
+#[path = "./script_test.rs"]
+mod script_test;
+
+use client::Client;
+use redis;
+use types::{ErrorKind, RedisBinaryArg, RedisResult};
+
+/// A lua script which is invoked using its SHA1 digest (see redis [EVALSHA](https://redis.io/commands/evalsha)
+/// command), falling back to sending the entire script (see redis [EVAL](https://redis.io/commands/eval) command)
+/// in case the script is not loaded in redis yet.
+#[derive(Debug, Clone)]
+pub struct Script {
+    code: String,
+    hash: String,
+}
+
+impl Script {
+    /// Creates a new script and computes its SHA1 digest.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The lua script code
+    pub fn new(code: &str) -> Script {
+        Script {
+            code: code.to_string(),
+            hash: redis::Script::new(code).get_hash().to_string(),
+        }
+    }
+
+    /// Returns the script code.
+    pub fn get_code(self: &Script) -> &str {
+        &self.code
+    }
+
+    /// Returns the script SHA1 digest (in hexadecimal format).
+    pub fn get_hash(self: &Script) -> &str {
+        &self.hash
+    }
+
+    /// Invokes the script with the provided keys and arguments and returns the typed script output.<br>
+    /// The script is first invoked using its SHA1 digest and only in case redis returns a NOSCRIPT error, the entire
+    /// script is sent (which also loads the script for the next invocations).
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client used to invoke the script
+    /// * `keys` - The keys accessed by the script (available as KEYS in the script)
+    /// * `args` - The script arguments (available as ARGV in the script)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// use simple_redis::script::Script;
+    ///
+    /// let script = Script::new("return redis.call('INCRBY', KEYS[1], ARGV[1])");
+    ///
+    /// match script.invoke::<i64, _, _>(&mut client, vec!["my_counter"], vec![5]) {
+    ///     Ok(value) => println!("Counter value: {}", value),
+    ///     Err(error) => println!("Unable to invoke script: {}", error),
+    /// }
+    /// ```
+    pub fn invoke<T: redis::FromRedisValue, K: RedisBinaryArg, A: RedisBinaryArg>(
+        self: &Script,
+        client: &mut Client,
+        keys: Vec<K>,
+        args: Vec<A>,
+    ) -> RedisResult<T> {
+        match client.evalsha(&self.hash, &keys, &args) {
+            Err(ref error) if error.kind() == ErrorKind::NoScript => {
+                client.eval(&self.code, &keys, &args)
+            }
+            output => output,
+        }
+    }
+}
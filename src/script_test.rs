@@ -0,0 +1,20 @@
+use super::*;
+
+#[test]
+fn new_hash() {
+    let script = Script::new("return 1");
+
+    assert_eq!(script.get_code(), "return 1");
+    assert_eq!(
+        script.get_hash(),
+        "e0e1f9fabfc9d4800c877a703b823ac0578ff8db"
+    );
+}
+
+#[test]
+fn new_different_code() {
+    let first = Script::new("return 1");
+    let second = Script::new("return 2");
+
+    assert_ne!(first.get_hash(), second.get_hash());
+}
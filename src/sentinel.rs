@@ -0,0 +1,320 @@
+//! # sentinel
+//!
+//! Enables connecting to redis servers managed by [redis sentinel](https://redis.io/topics/sentinel).<br>
+//! The current master address is resolved via the sentinels on every new connection, so in case of a failover
+//! the client reconnects to the new master.
+//!
+
+#[cfg(test)]
+#[path = "./sentinel_test.rs"]
+mod sentinel_test;
+
+use client;
+use client::Client;
+use connection::ConnectionOptions;
+use redis;
+use socket;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::time::Duration;
+use types::{ErrorInfo, RedisError};
+
+/// The max time to wait for a sentinel response (and for the sentinel connection in case no connect timeout is set)
+const SENTINEL_TIMEOUT_MILLIS: u64 = 1000;
+/// The scheme of TLS connection strings
+const TLS_SCHEME: &'static str = "rediss://";
+
+/// Holds the sentinel client configuration.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// The database to use
+    pub database: i64,
+    /// The redis servers password (if needed)
+    pub password: Option<String>,
+    /// If true, read only commands are invoked on a replica (chosen randomly) instead of the master
+    pub read_from_replicas: bool,
+    /// The max time to wait for a new connection (to the sentinels or the redis servers) to be established
+    pub connect_timeout: Option<Duration>,
+    /// The max time to wait for a redis server command response
+    pub read_timeout: Option<Duration>,
+    /// The max time to wait for a redis server command to be sent
+    pub write_timeout: Option<Duration>,
+}
+
+/// The server role to connect to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Role {
+    Master,
+    Replica,
+}
+
+/// Holds the information needed to resolve the redis server address.
+#[derive(Debug, Clone)]
+pub(crate) struct SentinelConfig {
+    /// The sentinels connection info
+    pub(crate) sentinels: Vec<redis::ConnectionInfo>,
+    /// The name of the monitored master
+    pub(crate) master_name: String,
+    /// The server role to connect to
+    pub(crate) role: Role,
+    /// The database to use
+    pub(crate) database: i64,
+    /// The redis servers password
+    pub(crate) password: Option<String>,
+}
+
+fn create_error(description: &str) -> redis::RedisError {
+    // resolution errors are connection errors, enabling the retry policy to retry them
+    io::Error::new(io::ErrorKind::Other, description.to_string()).into()
+}
+
+fn to_connection_info(
+    config: &SentinelConfig,
+    host: String,
+    port: &str,
+) -> redis::RedisResult<redis::ConnectionInfo> {
+    match port.parse::<u16>() {
+        Ok(port) => Ok(redis::ConnectionInfo {
+            addr: Box::new(redis::ConnectionAddr::Tcp(host, port)),
+            db: config.database,
+            passwd: config.password.clone(),
+        }),
+        Err(_) => Err(create_error("Invalid address returned by sentinel.")),
+    }
+}
+
+/// Returns the address of a random healthy replica out of the SENTINEL REPLICAS response.
+fn select_replica(replicas: Vec<HashMap<String, String>>) -> Option<(String, String)> {
+    let healthy: Vec<(String, String)> = replicas
+        .into_iter()
+        .filter(|replica| match replica.get("flags") {
+            Some(flags) => !flags
+                .split(',')
+                .any(|flag| flag == "s_down" || flag == "o_down" || flag == "disconnected"),
+            None => false,
+        })
+        .filter_map(
+            |mut replica| match (replica.remove("ip"), replica.remove("port")) {
+                (Some(ip), Some(port)) => Some((ip, port)),
+                _ => None,
+            },
+        )
+        .collect();
+
+    if healthy.is_empty() {
+        None
+    } else {
+        let random = RandomState::new().build_hasher().finish() as usize;
+
+        Some(healthy[random % healthy.len()].clone())
+    }
+}
+
+/// Returns the timeout used to query the sentinels and verify the resolved server role.
+fn get_sentinel_timeout() -> Duration {
+    Duration::from_millis(SENTINEL_TIMEOUT_MILLIS)
+}
+
+fn query_sentinel(
+    config: &SentinelConfig,
+    sentinel: &redis::ConnectionInfo,
+    options: &ConnectionOptions,
+) -> redis::RedisResult<redis::ConnectionInfo> {
+    let timeout = Some(get_sentinel_timeout());
+    let mut sentinel_options = ConnectionOptions::default();
+    sentinel_options.connect_timeout = options.connect_timeout.or(timeout);
+    sentinel_options.read_timeout = timeout;
+    sentinel_options.write_timeout = timeout;
+
+    let connection = socket::connect(sentinel, &sentinel_options)?;
+
+    if config.role == Role::Replica {
+        // older sentinels only support the SLAVES sub command
+        let replicas: Vec<HashMap<String, String>> = match redis::cmd("SENTINEL")
+            .arg("REPLICAS")
+            .arg(config.master_name.as_str())
+            .query(&connection)
+        {
+            Ok(replicas) => replicas,
+            Err(_) => redis::cmd("SENTINEL")
+                .arg("SLAVES")
+                .arg(config.master_name.as_str())
+                .query(&connection)?,
+        };
+
+        // fallback to the master in case no replica is available
+        if let Some((host, port)) = select_replica(replicas) {
+            return to_connection_info(config, host, &port);
+        }
+    }
+
+    let address: Option<(String, String)> = redis::cmd("SENTINEL")
+        .arg("GET-MASTER-ADDR-BY-NAME")
+        .arg(config.master_name.as_str())
+        .query(&connection)?;
+
+    match address {
+        Some((host, port)) => to_connection_info(config, host, &port),
+        None => Err(create_error("Master not monitored by sentinel.")),
+    }
+}
+
+/// Resolves the redis server connection info by querying the sentinels one after the other.<br>
+/// Every sentinel query is bounded by the connect timeout and the sentinel response timeout, so an unreachable
+/// sentinel does not block the resolution.
+pub(crate) fn resolve(
+    config: &SentinelConfig,
+    options: &ConnectionOptions,
+) -> redis::RedisResult<redis::ConnectionInfo> {
+    let mut last_error = create_error("No sentinels defined.");
+
+    for sentinel in &config.sentinels {
+        match query_sentinel(config, sentinel, options) {
+            Ok(connection_info) => return Ok(connection_info),
+            Err(error) => last_error = error,
+        }
+    }
+
+    Err(last_error)
+}
+
+/// Verifies the connection is to a master, as the sentinels might not be updated yet during a failover.<br>
+/// The check is bounded by the sentinel response timeout, after which the connection read timeout is restored.
+fn verify_master(
+    connection: &socket::Connection,
+    options: &ConnectionOptions,
+) -> redis::RedisResult<()> {
+    connection.set_read_timeout(Some(get_sentinel_timeout()))?;
+    let role: Vec<redis::Value> = redis::cmd("ROLE").query(connection)?;
+    connection.set_read_timeout(options.read_timeout)?;
+
+    match role.first() {
+        Some(&redis::Value::Data(ref role)) if role.as_slice() == b"master" => Ok(()),
+        _ => Err(create_error("Resolved redis server is not a master.")),
+    }
+}
+
+/// Creates a new connection to the redis server resolved via the sentinels and applies the provided options on
+/// it.<br>
+/// Master connections are verified to be connected to a master, as the sentinels might not be updated yet
+/// during a failover.
+pub(crate) fn connect(
+    config: &SentinelConfig,
+    options: &ConnectionOptions,
+) -> redis::RedisResult<socket::Connection> {
+    let connection_info = resolve(config, options)?;
+    let connection = socket::connect(&connection_info, options)?;
+
+    if config.role == Role::Master {
+        verify_master(&connection, options)?;
+    }
+
+    Ok(connection)
+}
+
+/// Creates a new pubsub connection to the redis master resolved via the sentinels and applies the provided options
+/// on it.
+pub(crate) fn connect_pubsub(
+    config: &SentinelConfig,
+    options: &ConnectionOptions,
+) -> redis::RedisResult<socket::PubSub> {
+    let mut master_config = config.clone();
+    master_config.role = Role::Master;
+
+    let connection_info = resolve(&master_config, options)?;
+
+    socket::connect_pubsub(&connection_info, options)
+}
+
+/// Constructs a new redis client which connects to the current master, as resolved by the provided sentinels.<br>
+/// The sentinel connection strings must be in the following format: `redis://<hostname>[:port]`<br>
+/// TLS is not supported for sentinel clients, so `rediss://` sentinel connection strings are rejected.
+///
+/// # Arguments
+///
+/// * `sentinels` - The sentinels connection strings in the format of: `redis://<hostname>[:port]`
+/// * `master_name` - The name of the master monitored by the sentinels
+/// * `options` - The sentinel client options
+///
+/// # Example
+///
+/// ```
+/// extern crate simple_redis;
+/// use simple_redis::sentinel::Options;
+/// use std::time::Duration;
+///
+/// fn main() {
+///     let mut options = Options::default();
+///     options.read_from_replicas = true;
+///     options.connect_timeout = Some(Duration::from_secs(1));
+///
+///     match simple_redis::sentinel::create(
+///         vec!["redis://127.0.0.1:26379", "redis://127.0.0.1:26380"],
+///         "mymaster",
+///         options,
+///     ) {
+///         Ok(client) => println!("Created Redis Client"),
+///         Err(error) => println!("Unable to create Redis client: {}", error)
+///     }
+/// }
+/// ```
+pub fn create(
+    sentinels: Vec<&str>,
+    master_name: &str,
+    options: Options,
+) -> Result<Client, RedisError> {
+    if sentinels.is_empty() {
+        return Err(RedisError {
+            info: ErrorInfo::Description("No sentinels defined."),
+        });
+    }
+
+    if sentinels
+        .iter()
+        .any(|sentinel| sentinel.starts_with(TLS_SCHEME))
+    {
+        return Err(RedisError {
+            info: ErrorInfo::RedisError(redis::RedisError::from((
+                redis::ErrorKind::InvalidClientConfig,
+                "TLS is not supported for sentinel connections.",
+            ))),
+        });
+    }
+
+    let mut sentinels_connection_info = vec![];
+    for sentinel in &sentinels {
+        match redis::IntoConnectionInfo::into_connection_info(*sentinel) {
+            Ok(connection_info) => sentinels_connection_info.push(connection_info),
+            Err(error) => {
+                return Err(RedisError {
+                    info: ErrorInfo::RedisError(error),
+                })
+            }
+        }
+    }
+
+    let config = SentinelConfig {
+        sentinels: sentinels_connection_info,
+        master_name: master_name.to_string(),
+        role: Role::Master,
+        database: options.database,
+        password: options.password,
+    };
+
+    // the connection string is only validated as all connections are resolved via the sentinels
+    let mut builder =
+        client::ClientBuilder::new(sentinels[0]).sentinel(config, options.read_from_replicas);
+    if let Some(connect_timeout) = options.connect_timeout {
+        builder = builder.connect_timeout(connect_timeout);
+    }
+    if let Some(read_timeout) = options.read_timeout {
+        builder = builder.read_timeout(read_timeout);
+    }
+    if let Some(write_timeout) = options.write_timeout {
+        builder = builder.write_timeout(write_timeout);
+    }
+
+    builder.build()
+}
@@ -0,0 +1,133 @@
+use super::*;
+
+fn create_replica(ip: &str, port: &str, flags: &str) -> HashMap<String, String> {
+    let mut replica = HashMap::new();
+    replica.insert("ip".to_string(), ip.to_string());
+    replica.insert("port".to_string(), port.to_string());
+    replica.insert("flags".to_string(), flags.to_string());
+
+    replica
+}
+
+#[test]
+fn create_no_sentinels() {
+    let result = create(vec![], "mymaster", Options::default());
+    assert!(result.is_err());
+}
+
+#[test]
+fn create_invalid_sentinel() {
+    let result = create(vec!["test/bad/url"], "mymaster", Options::default());
+    assert!(result.is_err());
+}
+
+#[test]
+fn create_valid() {
+    let mut options = Options::default();
+    options.read_from_replicas = true;
+
+    let client = create(
+        vec!["redis://127.0.0.1:26379", "redis://127.0.0.1:26380"],
+        "mymaster",
+        options,
+    )
+    .unwrap();
+    assert!(!client.is_connection_open());
+}
+
+#[test]
+fn select_replica_empty() {
+    assert!(select_replica(vec![]).is_none());
+}
+
+#[test]
+fn select_replica_down() {
+    let replicas = vec![
+        create_replica("10.0.0.1", "6379", "slave,s_down"),
+        create_replica("10.0.0.2", "6379", "slave,disconnected"),
+        create_replica("10.0.0.3", "6379", "slave,o_down"),
+    ];
+
+    assert!(select_replica(replicas).is_none());
+}
+
+#[test]
+fn select_replica_healthy() {
+    let replicas = vec![
+        create_replica("10.0.0.1", "6379", "slave,s_down"),
+        create_replica("10.0.0.2", "6380", "slave"),
+    ];
+
+    let address = select_replica(replicas).unwrap();
+    assert_eq!(address, ("10.0.0.2".to_string(), "6380".to_string()));
+}
+
+#[test]
+fn to_connection_info_valid() {
+    let config = SentinelConfig {
+        sentinels: vec![],
+        master_name: "mymaster".to_string(),
+        role: Role::Master,
+        database: 3,
+        password: Some("secret".to_string()),
+    };
+
+    let connection_info = to_connection_info(&config, "10.0.0.1".to_string(), "6380").unwrap();
+    assert_eq!(
+        *connection_info.addr,
+        redis::ConnectionAddr::Tcp("10.0.0.1".to_string(), 6380)
+    );
+    assert_eq!(connection_info.db, 3);
+    assert_eq!(connection_info.passwd, Some("secret".to_string()));
+
+    assert!(to_connection_info(&config, "10.0.0.1".to_string(), "bad").is_err());
+}
+
+#[test]
+fn resolve_unreachable_sentinels() {
+    let config = SentinelConfig {
+        sentinels: vec![
+            redis::IntoConnectionInfo::into_connection_info("redis://127.0.0.1:1").unwrap(),
+        ],
+        master_name: "mymaster".to_string(),
+        role: Role::Master,
+        database: 0,
+        password: None,
+    };
+
+    let error = resolve(&config, &ConnectionOptions::default()).unwrap_err();
+    assert!(error.is_io_error());
+}
+
+#[test]
+fn resolve_unresponsive_sentinel() {
+    // the listener accepts the connection but never responds
+    let listener = ::std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let config = SentinelConfig {
+        sentinels: vec![redis::IntoConnectionInfo::into_connection_info(
+            format!("redis://127.0.0.1:{}", port).as_str(),
+        )
+        .unwrap()],
+        master_name: "mymaster".to_string(),
+        role: Role::Master,
+        database: 0,
+        password: None,
+    };
+
+    let start = ::std::time::Instant::now();
+    let error = resolve(&config, &ConnectionOptions::default()).unwrap_err();
+    assert!(error.is_timeout());
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn create_tls_sentinel() {
+    let result = create(
+        vec!["rediss://127.0.0.1:26379"],
+        "mymaster",
+        Options::default(),
+    );
+    assert!(result.is_err());
+}
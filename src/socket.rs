@@ -0,0 +1,506 @@
+//! # socket
+//!
+//! Implements the redis connections on top of the underlying socket streams.<br>
+//! The redis-rs connections do not support a connect timeout, TCP keepalive, TCP_NODELAY or TLS, so this
+//! library creates the socket stream itself and uses the redis-rs protocol parsing on top of it.
+//!
+
+#[cfg(test)]
+#[path = "./socket_test.rs"]
+mod socket_test;
+
+use connection::ConnectionOptions;
+#[cfg(feature = "tls")]
+use native_tls::TlsStream;
+use redis;
+use socket2::{SockRef, TcpKeepalive};
+use std::cell::{Cell, RefCell};
+use std::io;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::time::Duration;
+#[cfg(feature = "tls")]
+use tls;
+use types;
+use types::Message;
+
+fn create_closed_error() -> redis::RedisError {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "Connection closed by the server.",
+    )
+    .into()
+}
+
+/// The socket stream of a single connection.
+enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+    #[cfg(feature = "tls")]
+    Tls(TlsStream<TcpStream>),
+}
+
+impl Read for Stream {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        match *self {
+            Stream::Tcp(ref mut stream) => stream.read(buffer),
+            #[cfg(unix)]
+            Stream::Unix(ref mut stream) => stream.read(buffer),
+            #[cfg(feature = "tls")]
+            Stream::Tls(ref mut stream) => stream.read(buffer),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        match *self {
+            Stream::Tcp(ref mut stream) => stream.write(buffer),
+            #[cfg(unix)]
+            Stream::Unix(ref mut stream) => stream.write(buffer),
+            #[cfg(feature = "tls")]
+            Stream::Tls(ref mut stream) => stream.write(buffer),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match *self {
+            Stream::Tcp(ref mut stream) => stream.flush(),
+            #[cfg(unix)]
+            Stream::Unix(ref mut stream) => stream.flush(),
+            #[cfg(feature = "tls")]
+            Stream::Tls(ref mut stream) => stream.flush(),
+        }
+    }
+}
+
+impl Stream {
+    fn set_read_timeout(self: &Stream, timeout: Option<Duration>) -> io::Result<()> {
+        match *self {
+            Stream::Tcp(ref stream) => stream.set_read_timeout(timeout),
+            #[cfg(unix)]
+            Stream::Unix(ref stream) => stream.set_read_timeout(timeout),
+            #[cfg(feature = "tls")]
+            Stream::Tls(ref stream) => stream.get_ref().set_read_timeout(timeout),
+        }
+    }
+
+    fn set_write_timeout(self: &Stream, timeout: Option<Duration>) -> io::Result<()> {
+        match *self {
+            Stream::Tcp(ref stream) => stream.set_write_timeout(timeout),
+            #[cfg(unix)]
+            Stream::Unix(ref stream) => stream.set_write_timeout(timeout),
+            #[cfg(feature = "tls")]
+            Stream::Tls(ref stream) => stream.get_ref().set_write_timeout(timeout),
+        }
+    }
+
+    fn shutdown(self: &mut Stream) {
+        match *self {
+            Stream::Tcp(ref stream) => {
+                let _ = stream.shutdown(Shutdown::Both);
+            }
+            #[cfg(unix)]
+            Stream::Unix(ref stream) => {
+                let _ = stream.shutdown(Shutdown::Both);
+            }
+            #[cfg(feature = "tls")]
+            Stream::Tls(ref mut stream) => {
+                let _ = stream.shutdown();
+                let _ = stream.get_ref().shutdown(Shutdown::Both);
+            }
+        }
+    }
+}
+
+/// A single redis connection.
+pub(crate) struct Connection {
+    stream: RefCell<BufReader<Stream>>,
+    db: i64,
+    /// Set once the stream is shut down, after which the connection can no longer be used
+    closed: Cell<bool>,
+}
+
+impl Connection {
+    /// Shuts down the stream, as it may hold partially read or unread responses.
+    fn close(self: &Connection, stream: &mut BufReader<Stream>) {
+        stream.get_mut().shutdown();
+        self.closed.set(true);
+    }
+
+    /// Returns true if the connection was closed due to an IO or protocol error.
+    pub(crate) fn is_closed(self: &Connection) -> bool {
+        self.closed.get()
+    }
+
+    /// Sends the packed command without reading the response.<br>
+    /// In case of a write error, the connection is closed as the command might have been partially sent.
+    pub(crate) fn send_packed_command(self: &Connection, packed: &[u8]) -> redis::RedisResult<()> {
+        let mut stream = self.stream.borrow_mut();
+
+        let result = {
+            let writer = stream.get_mut();
+            writer.write_all(packed).and_then(|_| writer.flush())
+        };
+
+        if let Err(error) = result {
+            self.close(&mut stream);
+            return Err(error.into());
+        }
+
+        Ok(())
+    }
+
+    /// Reads a single response from the connection.<br>
+    /// The outer error is returned in case the connection is no longer usable, while the inner result holds the
+    /// server response, including an error signalled by the server as the entire response.<br>
+    /// Any error after the response was partially read (for example an error nested in an EXEC response) leaves
+    /// the rest of the response unread, so the connection is closed.
+    fn read_response(
+        self: &Connection,
+    ) -> Result<redis::RedisResult<redis::Value>, redis::RedisError> {
+        let mut stream = self.stream.borrow_mut();
+
+        // nothing was read yet, so a read timeout (for example while waiting for pubsub messages) is recoverable
+        let first_byte = stream.fill_buf()?.first().cloned();
+
+        let result = if first_byte.is_none() {
+            // the server closed the connection (for example an idle connection)
+            Err(create_closed_error())
+        } else if first_byte == Some(b'-') {
+            // the error line is read entirely, so the connection remains usable once it is parsed
+            let mut line = vec![];
+            match stream.read_until(b'\n', &mut line) {
+                Ok(_) if line.ends_with(b"\r\n") => return Ok(redis::parse_redis_value(&line)),
+                Ok(_) => Err(create_closed_error()),
+                Err(error) => Err(error.into()),
+            }
+        } else {
+            redis::Parser::new(&mut *stream).parse_value()
+        };
+
+        match result {
+            Ok(_) => Ok(result),
+            Err(error) => {
+                self.close(&mut stream);
+
+                if types::is_short_read(&error) {
+                    Err(create_closed_error())
+                } else {
+                    Err(error)
+                }
+            }
+        }
+    }
+
+    /// Reads a single response from the connection.
+    pub(crate) fn recv_response(self: &Connection) -> redis::RedisResult<redis::Value> {
+        self.read_response()?
+    }
+
+    /// Sets the read timeout of the connection (None to block forever).
+    pub(crate) fn set_read_timeout(
+        self: &Connection,
+        timeout: Option<Duration>,
+    ) -> redis::RedisResult<()> {
+        self.stream.borrow().get_ref().set_read_timeout(timeout)?;
+
+        Ok(())
+    }
+
+    /// Sets the write timeout of the connection (None to block forever).
+    pub(crate) fn set_write_timeout(
+        self: &Connection,
+        timeout: Option<Duration>,
+    ) -> redis::RedisResult<()> {
+        self.stream.borrow().get_ref().set_write_timeout(timeout)?;
+
+        Ok(())
+    }
+}
+
+impl redis::ConnectionLike for Connection {
+    fn req_packed_command(&self, packed: &[u8]) -> redis::RedisResult<redis::Value> {
+        self.send_packed_command(packed)?;
+
+        self.recv_response()
+    }
+
+    fn req_packed_commands(
+        &self,
+        packed: &[u8],
+        offset: usize,
+        count: usize,
+    ) -> redis::RedisResult<Vec<redis::Value>> {
+        self.send_packed_command(packed)?;
+
+        // all responses are read, even after a server error, to keep the connection in sync
+        let mut values = vec![];
+        let mut first_error = None;
+        for index in 0..(offset + count) {
+            match self.read_response()? {
+                Ok(value) => {
+                    if index >= offset {
+                        values.push(value);
+                    }
+                }
+                Err(error) => {
+                    if first_error.is_none() {
+                        first_error = Some(error);
+                    }
+                }
+            }
+        }
+
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(values),
+        }
+    }
+
+    fn get_db(&self) -> i64 {
+        self.db
+    }
+}
+
+/// A pubsub connection.
+pub(crate) struct PubSub {
+    connection: Connection,
+}
+
+impl PubSub {
+    /// Subscribes to the provided channel.
+    pub(crate) fn subscribe(self: &PubSub, channel: &str) -> redis::RedisResult<()> {
+        redis::cmd("SUBSCRIBE").arg(channel).query(&self.connection)
+    }
+
+    /// Subscribes to the provided channel pattern.
+    pub(crate) fn psubscribe(self: &PubSub, channel: &str) -> redis::RedisResult<()> {
+        redis::cmd("PSUBSCRIBE")
+            .arg(channel)
+            .query(&self.connection)
+    }
+
+    /// Unsubscribes from the provided channel.
+    pub(crate) fn unsubscribe(self: &PubSub, channel: &str) -> redis::RedisResult<()> {
+        redis::cmd("UNSUBSCRIBE")
+            .arg(channel)
+            .query(&self.connection)
+    }
+
+    /// Unsubscribes from the provided channel pattern.
+    pub(crate) fn punsubscribe(self: &PubSub, channel: &str) -> redis::RedisResult<()> {
+        redis::cmd("PUNSUBSCRIBE")
+            .arg(channel)
+            .query(&self.connection)
+    }
+
+    /// Reads the next published message, skipping any other pubsub responses.
+    pub(crate) fn get_message(self: &PubSub) -> redis::RedisResult<Message> {
+        loop {
+            let values: Vec<redis::Value> =
+                redis::from_redis_value(&self.connection.recv_response()?)?;
+            let mut values = values.into_iter();
+
+            let message_type: String = match values.next() {
+                Some(value) => redis::from_redis_value(&value)?,
+                None => continue,
+            };
+
+            let pattern = if message_type == "pmessage" {
+                values.next()
+            } else if message_type == "message" {
+                None
+            } else {
+                continue;
+            };
+
+            if let (Some(channel), Some(payload)) = (values.next(), values.next()) {
+                return Ok(Message::new(channel, payload, pattern));
+            }
+        }
+    }
+
+    /// Sets the read timeout of the connection (None to block forever).
+    pub(crate) fn set_read_timeout(
+        self: &PubSub,
+        timeout: Option<Duration>,
+    ) -> redis::RedisResult<()> {
+        self.connection.set_read_timeout(timeout)
+    }
+}
+
+fn connect_tcp(host: &str, port: u16, options: &ConnectionOptions) -> io::Result<TcpStream> {
+    let mut last_error = io::Error::new(io::ErrorKind::Other, "Unable to resolve address.");
+
+    for socket_address in (host, port).to_socket_addrs()? {
+        let result = match options.connect_timeout {
+            Some(connect_timeout) => TcpStream::connect_timeout(&socket_address, connect_timeout),
+            None => TcpStream::connect(&socket_address),
+        };
+
+        match result {
+            Ok(stream) => {
+                stream.set_nodelay(options.tcp_nodelay)?;
+
+                if let Some(keepalive) = options.tcp_keepalive {
+                    SockRef::from(&stream)
+                        .set_tcp_keepalive(&TcpKeepalive::new().with_time(keepalive))?;
+                }
+
+                return Ok(stream);
+            }
+            Err(error) => last_error = error,
+        }
+    }
+
+    Err(last_error)
+}
+
+#[cfg(feature = "tls")]
+fn create_tcp_stream(
+    host: &str,
+    port: u16,
+    options: &ConnectionOptions,
+) -> redis::RedisResult<Stream> {
+    let stream = connect_tcp(host, port, options)?;
+
+    match options.tls {
+        Some(ref tls_options) => {
+            // the handshake is bounded by the connect timeout
+            stream.set_read_timeout(options.connect_timeout)?;
+            stream.set_write_timeout(options.connect_timeout)?;
+
+            Ok(Stream::Tls(tls::connect(host, stream, tls_options)?))
+        }
+        None => Ok(Stream::Tcp(stream)),
+    }
+}
+
+#[cfg(not(feature = "tls"))]
+fn create_tcp_stream(
+    host: &str,
+    port: u16,
+    options: &ConnectionOptions,
+) -> redis::RedisResult<Stream> {
+    Ok(Stream::Tcp(connect_tcp(host, port, options)?))
+}
+
+#[cfg(unix)]
+fn create_unix_stream(
+    path: &::std::path::Path,
+    options: &ConnectionOptions,
+) -> redis::RedisResult<Stream> {
+    if options.is_tls() {
+        return Err(redis::RedisError::from((
+            redis::ErrorKind::InvalidClientConfig,
+            "TLS is only supported for TCP connections.",
+        )));
+    }
+
+    Ok(Stream::Unix(UnixStream::connect(path)?))
+}
+
+#[cfg(not(unix))]
+fn create_unix_stream(
+    _path: &::std::path::Path,
+    _options: &ConnectionOptions,
+) -> redis::RedisResult<Stream> {
+    Err(redis::RedisError::from((
+        redis::ErrorKind::InvalidClientConfig,
+        "Unix sockets are not available on this platform.",
+    )))
+}
+
+/// Authenticates, selects the database and sets the client name on the new connection.
+fn setup(
+    connection: &Connection,
+    connection_info: &redis::ConnectionInfo,
+    options: &ConnectionOptions,
+) -> redis::RedisResult<()> {
+    if let Some(ref password) = connection_info.passwd {
+        match redis::cmd("AUTH")
+            .arg(password.as_str())
+            .query::<redis::Value>(connection)
+        {
+            Ok(redis::Value::Okay) => (),
+            // connection errors are kept to enable retrying them
+            Err(error) if error.is_io_error() => return Err(error),
+            _ => {
+                return Err(redis::RedisError::from((
+                    redis::ErrorKind::AuthenticationFailed,
+                    "Password authentication failed",
+                )))
+            }
+        }
+    }
+
+    if connection_info.db != 0 {
+        match redis::cmd("SELECT")
+            .arg(connection_info.db)
+            .query::<redis::Value>(connection)
+        {
+            Ok(redis::Value::Okay) => (),
+            Err(error) if error.is_io_error() => return Err(error),
+            _ => {
+                return Err(redis::RedisError::from((
+                    redis::ErrorKind::ResponseError,
+                    "Redis server refused to switch database",
+                )))
+            }
+        }
+    }
+
+    if let Some(ref client_name) = options.client_name {
+        redis::cmd("CLIENT")
+            .arg("SETNAME")
+            .arg(client_name.as_str())
+            .query::<()>(connection)?;
+    }
+
+    Ok(())
+}
+
+/// Creates a new redis connection and applies the provided options on it.<br>
+/// The connection setup (TLS handshake, authentication, database selection and client name) is bounded by the
+/// connect timeout, after which the read and write timeouts are applied.
+pub(crate) fn connect(
+    connection_info: &redis::ConnectionInfo,
+    options: &ConnectionOptions,
+) -> redis::RedisResult<Connection> {
+    let stream = match *connection_info.addr {
+        redis::ConnectionAddr::Tcp(ref host, port) => create_tcp_stream(host, port, options)?,
+        redis::ConnectionAddr::Unix(ref path) => create_unix_stream(path, options)?,
+    };
+
+    let connection = Connection {
+        stream: RefCell::new(BufReader::new(stream)),
+        db: connection_info.db,
+        closed: Cell::new(false),
+    };
+
+    if options.connect_timeout.is_some() {
+        connection.set_read_timeout(options.connect_timeout)?;
+        connection.set_write_timeout(options.connect_timeout)?;
+    }
+
+    setup(&connection, connection_info, options)?;
+
+    connection.set_read_timeout(options.read_timeout)?;
+    connection.set_write_timeout(options.write_timeout)?;
+
+    Ok(connection)
+}
+
+/// Creates a new redis pubsub connection and applies the provided options on it.
+pub(crate) fn connect_pubsub(
+    connection_info: &redis::ConnectionInfo,
+    options: &ConnectionOptions,
+) -> redis::RedisResult<PubSub> {
+    let connection = connect(connection_info, options)?;
+
+    Ok(PubSub { connection })
+}
@@ -0,0 +1,321 @@
+use super::*;
+use connection;
+use std::io::BufRead;
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Instant;
+
+fn read_command(reader: &mut BufRead) -> Option<Vec<String>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+        return None;
+    }
+
+    let count: usize = line.trim()[1..].parse().unwrap();
+    let mut args = vec![];
+    for _ in 0..count {
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        args.push(line.trim_end().to_string());
+    }
+
+    Some(args)
+}
+
+/// Starts a fake redis server which serves a single connection and reports every received command.
+fn start_server(
+    respond: fn(&[String]) -> String,
+) -> (redis::ConnectionInfo, mpsc::Receiver<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        while let Some(args) = read_command(&mut reader) {
+            let response = respond(&args);
+            sender.send(args).unwrap();
+            writer.write_all(response.as_bytes()).unwrap();
+        }
+    });
+
+    let connection_info =
+        connection::parse_connection_info(&format!("redis://127.0.0.1:{}/", port)).unwrap();
+
+    (connection_info, receiver)
+}
+
+fn respond_ok(args: &[String]) -> String {
+    match args[0].as_str() {
+        "GET" => "-ERR wrong number of arguments\r\n".to_string(),
+        _ => "+OK\r\n".to_string(),
+    }
+}
+
+#[test]
+fn connect_socket_options() {
+    let (connection_info, _receiver) = start_server(respond_ok);
+
+    let mut options = ConnectionOptions::default();
+    options.connect_timeout = Some(Duration::from_secs(5));
+    options.tcp_nodelay = true;
+    options.tcp_keepalive = Some(Duration::from_secs(30));
+
+    let connection = connect(&connection_info, &options).unwrap();
+
+    let stream = connection.stream.borrow();
+    match *stream.get_ref() {
+        Stream::Tcp(ref stream) => {
+            assert!(stream.nodelay().unwrap());
+            assert!(SockRef::from(stream).keepalive().unwrap());
+        }
+        #[allow(unreachable_patterns)]
+        _ => panic!("Expected a TCP stream"),
+    }
+}
+
+#[test]
+fn connect_default_socket_options() {
+    let (connection_info, _receiver) = start_server(respond_ok);
+
+    let connection = connect(&connection_info, &ConnectionOptions::default()).unwrap();
+
+    let stream = connection.stream.borrow();
+    match *stream.get_ref() {
+        Stream::Tcp(ref stream) => {
+            assert!(!stream.nodelay().unwrap());
+            assert!(!SockRef::from(stream).keepalive().unwrap());
+        }
+        #[allow(unreachable_patterns)]
+        _ => panic!("Expected a TCP stream"),
+    }
+}
+
+#[test]
+fn connect_setup_commands() {
+    let (mut connection_info, receiver) = start_server(respond_ok);
+    connection_info.passwd = Some("secret".to_string());
+    connection_info.db = 2;
+
+    let mut options = ConnectionOptions::default();
+    options.client_name = Some("myapp".to_string());
+
+    let connection = connect(&connection_info, &options).unwrap();
+    assert_eq!(connection.db, 2);
+
+    assert_eq!(receiver.recv().unwrap(), vec!["AUTH", "secret"]);
+    assert_eq!(receiver.recv().unwrap(), vec!["SELECT", "2"]);
+    assert_eq!(receiver.recv().unwrap(), vec!["CLIENT", "SETNAME", "myapp"]);
+}
+
+#[test]
+fn connect_setup_timeout() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    // accepts the connection but never responds
+    thread::spawn(move || {
+        let _stream = listener.accept().unwrap();
+        thread::sleep(Duration::from_secs(10));
+    });
+
+    let mut connection_info =
+        connection::parse_connection_info(&format!("redis://127.0.0.1:{}/", port)).unwrap();
+    connection_info.passwd = Some("secret".to_string());
+
+    let mut options = ConnectionOptions::default();
+    options.connect_timeout = Some(Duration::from_millis(100));
+
+    let start = Instant::now();
+    let result = connect(&connection_info, &options);
+
+    assert!(result.err().unwrap().is_timeout());
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn connect_refused() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+
+    let connection_info =
+        connection::parse_connection_info(&format!("redis://127.0.0.1:{}/", port)).unwrap();
+
+    let mut options = ConnectionOptions::default();
+    options.connect_timeout = Some(Duration::from_secs(1));
+
+    let result = connect(&connection_info, &options);
+
+    assert!(result.err().unwrap().is_io_error());
+}
+
+#[test]
+fn req_packed_commands_server_error() {
+    let (connection_info, _receiver) = start_server(respond_ok);
+
+    let connection = connect(&connection_info, &ConnectionOptions::default()).unwrap();
+
+    let result: redis::RedisResult<()> = redis::pipe()
+        .cmd("SET")
+        .arg("key")
+        .arg("value")
+        .cmd("GET")
+        .cmd("SET")
+        .arg("key")
+        .arg("value")
+        .query(&connection);
+    assert_eq!(result.unwrap_err().kind(), redis::ErrorKind::ResponseError);
+
+    // all responses were read so the connection is still in sync
+    let value: redis::Value = redis::cmd("PING").query(&connection).unwrap();
+    assert_eq!(value, redis::Value::Okay);
+}
+
+#[test]
+fn read_response_top_level_error() {
+    let (connection_info, _receiver) = start_server(respond_ok);
+
+    let connection = connect(&connection_info, &ConnectionOptions::default()).unwrap();
+
+    let result: redis::RedisResult<redis::Value> = redis::cmd("GET").query(&connection);
+    assert_eq!(result.unwrap_err().kind(), redis::ErrorKind::ResponseError);
+    assert!(!connection.is_closed());
+
+    let value: redis::Value = redis::cmd("PING").query(&connection).unwrap();
+    assert_eq!(value, redis::Value::Okay);
+}
+
+#[test]
+fn read_response_nested_error() {
+    let (connection_info, _receiver) = start_server(|args| match args[0].as_str() {
+        "EXEC" => "*2\r\n-WRONGTYPE Operation against a key\r\n$5\r\nstale\r\n".to_string(),
+        _ => "+OK\r\n".to_string(),
+    });
+
+    let connection = connect(&connection_info, &ConnectionOptions::default()).unwrap();
+
+    let result: redis::RedisResult<redis::Value> = redis::cmd("EXEC").query(&connection);
+    assert_eq!(result.unwrap_err().kind(), redis::ErrorKind::ExtensionError);
+
+    // the rest of the EXEC response was not read, so it must not be returned to the next command
+    assert!(connection.is_closed());
+    let result: redis::RedisResult<redis::Value> = redis::cmd("PING").query(&connection);
+    assert!(result.is_err());
+}
+
+#[test]
+fn read_response_invalid_response() {
+    let (connection_info, _receiver) = start_server(|_| "?garbage\r\n+OK\r\n".to_string());
+
+    let connection = connect(&connection_info, &ConnectionOptions::default()).unwrap();
+
+    let result: redis::RedisResult<redis::Value> = redis::cmd("PING").query(&connection);
+    assert!(result.is_err());
+    assert!(connection.is_closed());
+}
+
+#[test]
+fn read_response_connection_closed() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    // sends a partial response and closes the connection
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        read_command(&mut BufReader::new(stream));
+        writer.write_all(b"$5\r\nval").unwrap();
+    });
+
+    let connection_info =
+        connection::parse_connection_info(&format!("redis://127.0.0.1:{}/", port)).unwrap();
+    let connection = connect(&connection_info, &ConnectionOptions::default()).unwrap();
+
+    let result: redis::RedisResult<redis::Value> = redis::cmd("GET").query(&connection);
+    let error = result.unwrap_err();
+    assert!(error.is_io_error());
+    assert!(connection.is_closed());
+}
+
+#[test]
+fn read_response_timeout_keeps_connection() {
+    let (connection_info, _receiver) = start_server(respond_ok);
+
+    let pubsub = connect_pubsub(&connection_info, &ConnectionOptions::default()).unwrap();
+    pubsub
+        .set_read_timeout(Some(Duration::from_millis(50)))
+        .unwrap();
+
+    // no message is published, so the read times out before any data is read
+    let error = pubsub.get_message().err().unwrap();
+    assert!(error.is_timeout());
+    assert!(!pubsub.connection.is_closed());
+}
+
+#[test]
+fn send_packed_command_write_error() {
+    let (connection_info, _receiver) = start_server(respond_ok);
+
+    let connection = connect(&connection_info, &ConnectionOptions::default()).unwrap();
+    connection.stream.borrow_mut().get_mut().shutdown();
+
+    assert!(connection
+        .send_packed_command(b"*1\r\n$4\r\nPING\r\n")
+        .is_err());
+    assert!(connection.is_closed());
+}
+
+#[test]
+fn pubsub_get_message() {
+    let (connection_info, _receiver) = start_server(|args| match args[0].as_str() {
+        "SUBSCRIBE" => concat!(
+            "*3\r\n$9\r\nsubscribe\r\n$2\r\nch\r\n:1\r\n",
+            "*3\r\n$7\r\nmessage\r\n$2\r\nch\r\n$5\r\nhello\r\n"
+        )
+        .to_string(),
+        _ => "+OK\r\n".to_string(),
+    });
+
+    let pubsub = connect_pubsub(&connection_info, &ConnectionOptions::default()).unwrap();
+    pubsub.subscribe("ch").unwrap();
+    pubsub
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+
+    let message = pubsub.get_message().unwrap();
+    assert_eq!(message.get_channel_name(), "ch");
+    assert!(!message.from_pattern());
+    let payload: String = message.get_payload().unwrap();
+    assert_eq!(payload, "hello");
+}
+
+#[test]
+fn pubsub_get_pattern_message() {
+    let (connection_info, _receiver) = start_server(|args| match args[0].as_str() {
+        "PSUBSCRIBE" => concat!(
+            "*3\r\n$10\r\npsubscribe\r\n$3\r\nch*\r\n:1\r\n",
+            "*4\r\n$8\r\npmessage\r\n$3\r\nch*\r\n$3\r\nch1\r\n$5\r\nhello\r\n"
+        )
+        .to_string(),
+        _ => "+OK\r\n".to_string(),
+    });
+
+    let pubsub = connect_pubsub(&connection_info, &ConnectionOptions::default()).unwrap();
+    pubsub.psubscribe("ch*").unwrap();
+    pubsub
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+
+    let message = pubsub.get_message().unwrap();
+    assert_eq!(message.get_channel_name(), "ch1");
+    assert!(message.from_pattern());
+    let pattern: String = message.get_pattern().unwrap();
+    assert_eq!(pattern, "ch*");
+}
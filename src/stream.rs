@@ -0,0 +1,217 @@
+//! # stream
+//!
+//! Parses the stream commands responses and implements the stream consumer group reader.
+//!
+
+#[cfg(test)]
+#[path = "./stream_test.rs"]
+mod stream_test;
+
+use client::Client;
+use commands;
+use redis;
+use types;
+use types::{
+    ErrorInfo, RedisError, RedisResult, StreamAutoClaimResult, StreamEntry, StreamPendingEntry,
+    StreamPendingSummary, StreamReadOptions,
+};
+
+fn create_parse_error() -> RedisError {
+    RedisError {
+        info: ErrorInfo::Description(types::PARSE_FAILED),
+    }
+}
+
+fn from_value<T: redis::FromRedisValue>(value: &redis::Value) -> RedisResult<T> {
+    match redis::from_redis_value(value) {
+        Ok(output) => Ok(output),
+        Err(error) => Err(RedisError {
+            info: ErrorInfo::RedisError(error),
+        }),
+    }
+}
+
+fn to_values(value: &redis::Value) -> RedisResult<&[redis::Value]> {
+    match *value {
+        redis::Value::Bulk(ref values) => Ok(values),
+        redis::Value::Nil => Ok(&[]),
+        _ => Err(create_parse_error()),
+    }
+}
+
+/// Parses a single stream entry, for example: `[id, [field1, value1, field2, value2]]`
+pub(crate) fn to_entry(value: &redis::Value) -> RedisResult<StreamEntry> {
+    let values = to_values(value)?;
+    if values.len() != 2 {
+        return Err(create_parse_error());
+    }
+
+    let fields = commands::from_values(to_values(&values[1])?)?;
+
+    Ok(StreamEntry {
+        id: from_value(&values[0])?,
+        fields: commands::to_field_values(fields)?,
+    })
+}
+
+/// Parses a list of stream entries (nil is parsed as an empty list).
+pub(crate) fn to_entries(value: &redis::Value) -> RedisResult<Vec<StreamEntry>> {
+    to_values(value)?.iter().map(to_entry).collect()
+}
+
+/// Parses the entries of multiple streams, for example: `[[key1, [entries...]], [key2, [entries...]]]`<br>
+/// In case the read timed out (nil response), no streams are returned.
+pub(crate) fn to_streams(value: &redis::Value) -> RedisResult<Vec<(String, Vec<StreamEntry>)>> {
+    let mut output = vec![];
+
+    for stream in to_values(value)? {
+        let values = to_values(stream)?;
+        if values.len() != 2 {
+            return Err(create_parse_error());
+        }
+
+        output.push((from_value(&values[0])?, to_entries(&values[1])?));
+    }
+
+    Ok(output)
+}
+
+/// Parses the XPENDING summary, for example: `[count, min_id, max_id, [[consumer, count]...]]`
+pub(crate) fn to_pending_summary(value: &redis::Value) -> RedisResult<StreamPendingSummary> {
+    let values = to_values(value)?;
+    if values.len() != 4 {
+        return Err(create_parse_error());
+    }
+
+    let mut consumers = vec![];
+    for consumer in to_values(&values[3])? {
+        let (name, count): (String, usize) = from_value(consumer)?;
+        consumers.push((name, count));
+    }
+
+    Ok(StreamPendingSummary {
+        count: from_value(&values[0])?,
+        min_id: from_value(&values[1])?,
+        max_id: from_value(&values[2])?,
+        consumers,
+    })
+}
+
+/// Parses the XPENDING entries, for example: `[[id, consumer, idle, deliveries]...]`
+pub(crate) fn to_pending_entries(value: &redis::Value) -> RedisResult<Vec<StreamPendingEntry>> {
+    let mut output = vec![];
+
+    for entry in to_values(value)? {
+        let (id, consumer, idle, deliveries): (String, String, u64, usize) = from_value(entry)?;
+
+        output.push(StreamPendingEntry {
+            id,
+            consumer,
+            idle,
+            deliveries,
+        });
+    }
+
+    Ok(output)
+}
+
+/// Parses the XAUTOCLAIM output, for example: `[next_id, [entries...], [deleted_ids...]]`<br>
+/// The deleted IDs are only returned by redis 7 and above.
+pub(crate) fn to_autoclaim_result(value: &redis::Value) -> RedisResult<StreamAutoClaimResult> {
+    let values = to_values(value)?;
+    if values.len() < 2 {
+        return Err(create_parse_error());
+    }
+
+    let deleted_ids = match values.get(2) {
+        Some(deleted_ids) => from_value(deleted_ids)?,
+        None => vec![],
+    };
+
+    Ok(StreamAutoClaimResult {
+        next_id: from_value(&values[0])?,
+        entries: to_entries(&values[1])?,
+        deleted_ids,
+    })
+}
+
+/// Reads the entries of a stream as a member of a consumer group.<br>
+/// The consumer pending entries (entries which were delivered to this consumer but not acknowledged, for example
+/// before a crash) are returned first, after which only new entries are returned.<br>
+/// Every read entry must be acknowledged (see the ack function) once processed, otherwise it will be returned again
+/// by the next reader of the same consumer.
+pub struct StreamGroupReader<'a> {
+    client: &'a mut Client,
+    key: String,
+    group: String,
+    consumer: String,
+    options: StreamReadOptions,
+    pending_id: Option<String>,
+}
+
+impl<'a> StreamGroupReader<'a> {
+    fn read_from(&mut self, id: &str, options: StreamReadOptions) -> RedisResult<Vec<StreamEntry>> {
+        let streams = self.client.xreadgroup(
+            &self.group,
+            &self.consumer,
+            vec![(self.key.as_str(), id)],
+            options,
+        )?;
+
+        Ok(streams
+            .into_iter()
+            .filter(|stream| stream.0 == self.key)
+            .flat_map(|stream| stream.1)
+            .collect())
+    }
+
+    /// Returns the next batch of entries.<br>
+    /// In case the read options define a block timeout and no new entries were added within this timeout, an empty
+    /// batch is returned.
+    pub fn read(&mut self) -> RedisResult<Vec<StreamEntry>> {
+        if let Some(pending_id) = self.pending_id.clone() {
+            // pending entries are already available, so there is no need to block
+            let mut options = self.options;
+            options.block = None;
+
+            let entries = self.read_from(&pending_id, options)?;
+            match entries.last() {
+                Some(entry) => {
+                    self.pending_id = Some(entry.id.clone());
+                    return Ok(entries);
+                }
+                None => self.pending_id = None,
+            }
+        }
+
+        let options = self.options;
+        self.read_from(">", options)
+    }
+
+    /// Acknowledges the provided entries, removing them from the consumer pending entries.<br>
+    /// Returns the amount of acknowledged entries.
+    pub fn ack(&mut self, ids: Vec<&str>) -> RedisResult<i32> {
+        let key = self.key.clone();
+        let group = self.group.clone();
+
+        self.client.xack(&key, &group, ids)
+    }
+}
+
+/// Creates and returns a new stream consumer group reader
+pub(crate) fn create_group_reader<'a>(
+    client: &'a mut Client,
+    key: &str,
+    group: &str,
+    consumer: &str,
+    options: StreamReadOptions,
+) -> StreamGroupReader<'a> {
+    StreamGroupReader {
+        client,
+        key: key.to_string(),
+        group: group.to_string(),
+        consumer: consumer.to_string(),
+        options,
+        pending_id: Some("0".to_string()),
+    }
+}
@@ -0,0 +1,138 @@
+use super::*;
+
+fn parse(response: &[u8]) -> redis::Value {
+    redis::parse_redis_value(response).unwrap()
+}
+
+#[test]
+fn to_entry_valid() {
+    let entry = to_entry(&parse(
+        b"*2\r\n$3\r\n1-0\r\n*4\r\n$1\r\na\r\n$1\r\n1\r\n$1\r\nb\r\n$1\r\n2\r\n",
+    ))
+    .unwrap();
+
+    assert_eq!(
+        entry,
+        StreamEntry {
+            id: "1-0".to_string(),
+            fields: vec![
+                ("a".to_string(), "1".to_string()),
+                ("b".to_string(), "2".to_string())
+            ],
+        }
+    );
+}
+
+#[test]
+fn to_entry_deleted() {
+    let entry = to_entry(&parse(b"*2\r\n$3\r\n1-0\r\n$-1\r\n")).unwrap();
+
+    assert_eq!(entry.id, "1-0");
+    assert!(entry.fields.is_empty());
+}
+
+#[test]
+fn to_entry_invalid() {
+    assert!(to_entry(&parse(b"*1\r\n$3\r\n1-0\r\n")).is_err());
+    assert!(to_entry(&parse(b"*2\r\n$3\r\n1-0\r\n*1\r\n$1\r\na\r\n")).is_err());
+    assert!(to_entry(&parse(b":1\r\n")).is_err());
+}
+
+#[test]
+fn to_entry_binary_value() {
+    // a value which can't be converted fails the entry instead of shifting the other fields
+    let result = to_entry(&parse(
+        b"*2\r\n$3\r\n1-0\r\n*4\r\n$1\r\na\r\n$1\r\n\xff\r\n$1\r\nb\r\n$1\r\n2\r\n",
+    ));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn to_entries_nil() {
+    assert!(to_entries(&redis::Value::Nil).unwrap().is_empty());
+}
+
+#[test]
+fn to_streams_valid() {
+    let streams = to_streams(&parse(
+        b"*1\r\n*2\r\n$6\r\nstream\r\n*1\r\n*2\r\n$3\r\n1-0\r\n*2\r\n$1\r\na\r\n$1\r\n1\r\n",
+    ))
+    .unwrap();
+
+    assert_eq!(streams.len(), 1);
+    assert_eq!(streams[0].0, "stream");
+    assert_eq!(streams[0].1.len(), 1);
+    assert_eq!(streams[0].1[0].id, "1-0");
+}
+
+#[test]
+fn to_streams_timeout() {
+    assert!(to_streams(&redis::Value::Nil).unwrap().is_empty());
+}
+
+#[test]
+fn to_pending_summary_valid() {
+    let summary = to_pending_summary(&parse(
+        b"*4\r\n:2\r\n$3\r\n1-0\r\n$3\r\n2-0\r\n*1\r\n*2\r\n$8\r\nconsumer\r\n$1\r\n2\r\n",
+    ))
+    .unwrap();
+
+    assert_eq!(
+        summary,
+        StreamPendingSummary {
+            count: 2,
+            min_id: Some("1-0".to_string()),
+            max_id: Some("2-0".to_string()),
+            consumers: vec![("consumer".to_string(), 2)],
+        }
+    );
+}
+
+#[test]
+fn to_pending_summary_empty() {
+    let summary = to_pending_summary(&parse(b"*4\r\n:0\r\n$-1\r\n$-1\r\n*-1\r\n")).unwrap();
+
+    assert_eq!(summary.count, 0);
+    assert!(summary.min_id.is_none());
+    assert!(summary.consumers.is_empty());
+}
+
+#[test]
+fn to_pending_entries_valid() {
+    let entries = to_pending_entries(&parse(
+        b"*1\r\n*4\r\n$3\r\n1-0\r\n$8\r\nconsumer\r\n:100\r\n:3\r\n",
+    ))
+    .unwrap();
+
+    assert_eq!(
+        entries,
+        vec![StreamPendingEntry {
+            id: "1-0".to_string(),
+            consumer: "consumer".to_string(),
+            idle: 100,
+            deliveries: 3,
+        }]
+    );
+}
+
+#[test]
+fn to_autoclaim_result_valid() {
+    let result = to_autoclaim_result(&parse(
+        b"*3\r\n$3\r\n0-0\r\n*1\r\n*2\r\n$3\r\n1-0\r\n*2\r\n$1\r\na\r\n$1\r\n1\r\n*1\r\n$3\r\n2-0\r\n",
+    ))
+    .unwrap();
+
+    assert_eq!(result.next_id, "0-0");
+    assert_eq!(result.entries.len(), 1);
+    assert_eq!(result.deleted_ids, vec!["2-0"]);
+}
+
+#[test]
+fn to_autoclaim_result_without_deleted() {
+    let result = to_autoclaim_result(&parse(b"*2\r\n$3\r\n0-0\r\n*0\r\n")).unwrap();
+
+    assert_eq!(result.next_id, "0-0");
+    assert!(result.entries.is_empty());
+    assert!(result.deleted_ids.is_empty());
+}
@@ -7,11 +7,15 @@
 #[path = "./subscriber_test.rs"]
 mod subscriber_test;
 
+use connection;
+use connection::ConnectionOptions;
 use redis;
+use socket;
 use std::ops::Add;
 use std::option::Option;
 use std::time::Duration;
 use std::time::SystemTime;
+use types;
 use types::{ErrorInfo, RedisEmptyResult, RedisError, RedisMessageResult};
 
 /// The redis pubsub wrapper.
@@ -19,13 +23,18 @@ pub(crate) struct Subscriber {
     subscribed: bool,
     subscriptions: Vec<String>,
     psubscriptions: Vec<String>,
-    pubsub: Option<redis::PubSub>,
+    pubsub: Option<socket::PubSub>,
+    /// The options applied on every new pubsub connection
+    options: ConnectionOptions,
 }
 
-fn subscribe_all(subscriber: &mut Subscriber, client: &redis::Client) -> RedisEmptyResult {
+fn subscribe_all(
+    subscriber: &mut Subscriber,
+    connection_info: &redis::ConnectionInfo,
+) -> RedisEmptyResult {
     // get pubsub
-    match client.get_pubsub() {
-        Ok(mut redis_pubsub) => {
+    match connection::connect_pubsub(connection_info, &subscriber.options) {
+        Ok(redis_pubsub) => {
             for channel in &subscriber.subscriptions {
                 let result = redis_pubsub.subscribe(channel);
 
@@ -90,17 +99,17 @@ fn get_message(subscriber: &mut Subscriber, timeout: u64) -> RedisMessageResult
 
             if timeout_result.is_err() {
                 output = Err(RedisError {
-                    info: ErrorInfo::Description("Unable to set read timeout."),
+                    info: ErrorInfo::Description(types::READ_TIMEOUT_NOT_SET),
                 })
             } else {
                 let start = SystemTime::now();
 
                 let message_result = redis_pubsub.get_message();
 
-                timeout_result = redis_pubsub.set_read_timeout(None);
+                timeout_result = redis_pubsub.set_read_timeout(subscriber.options.read_timeout);
                 if timeout_result.is_err() {
                     output = Err(RedisError {
-                        info: ErrorInfo::Description("Unable to set read timeout."),
+                        info: ErrorInfo::Description(types::READ_TIMEOUT_NOT_SET),
                     })
                 } else {
                     output = match message_result {
@@ -128,17 +137,17 @@ fn get_message(subscriber: &mut Subscriber, timeout: u64) -> RedisMessageResult
             output
         }
         None => Err(RedisError {
-            info: ErrorInfo::Description("Error while fetching pubsub."),
+            info: ErrorInfo::Description(types::PUBSUB_NOT_AVAILABLE),
         }),
     }
 }
 
 fn subscribe_and_get(
     subscriber: &mut Subscriber,
-    client: &redis::Client,
+    connection_info: &redis::ConnectionInfo,
     timeout: u64,
 ) -> RedisMessageResult {
-    match subscribe_all(subscriber, client) {
+    match subscribe_all(subscriber, connection_info) {
         Err(error) => Err(error),
         _ => match get_message(subscriber, timeout) {
             Ok(message) => Ok(message),
@@ -156,16 +165,16 @@ fn subscribe(subscriber: &mut Subscriber, channel: &str, pattern: bool) -> Redis
         }
 
         match subscriber.pubsub {
-            Some(ref mut redis_pubsub) => {
+            Some(ref redis_pubsub) => {
                 if pattern {
-                    match redis_pubsub.psubscribe(channel.to_string()) {
+                    match redis_pubsub.psubscribe(channel) {
                         Err(error) => Err(RedisError {
                             info: ErrorInfo::RedisError(error),
                         }),
                         _ => Ok(()),
                     }
                 } else {
-                    match redis_pubsub.subscribe(channel.to_string()) {
+                    match redis_pubsub.subscribe(channel) {
                         Err(error) => Err(RedisError {
                             info: ErrorInfo::RedisError(error),
                         }),
@@ -174,7 +183,7 @@ fn subscribe(subscriber: &mut Subscriber, channel: &str, pattern: bool) -> Redis
                 }
             }
             None => Err(RedisError {
-                info: ErrorInfo::Description("Error while fetching pubsub."),
+                info: ErrorInfo::Description(types::PUBSUB_NOT_AVAILABLE),
             }),
         }
     } else {
@@ -208,16 +217,16 @@ fn unsubscribe(subscriber: &mut Subscriber, channel: &str, pattern: bool) -> Red
 
             if subscriber.subscribed {
                 unsub_result = match subscriber.pubsub {
-                    Some(ref mut redis_pubsub) => {
+                    Some(ref redis_pubsub) => {
                         if pattern {
-                            match redis_pubsub.punsubscribe(channel.to_string()) {
+                            match redis_pubsub.punsubscribe(channel) {
                                 Err(error) => Err(RedisError {
                                     info: ErrorInfo::RedisError(error),
                                 }),
                                 _ => Ok(()),
                             }
                         } else {
-                            match redis_pubsub.unsubscribe(channel.to_string()) {
+                            match redis_pubsub.unsubscribe(channel) {
                                 Err(error) => Err(RedisError {
                                     info: ErrorInfo::RedisError(error),
                                 }),
@@ -226,7 +235,7 @@ fn unsubscribe(subscriber: &mut Subscriber, channel: &str, pattern: bool) -> Red
                         }
                     }
                     None => Err(RedisError {
-                        info: ErrorInfo::Description("Error while fetching pubsub."),
+                        info: ErrorInfo::Description(types::PUBSUB_NOT_AVAILABLE),
                     }),
                 }
             }
@@ -288,7 +297,7 @@ impl Subscriber {
 
     pub(crate) fn get_message(
         self: &mut Subscriber,
-        client: &redis::Client,
+        connection_info: &redis::ConnectionInfo,
         timeout: u64,
     ) -> RedisMessageResult {
         if self.pubsub.is_some() {
@@ -298,11 +307,11 @@ impl Subscriber {
                     ErrorInfo::TimeoutError(description) => Err(RedisError {
                         info: ErrorInfo::TimeoutError(description),
                     }),
-                    _ => subscribe_and_get(self, client, timeout),
+                    _ => subscribe_and_get(self, connection_info, timeout),
                 },
             }
         } else {
-            subscribe_and_get(self, client, timeout)
+            subscribe_and_get(self, connection_info, timeout)
         }
     }
 
@@ -337,11 +346,12 @@ impl Subscriber {
 }
 
 /// Creates and returns a new connection
-pub(crate) fn create() -> Subscriber {
+pub(crate) fn create(options: ConnectionOptions) -> Subscriber {
     Subscriber {
         subscribed: false,
         subscriptions: vec![],
         psubscriptions: vec![],
         pubsub: None,
+        options,
     }
 }
@@ -2,7 +2,7 @@ use super::*;
 
 #[test]
 fn create_check_state() {
-    let subscriber = create();
+    let subscriber = create(ConnectionOptions::default());
     assert!(!subscriber.subscribed);
     assert_eq!(subscriber.subscriptions.len(), 0);
     assert_eq!(subscriber.psubscriptions.len(), 0);
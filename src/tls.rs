@@ -0,0 +1,88 @@
+//! # tls
+//!
+//! Enables connecting to redis using TLS (available via the tls feature).
+//!
+
+#[cfg(test)]
+#[path = "./tls_test.rs"]
+mod tls_test;
+
+use native_tls::{Certificate, Identity, TlsConnector, TlsStream};
+use redis;
+use std::io;
+use std::net::TcpStream;
+
+/// Holds the TLS configuration.
+#[derive(Debug, Clone, Default)]
+pub struct TlsOptions {
+    /// PEM encoded CA certificates used to verify the server certificate (in addition to the system root certificates)
+    pub ca_certificates: Option<Vec<u8>>,
+    /// PEM encoded client certificate used for mutual TLS
+    pub client_certificate: Option<Vec<u8>>,
+    /// PEM encoded PKCS #8 private key of the client certificate
+    pub client_key: Option<Vec<u8>>,
+    /// The server name used for SNI and to verify the server certificate (defaults to the connection host)
+    pub server_name: Option<String>,
+    /// If true, the server certificate and host name are not verified (should only be used for testing)
+    pub insecure: bool,
+}
+
+fn to_redis_error<E: ToString>(error: E) -> redis::RedisError {
+    io::Error::new(io::ErrorKind::Other, error.to_string()).into()
+}
+
+fn create_connector(options: &TlsOptions) -> redis::RedisResult<TlsConnector> {
+    let mut builder = TlsConnector::builder();
+
+    if let Some(ref ca_certificates) = options.ca_certificates {
+        let certificates = Certificate::stack_from_pem(ca_certificates).map_err(to_redis_error)?;
+        if certificates.is_empty() {
+            return Err(redis::RedisError::from((
+                redis::ErrorKind::InvalidClientConfig,
+                "No CA certificates found.",
+            )));
+        }
+
+        for certificate in certificates {
+            builder.add_root_certificate(certificate);
+        }
+    }
+
+    match (&options.client_certificate, &options.client_key) {
+        (&Some(ref certificate), &Some(ref key)) => {
+            builder.identity(Identity::from_pkcs8(certificate, key).map_err(to_redis_error)?);
+        }
+        (&None, &None) => (),
+        _ => {
+            return Err(redis::RedisError::from((
+                redis::ErrorKind::InvalidClientConfig,
+                "Both client certificate and key are required for mutual TLS.",
+            )))
+        }
+    };
+
+    if options.insecure {
+        builder.danger_accept_invalid_certs(true);
+        builder.danger_accept_invalid_hostnames(true);
+    }
+
+    builder.build().map_err(to_redis_error)
+}
+
+/// Runs the TLS handshake on top of the provided TCP stream.<br>
+/// The server certificate is verified against the server name option, defaulting to the connection host.
+pub(crate) fn connect(
+    host: &str,
+    stream: TcpStream,
+    options: &TlsOptions,
+) -> redis::RedisResult<TlsStream<TcpStream>> {
+    let connector = create_connector(options)?;
+    let server_name = match options.server_name {
+        Some(ref server_name) => server_name.as_str(),
+        None => host,
+    };
+
+    connector
+        .connect(server_name, stream)
+        .map_err(to_redis_error)
+}
@@ -0,0 +1,154 @@
+use super::*;
+use client::ClientBuilder;
+use native_tls::TlsAcceptor;
+use std::io::Write;
+use std::net::TcpListener;
+use std::thread;
+
+// self signed certificate and key which are only used by the tests (see tests/tls/README.md)
+const CERTIFICATE: &'static [u8] = include_bytes!("../tests/tls/test_only_cert.pem");
+const KEY: &'static [u8] = include_bytes!("../tests/tls/test_only_key.pem");
+
+/// Starts a TLS server which responds to ECHO commands and returns its port
+fn start_echo_server() -> u16 {
+    let identity = Identity::from_pkcs8(CERTIFICATE, KEY).unwrap();
+    let acceptor = TlsAcceptor::new(identity).unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let acceptor = acceptor.clone();
+
+            thread::spawn(move || {
+                let mut tls_stream = match acceptor.accept(stream.unwrap()) {
+                    Ok(tls_stream) => tls_stream,
+                    Err(_) => return,
+                };
+
+                loop {
+                    let request = match redis::Parser::new(&mut tls_stream).parse_value() {
+                        Ok(request) => request,
+                        Err(_) => break,
+                    };
+                    let args: Vec<String> = redis::from_redis_value(&request).unwrap();
+
+                    let response = match args[0].as_str() {
+                        "ECHO" => format!("${}\r\n{}\r\n", args[1].len(), args[1]),
+                        // confirm the subscription and publish a single message to the channel
+                        "SUBSCRIBE" => format!(
+                            "*3\r\n$9\r\nsubscribe\r\n${0}\r\n{1}\r\n:1\r\n*3\r\n$7\r\nmessage\r\n${0}\r\n{1}\r\n$5\r\nhello\r\n",
+                            args[1].len(),
+                            args[1]
+                        ),
+                        _ => "+OK\r\n".to_string(),
+                    };
+                    if tls_stream.write_all(response.as_bytes()).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+
+    port
+}
+
+#[test]
+fn create_connector_valid() {
+    let mut options = TlsOptions::default();
+    options.ca_certificates = Some(CERTIFICATE.to_vec());
+    options.client_certificate = Some(CERTIFICATE.to_vec());
+    options.client_key = Some(KEY.to_vec());
+
+    assert!(create_connector(&options).is_ok());
+}
+
+#[test]
+fn create_connector_missing_key() {
+    let mut options = TlsOptions::default();
+    options.client_certificate = Some(CERTIFICATE.to_vec());
+
+    let error = create_connector(&options).unwrap_err();
+    assert_eq!(error.kind(), redis::ErrorKind::InvalidClientConfig);
+}
+
+#[test]
+fn create_connector_invalid_certificate() {
+    let mut options = TlsOptions::default();
+    options.ca_certificates = Some(b"invalid".to_vec());
+
+    assert!(create_connector(&options).is_err());
+}
+
+#[test]
+fn run_command_with_ca_certificate() {
+    let port = start_echo_server();
+
+    let mut options = TlsOptions::default();
+    options.ca_certificates = Some(CERTIFICATE.to_vec());
+    options.server_name = Some("localhost".to_string());
+
+    let mut client = ClientBuilder::new(&format!("rediss://127.0.0.1:{}/", port))
+        .tls(options)
+        .build()
+        .unwrap();
+
+    for index in 0..10 {
+        let value = client
+            .run_command::<String>("ECHO", vec![&*format!("value{}", index)])
+            .unwrap();
+        assert_eq!(value, format!("value{}", index));
+    }
+}
+
+#[test]
+fn run_command_insecure() {
+    let port = start_echo_server();
+
+    let mut options = TlsOptions::default();
+    options.insecure = true;
+
+    let mut client = ClientBuilder::new(&format!("redis://127.0.0.1:{}/", port))
+        .tls(options)
+        .build()
+        .unwrap();
+
+    let value = client
+        .run_command::<String>("ECHO", vec!["testing"])
+        .unwrap();
+    assert_eq!(value, "testing");
+}
+
+#[test]
+fn run_command_untrusted_certificate() {
+    let port = start_echo_server();
+
+    let mut client = ClientBuilder::new(&format!("rediss://127.0.0.1:{}/", port))
+        .retry_policy(::types::RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    assert!(client
+        .run_command::<String>("ECHO", vec!["testing"])
+        .is_err());
+}
+
+#[test]
+fn pubsub_over_tls() {
+    let port = start_echo_server();
+
+    let mut options = TlsOptions::default();
+    options.insecure = true;
+
+    let mut client = ClientBuilder::new(&format!("rediss://127.0.0.1:{}/", port))
+        .tls(options)
+        .build()
+        .unwrap();
+
+    client.subscribe("tls_channel").unwrap();
+    let message = client.get_message(1000).unwrap();
+    assert_eq!(message.get_channel_name(), "tls_channel");
+    let payload: String = message.get_payload().unwrap();
+    assert_eq!(payload, "hello");
+}
@@ -0,0 +1,149 @@
+//! # transaction
+//!
+//! Implements atomic redis transactions with optimistic locking.
+//!
+
+#[cfg(test)]
+#[path = "./transaction_test.rs"]
+mod transaction_test;
+
+use client::Client;
+use pipeline;
+use pipeline::Pipeline;
+use redis;
+use types;
+use types::{ErrorInfo, RedisArg, RedisBinaryArg, RedisEmptyResult, RedisError, RedisResult};
+
+/// The max amount of times the transaction is invoked in case the watched keys keep being modified
+const MAX_ATTEMPTS: u32 = 100;
+
+/// Holds the commands of a single transaction attempt.<br>
+/// All queued commands are executed atomically once the transaction function returns.
+pub struct Transaction<'a> {
+    pipeline: Pipeline<'a>,
+}
+
+impl<'a> Transaction<'a> {
+    /// Queues the requested command with the provided arguments.<br>
+    /// This can be used to queue operations that are not directly exposed by the transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The Redis command, for example: `GET`
+    /// * `args` - Vector of arguments for the given command
+    pub fn cmd(&mut self, command: &str, args: Vec<&str>) -> &mut Transaction<'a> {
+        self.binary_cmd(command, args)
+    }
+
+    /// Queues the requested command with the provided binary safe arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The Redis command, for example: `SET`
+    /// * `args` - Vector of arguments (strings or bytes) for the given command
+    pub fn binary_cmd(
+        &mut self,
+        command: &str,
+        args: Vec<impl AsRef<[u8]>>,
+    ) -> &mut Transaction<'a> {
+        self.pipeline.binary_cmd(command, args);
+        self
+    }
+
+    /// Invokes the requested command immediately (not as part of the queued transaction commands) and returns
+    /// the operation response.<br>
+    /// This is usually used to read the current values of the watched keys.<br>
+    /// The command is invoked on the transaction connection and in case of a connection error, the transaction fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The Redis command, for example: `GET`
+    /// * `args` - Vector of arguments for the given command
+    pub fn run_command<T: redis::FromRedisValue>(
+        &mut self,
+        command: &str,
+        args: Vec<&str>,
+    ) -> RedisResult<T> {
+        self.pipeline.client.run_command_pinned(command, args)
+    }
+}
+
+queue_commands!(Transaction);
+
+fn unwatch(client: &mut Client, keys: &Vec<&str>) {
+    if !keys.is_empty() {
+        // failure to unwatch only impacts the next transaction on a broken connection, which will fail anyway
+        let _: RedisEmptyResult = client.run_command_pinned("UNWATCH", Vec::<&str>::new());
+    }
+}
+
+/// Runs the provided function as an atomic transaction, invoking it again in case any of the watched keys was
+/// modified before the transaction was executed (up to MAX_ATTEMPTS times).
+pub(crate) fn run<T, F>(client: &mut Client, keys: Vec<&str>, mut func: F) -> RedisResult<T>
+where
+    T: redis::FromRedisValue,
+    F: FnMut(&mut Transaction) -> RedisEmptyResult,
+{
+    // the only point in which a new connection may be created
+    if let Err(error) = client.open_verified_connection() {
+        return Err(error);
+    }
+
+    for _ in 0..MAX_ATTEMPTS {
+        if !keys.is_empty() {
+            if let Err(error) = client.run_command_pinned::<()>("WATCH", keys.clone()) {
+                return Err(error);
+            }
+        }
+
+        let (packed, count) = {
+            let mut transaction = Transaction {
+                pipeline: pipeline::create(client),
+            };
+
+            match func(&mut transaction) {
+                Ok(_) => transaction.pipeline.pack(true),
+                Err(error) => {
+                    unwatch(transaction.pipeline.client, &keys);
+                    return Err(error);
+                }
+            }
+        };
+
+        let mut results = client.run_packed_commands(&packed, count, false);
+
+        // the last response is the EXEC response, all others are MULTI and QUEUED responses
+        let exec_result = match results.pop() {
+            Some(result) => result,
+            None => Err(RedisError {
+                info: ErrorInfo::Description(types::MISSING_TRANSACTION_RESPONSE),
+            }),
+        };
+
+        for result in results {
+            if let Err(error) = result {
+                return Err(error);
+            }
+        }
+
+        match exec_result {
+            // one of the watched keys was modified
+            Ok(redis::Value::Nil) => continue,
+            Ok(value) => {
+                return match redis::from_redis_value(&value) {
+                    Ok(output) => Ok(output),
+                    Err(error) => Err(RedisError {
+                        info: ErrorInfo::RedisError(error),
+                    }),
+                };
+            }
+            Err(error) => return Err(error),
+        }
+    }
+
+    Err(RedisError {
+        info: ErrorInfo::Description(
+            "Transaction aborted as the watched keys kept being modified.",
+        ),
+    })
+}
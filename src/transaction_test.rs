@@ -0,0 +1,134 @@
+use super::*;
+use client;
+use std::io::Write;
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use types::ErrorKind;
+
+/// Starts a fake redis server which aborts every transaction (as if the watched keys were modified) and returns
+/// its port.
+fn start_conflicting_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    thread::spawn(move || {
+        let mut stream: TcpStream = listener.incoming().next().unwrap().unwrap();
+
+        loop {
+            let request = match redis::Parser::new(&mut stream).parse_value() {
+                Ok(request) => request,
+                Err(_) => break,
+            };
+            let args: Vec<String> = redis::from_redis_value(&request).unwrap();
+
+            let response = match args[0].as_str() {
+                "EXEC" => "*-1\r\n",
+                "MULTI" | "WATCH" | "UNWATCH" | "PING" => "+OK\r\n",
+                _ => "+QUEUED\r\n",
+            };
+            if stream.write_all(response.as_bytes()).is_err() {
+                break;
+            }
+        }
+    });
+
+    port
+}
+
+/// Starts a fake redis server which fails one of the commands of every transaction, followed by the response of
+/// the other command, and returns its port.<br>
+/// Every connection is served by its own thread, so clients can reconnect.
+fn start_nested_error_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream: TcpStream = stream.unwrap();
+
+            thread::spawn(move || loop {
+                let request = match redis::Parser::new(&mut stream).parse_value() {
+                    Ok(request) => request,
+                    Err(_) => break,
+                };
+                let args: Vec<String> = redis::from_redis_value(&request).unwrap();
+
+                let response = match args[0].as_str() {
+                    "EXEC" => "*2\r\n-WRONGTYPE Operation against a key\r\n$5\r\nstale\r\n",
+                    "GET" => "$5\r\nfresh\r\n",
+                    "MULTI" | "WATCH" | "UNWATCH" | "PING" => "+OK\r\n",
+                    _ => "+QUEUED\r\n",
+                };
+                if stream.write_all(response.as_bytes()).is_err() {
+                    break;
+                }
+            });
+        }
+    });
+
+    port
+}
+
+#[test]
+fn run_no_commands() {
+    let mut client = client::create("redis://127.0.0.1:6379/").unwrap();
+
+    let output: Vec<String> = run(&mut client, vec![], |_| Ok(())).unwrap();
+    assert!(output.is_empty());
+}
+
+#[test]
+fn run_function_error() {
+    let mut client = client::create("redis://127.0.0.1:6379/").unwrap();
+
+    let result: RedisResult<()> = run(&mut client, vec!["transaction_function_error"], |_| {
+        Err(RedisError {
+            info: ErrorInfo::Description("test"),
+        })
+    });
+    assert!(result.is_err());
+    assert!(client.is_connection_open());
+}
+
+#[test]
+fn run_max_attempts() {
+    let port = start_conflicting_server();
+    let mut client = client::create(&format!("redis://127.0.0.1:{}/", port)).unwrap();
+
+    let mut attempts = 0;
+    let result: RedisResult<()> = run(
+        &mut client,
+        vec!["transaction_max_attempts"],
+        |transaction| {
+            attempts = attempts + 1;
+            transaction.incr("transaction_max_attempts");
+
+            Ok(())
+        },
+    );
+
+    assert!(result.is_err());
+    assert_eq!(attempts, MAX_ATTEMPTS);
+}
+
+#[test]
+fn run_nested_error_closes_connection() {
+    let port = start_nested_error_server();
+    let mut client = client::create(&format!("redis://127.0.0.1:{}/", port)).unwrap();
+
+    let result: RedisResult<redis::Value> = run(&mut client, vec![], |transaction| {
+        transaction.incr("transaction_nested_error");
+        transaction.lpush("transaction_nested_error_list", "value");
+
+        Ok(())
+    });
+
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::WrongType);
+    assert!(!client.is_connection_created());
+
+    // the rest of the EXEC response must not be read by the next command
+    let value: String = client
+        .run_command("GET", vec!["transaction_nested_error"])
+        .unwrap();
+    assert_eq!(value, "fresh");
+}
@@ -11,6 +11,8 @@ use redis;
 use std::error;
 use std::fmt;
 use std::fmt::Display;
+use std::str;
+use std::time::Duration;
 
 #[derive(Debug)]
 /// Holds the error information
@@ -23,6 +25,133 @@ pub enum ErrorInfo {
     TimeoutError(&'static str),
 }
 
+/// Defines the kind of the error, enabling to handle errors without inspecting the error description
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorKind {
+    /// Connection IO error (for example connection refused or reset)
+    Io,
+    /// The operation timed out
+    Timeout,
+    /// Authentication failure
+    Auth,
+    /// Operation against a key holding the wrong kind of value (WRONGTYPE)
+    WrongType,
+    /// Unable to parse the redis response into the requested type (including nil responses parsed into a non
+    /// optional type)
+    Parse,
+    /// The requested value does not exist (for example a missing hash)
+    Nil,
+    /// Write operation against a read only replica (READONLY)
+    ReadOnly,
+    /// Redis is busy running a script or loading the dataset (BUSY or LOADING)
+    Busy,
+    /// The key is served by another cluster node (MOVED)
+    Moved,
+    /// The key is being migrated to another cluster node (ASK)
+    Ask,
+    /// The keys of a cluster command do not hash to the same slot (CROSSSLOT)
+    CrossSlot,
+    /// The requested script does not exist (NOSCRIPT)
+    NoScript,
+    /// The transaction was aborted due to previous errors (EXECABORT)
+    ExecAbort,
+    /// Invalid client configuration (for example invalid connection string)
+    ClientConfig,
+    /// Any other error returned by the redis server, holding the original error code (for example ERR)
+    ServerError(String),
+    /// Any other error
+    Other,
+}
+
+/// The description of the errors raised when the connection is not usable
+pub(crate) const CONNECTION_NOT_AVAILABLE: &'static str = "Redis connection not available.";
+pub(crate) const READ_TIMEOUT_NOT_SET: &'static str = "Unable to set read timeout.";
+pub(crate) const PUBSUB_NOT_AVAILABLE: &'static str = "Error while fetching pubsub.";
+pub(crate) const PIPELINE_ABORTED: &'static str =
+    "Pipeline aborted due to a previous connection error.";
+pub(crate) const SCAN_CONNECTION_RESET: &'static str =
+    "Connection was reset while scanning, scan must be restarted.";
+/// The description of the errors raised when the response can't be parsed
+pub(crate) const PARSE_FAILED: &'static str = "Unable to parse output value.";
+pub(crate) const MISSING_TRANSACTION_RESPONSE: &'static str = "Missing transaction response.";
+/// The description of the error raised when the requested hash does not exist
+pub(crate) const HASH_NOT_FOUND: &'static str = "Hash does not exist.";
+/// The description of the error raised when the command keys belong to different cluster slots
+pub(crate) const CROSS_SLOT: &'static str = "Keys in request don't hash to the same slot.";
+
+/// Returns the kind of the errors raised by this library (as opposed to errors returned by redis).
+fn get_description_error_kind(description: &str) -> ErrorKind {
+    match description {
+        CONNECTION_NOT_AVAILABLE
+        | READ_TIMEOUT_NOT_SET
+        | PUBSUB_NOT_AVAILABLE
+        | PIPELINE_ABORTED
+        | SCAN_CONNECTION_RESET => ErrorKind::Io,
+        PARSE_FAILED | MISSING_TRANSACTION_RESPONSE => ErrorKind::Parse,
+        HASH_NOT_FOUND => ErrorKind::Nil,
+        CROSS_SLOT => ErrorKind::CrossSlot,
+        _ => ErrorKind::Other,
+    }
+}
+
+/// Returns the kind of the redis error.<br>
+/// Redis-rs does not expose the server error code for the errors it maps to its own error kinds, so all
+/// generic server errors (ERR) and protocol errors are classified as ERR server errors.
+/// Returns true if the response ended before it was entirely read (for example when the server closed the
+/// connection), which redis-rs 0.8 reports as a response error instead of an IO error.
+pub(crate) fn is_short_read(cause: &redis::RedisError) -> bool {
+    cause.kind() == redis::ErrorKind::ResponseError
+        && cause.to_string() == "Could not read enough bytes"
+}
+
+fn get_redis_error_kind(cause: &redis::RedisError) -> ErrorKind {
+    match cause.kind() {
+        redis::ErrorKind::IoError => {
+            if cause.is_timeout() {
+                ErrorKind::Timeout
+            } else {
+                ErrorKind::Io
+            }
+        }
+        redis::ErrorKind::AuthenticationFailed => ErrorKind::Auth,
+        redis::ErrorKind::TypeError => ErrorKind::Parse,
+        redis::ErrorKind::ExecAbortError => ErrorKind::ExecAbort,
+        redis::ErrorKind::BusyLoadingError => ErrorKind::Busy,
+        redis::ErrorKind::NoScriptError => ErrorKind::NoScript,
+        redis::ErrorKind::InvalidClientConfig => ErrorKind::ClientConfig,
+        redis::ErrorKind::ResponseError => {
+            if is_short_read(cause) {
+                ErrorKind::Io
+            } else {
+                ErrorKind::ServerError("ERR".to_string())
+            }
+        }
+        redis::ErrorKind::ExtensionError => match cause.extension_error_code() {
+            Some("WRONGTYPE") => ErrorKind::WrongType,
+            Some("READONLY") => ErrorKind::ReadOnly,
+            Some("BUSY") => ErrorKind::Busy,
+            Some("MOVED") => ErrorKind::Moved,
+            Some("ASK") => ErrorKind::Ask,
+            Some("CROSSSLOT") => ErrorKind::CrossSlot,
+            Some("NOAUTH") | Some("WRONGPASS") | Some("NOPERM") => ErrorKind::Auth,
+            Some(code) => ErrorKind::ServerError(code.to_string()),
+            None => ErrorKind::Other,
+        },
+    }
+}
+
+/// Returns the detail of a redis extension error (for example `3999 127.0.0.1:6381` of a MOVED error).<br>
+/// Redis-rs 0.8 does not expose the error detail, so it is extracted from the error text, which is formatted as
+/// `<code>: <detail>` for extension errors.
+pub(crate) fn get_extension_error_detail(cause: &redis::RedisError) -> Option<String> {
+    let code = cause.extension_error_code()?;
+    let text = cause.to_string();
+
+    text.strip_prefix(code)?
+        .strip_prefix(": ")
+        .map(|detail| detail.to_string())
+}
+
 #[derive(Debug)]
 /// Redis Error struct
 pub struct RedisError {
@@ -60,9 +189,63 @@ impl Display for RedisError {
     }
 }
 
+impl RedisError {
+    /// Returns the error kind.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    /// use simple_redis::types::ErrorKind;
+    ///
+    /// client.set("my_text_key", "text").unwrap();
+    ///
+    /// match client.lpush("my_text_key", "value") {
+    ///     Err(ref error) if error.kind() == ErrorKind::WrongType => println!("Key is not a list"),
+    ///     Err(error) => println!("Unable to push value: {}", error),
+    ///     _ => println!("Value pushed"),
+    /// }
+    /// ```
+    pub fn kind(self: &RedisError) -> ErrorKind {
+        match self.info {
+            ErrorInfo::RedisError(ref cause) => get_redis_error_kind(cause),
+            ErrorInfo::Description(description) => get_description_error_kind(description),
+            ErrorInfo::TimeoutError(_) => ErrorKind::Timeout,
+        }
+    }
+
+    /// Returns true if the error is caused by a connection issue (IO error or timeout).
+    pub fn is_connection_error(self: &RedisError) -> bool {
+        match self.kind() {
+            ErrorKind::Io | ErrorKind::Timeout => true,
+            _ => false,
+        }
+    }
+
+    /// Returns true if the operation may succeed in case it is invoked again (for example after a connection
+    /// error, or while redis is busy or in the middle of a failover).
+    pub fn is_retryable(self: &RedisError) -> bool {
+        match self.kind() {
+            ErrorKind::Io | ErrorKind::Timeout | ErrorKind::Busy | ErrorKind::ReadOnly => true,
+            ErrorKind::ServerError(ref code) => match code.as_str() {
+                "TRYAGAIN" | "CLUSTERDOWN" | "MASTERDOWN" => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
 /// Defines a redis command argument
 pub trait RedisArg: Sized + ToString {}
 
+/// Defines a binary safe redis command argument.<br>
+/// All RedisArg types are sent using their string representation, while bytes are sent as is.
+pub trait RedisBinaryArg {
+    /// Returns the argument value as sent to redis
+    fn to_bytes(self: &Self) -> Vec<u8>;
+}
+
 macro_rules! as_redis_arg {
     ($t:ty) => {
         impl RedisArg for $t {}
@@ -71,6 +254,25 @@ macro_rules! as_redis_arg {
 
 impl<'a> RedisArg for &'a str {}
 
+impl<T: RedisArg> RedisBinaryArg for T {
+    fn to_bytes(self: &T) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+impl<'a> RedisBinaryArg for &'a [u8] {
+    fn to_bytes(self: &&'a [u8]) -> Vec<u8> {
+        self.to_vec()
+    }
+}
+
+impl RedisBinaryArg for Vec<u8> {
+    fn to_bytes(self: &Vec<u8>) -> Vec<u8> {
+        self.clone()
+    }
+}
+
+as_redis_arg!(String);
 as_redis_arg!(i8);
 as_redis_arg!(i16);
 as_redis_arg!(u16);
@@ -84,8 +286,597 @@ as_redis_arg!(isize);
 as_redis_arg!(usize);
 as_redis_arg!(bool);
 
-/// PubSub message
-pub type Message = redis::Msg;
+/// Defines a sorted set score range bound
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScoreBound {
+    /// The score is included in the range
+    Inclusive(f64),
+    /// The score is excluded from the range
+    Exclusive(f64),
+    /// Negative infinity (lowest possible score)
+    NegInfinity,
+    /// Positive infinity (highest possible score)
+    PosInfinity,
+}
+
+impl ScoreBound {
+    /// Returns the redis argument representing this bound
+    pub(crate) fn to_arg(self: &ScoreBound) -> String {
+        match *self {
+            ScoreBound::Inclusive(score) => score.to_string(),
+            ScoreBound::Exclusive(score) => format!("({}", score),
+            ScoreBound::NegInfinity => "-inf".to_string(),
+            ScoreBound::PosInfinity => "+inf".to_string(),
+        }
+    }
+}
+
+/// Defines a sorted set lexicographical range bound
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LexBound<'a> {
+    /// The member is included in the range
+    Inclusive(&'a str),
+    /// The member is excluded from the range
+    Exclusive(&'a str),
+    /// The lowest possible member
+    Min,
+    /// The highest possible member
+    Max,
+}
+
+impl<'a> LexBound<'a> {
+    /// Returns the redis argument representing this bound
+    pub(crate) fn to_arg(self: &LexBound<'a>) -> String {
+        match *self {
+            LexBound::Inclusive(member) => format!("[{}", member),
+            LexBound::Exclusive(member) => format!("({}", member),
+            LexBound::Min => "-".to_string(),
+            LexBound::Max => "+".to_string(),
+        }
+    }
+}
+
+/// Defines the key expiration set by the [SET](https://redis.io/commands/set) command
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetExpiration {
+    /// Expire after the provided amount of seconds (EX)
+    Seconds(usize),
+    /// Expire after the provided amount of milliseconds (PX)
+    Millis(usize),
+    /// Expire at the provided unix time in seconds (EXAT)
+    UnixTimeSeconds(u64),
+    /// Expire at the provided unix time in milliseconds (PXAT)
+    UnixTimeMillis(u64),
+    /// Retain the current expiration of the key (KEEPTTL)
+    KeepTtl,
+}
+
+impl SetExpiration {
+    /// Returns the redis arguments representing this expiration
+    pub(crate) fn to_args(self: &SetExpiration) -> Vec<String> {
+        match *self {
+            SetExpiration::Seconds(seconds) => vec!["EX".to_string(), seconds.to_string()],
+            SetExpiration::Millis(millis) => vec!["PX".to_string(), millis.to_string()],
+            SetExpiration::UnixTimeSeconds(time) => vec!["EXAT".to_string(), time.to_string()],
+            SetExpiration::UnixTimeMillis(time) => vec!["PXAT".to_string(), time.to_string()],
+            SetExpiration::KeepTtl => vec!["KEEPTTL".to_string()],
+        }
+    }
+}
+
+/// Defines the options of the [SET](https://redis.io/commands/set) command
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SetOptions {
+    /// The key expiration, by default any existing expiration is removed
+    pub expiration: Option<SetExpiration>,
+    /// Only set the key if it does not already exist (NX)
+    pub nx: bool,
+    /// Only set the key if it already exists (XX)
+    pub xx: bool,
+    /// Return the previous value stored at the key (GET)
+    pub get: bool,
+}
+
+impl SetOptions {
+    /// Returns the redis arguments representing these options
+    pub(crate) fn to_args(self: &SetOptions) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(ref expiration) = self.expiration {
+            args.extend(expiration.to_args());
+        }
+        if self.nx {
+            args.push("NX".to_string());
+        }
+        if self.xx {
+            args.push("XX".to_string());
+        }
+        if self.get {
+            args.push("GET".to_string());
+        }
+
+        args
+    }
+}
+
+/// Holds the [SET](https://redis.io/commands/set) command output
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetResult {
+    /// True in case the value was written, false in case the NX/XX condition was not met
+    pub written: bool,
+    /// The previous value stored at the key (as is, as it may be binary), only returned if requested by the GET
+    /// option
+    pub previous: Option<Vec<u8>>,
+}
+
+/// Defines the condition under which an expiration is set (for example in the
+/// [HEXPIRE](https://redis.io/commands/hexpire) command)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExpireCondition {
+    /// Only set the expiration if there is no existing expiration (NX)
+    Nx,
+    /// Only set the expiration if there is an existing expiration (XX)
+    Xx,
+    /// Only set the expiration if it is greater than the existing expiration (GT)
+    Gt,
+    /// Only set the expiration if it is less than the existing expiration (LT)
+    Lt,
+}
+
+impl ExpireCondition {
+    /// Returns the redis argument representing this condition
+    pub(crate) fn to_arg(self: &ExpireCondition) -> &'static str {
+        match *self {
+            ExpireCondition::Nx => "NX",
+            ExpireCondition::Xx => "XX",
+            ExpireCondition::Gt => "GT",
+            ExpireCondition::Lt => "LT",
+        }
+    }
+}
+
+/// Holds the per field result of the hash field expiration commands (HEXPIRE, HPEXPIRE)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HashFieldExpireStatus {
+    /// The key or field does not exist
+    NoField,
+    /// The expiration was not set as the condition was not met
+    ConditionNotMet,
+    /// The expiration was set
+    Set,
+    /// The field was deleted as the expiration is in the past
+    Deleted,
+}
+
+impl HashFieldExpireStatus {
+    /// Returns the status represented by the provided redis reply
+    pub(crate) fn from_reply(value: i64) -> Option<HashFieldExpireStatus> {
+        match value {
+            -2 => Some(HashFieldExpireStatus::NoField),
+            0 => Some(HashFieldExpireStatus::ConditionNotMet),
+            1 => Some(HashFieldExpireStatus::Set),
+            2 => Some(HashFieldExpireStatus::Deleted),
+            _ => None,
+        }
+    }
+}
+
+/// Holds the per field result of the hash field time to live commands (HTTL, HPTTL)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HashFieldTtl {
+    /// The key or field does not exist
+    NoField,
+    /// The field exists but has no expiration
+    NoExpiration,
+    /// The remaining time to live (in seconds for HTTL and milliseconds for HPTTL)
+    Expires(u64),
+}
+
+impl HashFieldTtl {
+    /// Returns the time to live represented by the provided redis reply
+    pub(crate) fn from_reply(value: i64) -> Option<HashFieldTtl> {
+        match value {
+            -2 => Some(HashFieldTtl::NoField),
+            -1 => Some(HashFieldTtl::NoExpiration),
+            ttl if ttl >= 0 => Some(HashFieldTtl::Expires(ttl as u64)),
+            _ => None,
+        }
+    }
+}
+
+/// Holds the per field result of the [HPERSIST](https://redis.io/commands/hpersist) command
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HashFieldPersistStatus {
+    /// The key or field does not exist
+    NoField,
+    /// The field exists but has no expiration
+    NoExpiration,
+    /// The expiration was removed
+    Persisted,
+}
+
+impl HashFieldPersistStatus {
+    /// Returns the status represented by the provided redis reply
+    pub(crate) fn from_reply(value: i64) -> Option<HashFieldPersistStatus> {
+        match value {
+            -2 => Some(HashFieldPersistStatus::NoField),
+            -1 => Some(HashFieldPersistStatus::NoExpiration),
+            1 => Some(HashFieldPersistStatus::Persisted),
+            _ => None,
+        }
+    }
+}
+
+/// Defines the list side to pop from or push to (for example in the [BLMOVE](https://redis.io/commands/blmove) command)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ListDirection {
+    /// The list head
+    Left,
+    /// The list tail
+    Right,
+}
+
+impl ListDirection {
+    /// Returns the redis argument representing this direction
+    pub(crate) fn to_arg(self: &ListDirection) -> &'static str {
+        match *self {
+            ListDirection::Left => "LEFT",
+            ListDirection::Right => "RIGHT",
+        }
+    }
+}
+
+/// Defines the options of the sorted set [ZADD](https://redis.io/commands/zadd) command
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ZAddOptions {
+    /// Only add new members, never update existing members (NX)
+    pub nx: bool,
+    /// Only update existing members, never add new members (XX)
+    pub xx: bool,
+    /// Only update existing members if the new score is greater than the current score (GT)
+    pub gt: bool,
+    /// Only update existing members if the new score is less than the current score (LT)
+    pub lt: bool,
+    /// Count changed members and not only new members (CH)
+    pub ch: bool,
+}
+
+impl ZAddOptions {
+    /// Returns the redis arguments representing these options
+    pub(crate) fn to_args(self: &ZAddOptions) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.nx {
+            args.push("NX".to_string());
+        }
+        if self.xx {
+            args.push("XX".to_string());
+        }
+        if self.gt {
+            args.push("GT".to_string());
+        }
+        if self.lt {
+            args.push("LT".to_string());
+        }
+        if self.ch {
+            args.push("CH".to_string());
+        }
+
+        args
+    }
+}
+
+/// Defines the options of the SCAN family commands (see redis [SCAN](https://redis.io/commands/scan) command)
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Only return elements matching the provided glob style pattern (MATCH)
+    pub pattern: Option<String>,
+    /// Hint for the amount of elements to return on each iteration (COUNT)
+    pub count: Option<usize>,
+    /// Only return keys of the provided type (TYPE), only used by the SCAN command
+    pub key_type: Option<String>,
+}
+
+/// Defines how a stream is trimmed (see redis [XTRIM](https://redis.io/commands/xtrim) command)
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamTrim {
+    /// Evicts entries as long as the stream length exceeds the provided length (MAXLEN)
+    MaxLen(usize),
+    /// Same as MaxLen, but enables redis to keep a few more entries for better performance (MAXLEN ~)
+    ApproxMaxLen(usize),
+    /// Evicts entries with IDs lower than the provided ID (MINID)
+    MinId(String),
+    /// Same as MinId, but enables redis to keep a few more entries for better performance (MINID ~)
+    ApproxMinId(String),
+}
+
+impl StreamTrim {
+    /// Returns the redis arguments representing this trim strategy
+    pub(crate) fn to_args(self: &StreamTrim) -> Vec<String> {
+        let (strategy, approximate, threshold) = match *self {
+            StreamTrim::MaxLen(length) => ("MAXLEN", false, length.to_string()),
+            StreamTrim::ApproxMaxLen(length) => ("MAXLEN", true, length.to_string()),
+            StreamTrim::MinId(ref id) => ("MINID", false, id.to_string()),
+            StreamTrim::ApproxMinId(ref id) => ("MINID", true, id.to_string()),
+        };
+
+        let mut args = vec![strategy.to_string()];
+        if approximate {
+            args.push("~".to_string());
+        }
+        args.push(threshold);
+
+        args
+    }
+}
+
+/// Defines the options of the stream read commands (see redis [XREAD](https://redis.io/commands/xread) and
+/// [XREADGROUP](https://redis.io/commands/xreadgroup) commands)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StreamReadOptions {
+    /// The max amount of entries to return per stream (COUNT)
+    pub count: Option<usize>,
+    /// If set, waits up to the provided timeout for new entries, zero to wait forever (BLOCK)
+    pub block: Option<Duration>,
+    /// Consumer groups only, the read entries are not added to the pending entries list (NOACK)
+    pub noack: bool,
+}
+
+impl StreamReadOptions {
+    /// Returns the redis arguments representing these options (excluding NOACK)
+    pub(crate) fn to_args(self: &StreamReadOptions) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(count) = self.count {
+            args.push("COUNT".to_string());
+            args.push(count.to_string());
+        }
+        if let Some(block) = self.block {
+            // redis blocks in milliseconds, so sub millisecond timeouts are rounded up as zero blocks forever
+            let millis = block.as_millis();
+            let millis = if millis == 0 && block > Duration::from_millis(0) {
+                1
+            } else {
+                millis
+            };
+
+            args.push("BLOCK".to_string());
+            args.push(millis.to_string());
+        }
+
+        args
+    }
+}
+
+/// Holds a single stream entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamEntry {
+    /// The entry ID
+    pub id: String,
+    /// The entry fields and values (in the order they were added, empty for deleted pending entries)
+    pub fields: Vec<(String, String)>,
+}
+
+/// Holds the summary of a consumer group pending entries
+/// (see redis [XPENDING](https://redis.io/commands/xpending) command)
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamPendingSummary {
+    /// The amount of pending entries
+    pub count: usize,
+    /// The lowest pending entry ID
+    pub min_id: Option<String>,
+    /// The highest pending entry ID
+    pub max_id: Option<String>,
+    /// The amount of pending entries per consumer
+    pub consumers: Vec<(String, usize)>,
+}
+
+/// Holds a single pending entry of a consumer group (see redis [XPENDING](https://redis.io/commands/xpending) command)
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamPendingEntry {
+    /// The entry ID
+    pub id: String,
+    /// The consumer which owns the entry
+    pub consumer: String,
+    /// The amount of millies passed since the entry was last delivered
+    pub idle: u64,
+    /// The amount of times the entry was delivered
+    pub deliveries: usize,
+}
+
+/// Holds the output of the redis [XAUTOCLAIM](https://redis.io/commands/xautoclaim) command
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamAutoClaimResult {
+    /// The ID to use as the start of the next XAUTOCLAIM invocation ("0-0" once done)
+    pub next_id: String,
+    /// The claimed entries
+    pub entries: Vec<StreamEntry>,
+    /// The IDs of the claimed entries which no longer exist in the stream (redis 7 and above)
+    pub deleted_ids: Vec<String>,
+}
+
+/// Defines how the restored function libraries are merged with the existing libraries
+/// (see redis [FUNCTION RESTORE](https://redis.io/commands/function-restore) command)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FunctionRestorePolicy {
+    /// Append the restored libraries and fail in case of a library name collision (APPEND)
+    Append,
+    /// Append the restored libraries and replace existing libraries in case of a name collision (REPLACE)
+    Replace,
+    /// Delete all existing libraries before restoring (FLUSH)
+    Flush,
+}
+
+impl FunctionRestorePolicy {
+    /// Returns the redis argument representing this policy
+    pub(crate) fn to_arg(self: &FunctionRestorePolicy) -> &'static str {
+        match *self {
+            FunctionRestorePolicy::Append => "APPEND",
+            FunctionRestorePolicy::Replace => "REPLACE",
+            FunctionRestorePolicy::Flush => "FLUSH",
+        }
+    }
+}
+
+/// Holds a function as returned by the redis [FUNCTION LIST](https://redis.io/commands/function-list) command
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionInfo {
+    /// The function name
+    pub name: String,
+    /// The function description (if defined)
+    pub description: Option<String>,
+    /// The function flags (for example no-writes)
+    pub flags: Vec<String>,
+}
+
+/// Holds a function library as returned by the redis [FUNCTION LIST](https://redis.io/commands/function-list)
+/// command
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionLibrary {
+    /// The library name
+    pub name: String,
+    /// The library engine (for example LUA)
+    pub engine: String,
+    /// The library functions
+    pub functions: Vec<FunctionInfo>,
+    /// The library source code (only returned if requested)
+    pub code: Option<String>,
+}
+
+/// Defines how failed commands are retried (including reconnecting to redis in case of connection errors).<br>
+/// The default policy does not retry failed commands, so retries must be enabled by setting max_attempts.<br>
+/// Commands which return a count of modified items (for example SADD, SREM, DEL or HDEL) are retried, as their
+/// effect is the same when replayed, however in case the first invocation was executed by redis, the count
+/// returned by the retry may be lower than the actual amount of modified items.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// The maximal amount of times a command is invoked (1 means no retries)
+    pub max_attempts: u32,
+    /// The delay before the first retry, doubled for every following retry
+    pub initial_backoff: Duration,
+    /// The maximal delay between retries
+    pub max_backoff: Duration,
+    /// If true, a random part of the delay is removed to prevent all clients from retrying at the same time
+    pub jitter: bool,
+    /// The error kinds to retry (None to retry all errors for which RedisError::is_retryable returns true)
+    pub retryable_kinds: Option<Vec<ErrorKind>>,
+    /// If true, commands which are not idempotent (for example INCR or LPUSH) are also retried in case they
+    /// might have already been executed by redis
+    pub retry_non_idempotent: bool,
+}
+
+impl RetryPolicy {
+    /// Returns a policy which never retries failed commands (same as the default policy)
+    pub fn none() -> RetryPolicy {
+        RetryPolicy::default()
+    }
+
+    /// Returns true if the provided error kind should be retried
+    pub(crate) fn is_retryable(self: &RetryPolicy, error: &RedisError) -> bool {
+        match self.retryable_kinds {
+            Some(ref kinds) => kinds.contains(&error.kind()),
+            None => error.is_retryable(),
+        }
+    }
+
+    /// Returns the delay before the provided retry attempt (starting from 1), without jitter
+    pub(crate) fn get_backoff(self: &RetryPolicy, retry: u32) -> Duration {
+        let mut backoff = self.initial_backoff;
+
+        for _ in 1..retry {
+            if backoff >= self.max_backoff {
+                break;
+            }
+
+            backoff = backoff * 2;
+        }
+
+        if backoff > self.max_backoff {
+            self.max_backoff
+        } else {
+            backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 1,
+            initial_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_secs(1),
+            jitter: true,
+            retryable_kinds: None,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+/// PubSub message.<br>
+/// Provides the same accessors as the redis-rs `Msg` type which was previously exposed under this name, so code
+/// using the Message type and its functions is not affected, while code which names `redis::Msg` directly should
+/// use this type instead.
+#[derive(Debug, Clone)]
+pub struct Message {
+    channel: redis::Value,
+    payload: redis::Value,
+    pattern: Option<redis::Value>,
+}
+
+impl Message {
+    /// Creates a new message from the raw pubsub response values.
+    pub(crate) fn new(
+        channel: redis::Value,
+        payload: redis::Value,
+        pattern: Option<redis::Value>,
+    ) -> Message {
+        Message {
+            channel,
+            payload,
+            pattern,
+        }
+    }
+
+    /// Returns the channel the message was published to.
+    pub fn get_channel<T: redis::FromRedisValue>(self: &Message) -> redis::RedisResult<T> {
+        redis::from_redis_value(&self.channel)
+    }
+
+    /// Returns the channel name or `?` in case the channel name is not a valid string.
+    pub fn get_channel_name(self: &Message) -> &str {
+        match self.channel {
+            redis::Value::Data(ref bytes) => str::from_utf8(bytes).unwrap_or("?"),
+            _ => "?",
+        }
+    }
+
+    /// Returns the message payload converted to the requested type.
+    pub fn get_payload<T: redis::FromRedisValue>(self: &Message) -> redis::RedisResult<T> {
+        redis::from_redis_value(&self.payload)
+    }
+
+    /// Returns the raw message payload bytes.
+    pub fn get_payload_bytes(self: &Message) -> &[u8] {
+        match self.payload {
+            redis::Value::Data(ref bytes) => bytes,
+            _ => b"",
+        }
+    }
+
+    /// Returns true if the message was received due to a pattern subscription.
+    pub fn from_pattern(self: &Message) -> bool {
+        self.pattern.is_some()
+    }
+
+    /// Returns the pattern which matched the channel (converted from nil in case of a non pattern subscription,
+    /// so `Option<String>` can be requested).
+    pub fn get_pattern<T: redis::FromRedisValue>(self: &Message) -> redis::RedisResult<T> {
+        match self.pattern {
+            Some(ref pattern) => redis::from_redis_value(pattern),
+            None => redis::from_redis_value(&redis::Value::Nil),
+        }
+    }
+}
 
 /// Redis result which either holds a value or a Redis error
 pub type RedisResult<T> = Result<T, RedisError>;
@@ -29,3 +29,360 @@ fn redis_error_timeout_error() {
     write!(&mut writer, "formatted {}", redis_error).unwrap();
     assert_eq!(writer, b"formatted timeout");
 }
+
+#[test]
+fn redis_error_kind_library_errors() {
+    let redis_error = RedisError {
+        info: ErrorInfo::Description("test"),
+    };
+    assert_eq!(redis_error.kind(), ErrorKind::Other);
+    assert!(!redis_error.is_retryable());
+    assert!(!redis_error.is_connection_error());
+
+    let redis_error = RedisError {
+        info: ErrorInfo::TimeoutError("timeout"),
+    };
+    assert_eq!(redis_error.kind(), ErrorKind::Timeout);
+    assert!(redis_error.is_retryable());
+    assert!(redis_error.is_connection_error());
+
+    let redis_error = RedisError {
+        info: ErrorInfo::Description(CONNECTION_NOT_AVAILABLE),
+    };
+    assert_eq!(redis_error.kind(), ErrorKind::Io);
+    assert_eq!(redis_error.description(), "Redis connection not available.");
+    assert!(redis_error.is_retryable());
+    assert!(redis_error.is_connection_error());
+
+    let redis_error = RedisError {
+        info: ErrorInfo::Description(PARSE_FAILED),
+    };
+    assert_eq!(redis_error.kind(), ErrorKind::Parse);
+    assert!(!redis_error.is_retryable());
+
+    let redis_error = RedisError {
+        info: ErrorInfo::Description(HASH_NOT_FOUND),
+    };
+    assert_eq!(redis_error.kind(), ErrorKind::Nil);
+    assert!(!redis_error.is_retryable());
+
+    let redis_error = RedisError {
+        info: ErrorInfo::Description(CROSS_SLOT),
+    };
+    assert_eq!(redis_error.kind(), ErrorKind::CrossSlot);
+    assert_eq!(
+        redis_error.description(),
+        "Keys in request don't hash to the same slot."
+    );
+    assert!(!redis_error.is_retryable());
+}
+
+fn create_server_error(response: &[u8]) -> RedisError {
+    let cause = match redis::parse_redis_value(response) {
+        Err(error) => error,
+        _ => panic!("test error"),
+    };
+
+    RedisError {
+        info: ErrorInfo::RedisError(cause),
+    }
+}
+
+#[test]
+fn redis_error_kind_server_errors() {
+    let redis_error = create_server_error(b"-WRONGTYPE Operation against a key\r\n");
+    assert_eq!(redis_error.kind(), ErrorKind::WrongType);
+    assert!(!redis_error.is_retryable());
+
+    let redis_error = create_server_error(b"-READONLY You can't write\r\n");
+    assert_eq!(redis_error.kind(), ErrorKind::ReadOnly);
+    assert!(redis_error.is_retryable());
+    assert!(!redis_error.is_connection_error());
+
+    let redis_error = create_server_error(b"-MOVED 3999 127.0.0.1:6381\r\n");
+    assert_eq!(redis_error.kind(), ErrorKind::Moved);
+
+    let redis_error = create_server_error(b"-ASK 3999 127.0.0.1:6381\r\n");
+    assert_eq!(redis_error.kind(), ErrorKind::Ask);
+
+    let redis_error =
+        create_server_error(b"-CROSSSLOT Keys in request don't hash to the same slot\r\n");
+    assert_eq!(redis_error.kind(), ErrorKind::CrossSlot);
+
+    let redis_error = create_server_error(b"-NOAUTH Authentication required\r\n");
+    assert_eq!(redis_error.kind(), ErrorKind::Auth);
+
+    let redis_error = create_server_error(b"-NOSCRIPT No matching script\r\n");
+    assert_eq!(redis_error.kind(), ErrorKind::NoScript);
+
+    let redis_error = create_server_error(b"-LOADING Redis is loading\r\n");
+    assert_eq!(redis_error.kind(), ErrorKind::Busy);
+    assert!(redis_error.is_retryable());
+
+    let redis_error = create_server_error(b"-TRYAGAIN Multiple keys request\r\n");
+    assert_eq!(
+        redis_error.kind(),
+        ErrorKind::ServerError("TRYAGAIN".to_string())
+    );
+    assert!(redis_error.is_retryable());
+
+    let redis_error = create_server_error(b"-ERR unknown command\r\n");
+    assert_eq!(
+        redis_error.kind(),
+        ErrorKind::ServerError("ERR".to_string())
+    );
+    assert!(!redis_error.is_retryable());
+}
+
+#[test]
+fn redis_error_kind_short_read() {
+    let cause = match redis::parse_redis_value(b"$5\r\nval") {
+        Err(error) => error,
+        _ => panic!("test error"),
+    };
+    assert!(is_short_read(&cause));
+
+    let redis_error = RedisError {
+        info: ErrorInfo::RedisError(cause),
+    };
+    assert_eq!(redis_error.kind(), ErrorKind::Io);
+    assert!(redis_error.is_connection_error());
+
+    let cause = match redis::parse_redis_value(b"-ERR unknown command\r\n") {
+        Err(error) => error,
+        _ => panic!("test error"),
+    };
+    assert!(!is_short_read(&cause));
+}
+
+#[test]
+fn redis_error_kind_client_errors() {
+    let cause: redis::RedisError =
+        std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused").into();
+    let redis_error = RedisError {
+        info: ErrorInfo::RedisError(cause),
+    };
+    assert_eq!(redis_error.kind(), ErrorKind::Io);
+    assert!(redis_error.is_connection_error());
+
+    let cause: redis::RedisError =
+        std::io::Error::new(std::io::ErrorKind::TimedOut, "timeout").into();
+    let redis_error = RedisError {
+        info: ErrorInfo::RedisError(cause),
+    };
+    assert_eq!(redis_error.kind(), ErrorKind::Timeout);
+
+    let cause = redis::from_redis_value::<String>(&redis::Value::Nil).unwrap_err();
+    let redis_error = RedisError {
+        info: ErrorInfo::RedisError(cause),
+    };
+    assert_eq!(redis_error.kind(), ErrorKind::Parse);
+
+    let cause = redis::from_redis_value::<i64>(&redis::Value::Data(b"abc".to_vec())).unwrap_err();
+    let redis_error = RedisError {
+        info: ErrorInfo::RedisError(cause),
+    };
+    assert_eq!(redis_error.kind(), ErrorKind::Parse);
+
+    let redis_error = match redis::Client::open("invalid://127.0.0.1") {
+        Err(cause) => RedisError {
+            info: ErrorInfo::RedisError(cause),
+        },
+        _ => panic!("test error"),
+    };
+    assert_eq!(redis_error.kind(), ErrorKind::ClientConfig);
+}
+
+#[test]
+fn retry_policy_none() {
+    let retry_policy = RetryPolicy::none();
+    assert_eq!(retry_policy.max_attempts, 1);
+}
+
+#[test]
+fn retry_policy_default() {
+    let retry_policy = RetryPolicy::default();
+    assert_eq!(retry_policy.max_attempts, 1);
+}
+
+#[test]
+fn retry_policy_is_retryable() {
+    let timeout_error = RedisError {
+        info: ErrorInfo::TimeoutError("timeout"),
+    };
+    let parse_error = RedisError {
+        info: ErrorInfo::Description(PARSE_FAILED),
+    };
+
+    let mut retry_policy = RetryPolicy::default();
+    assert!(retry_policy.is_retryable(&timeout_error));
+    assert!(!retry_policy.is_retryable(&parse_error));
+
+    retry_policy.retryable_kinds = Some(vec![ErrorKind::Parse]);
+    assert!(!retry_policy.is_retryable(&timeout_error));
+    assert!(retry_policy.is_retryable(&parse_error));
+}
+
+#[test]
+fn retry_policy_get_backoff() {
+    let mut retry_policy = RetryPolicy::default();
+    retry_policy.initial_backoff = Duration::from_millis(10);
+    retry_policy.max_backoff = Duration::from_millis(35);
+
+    assert_eq!(retry_policy.get_backoff(1), Duration::from_millis(10));
+    assert_eq!(retry_policy.get_backoff(2), Duration::from_millis(20));
+    assert_eq!(retry_policy.get_backoff(3), Duration::from_millis(35));
+    assert_eq!(retry_policy.get_backoff(100), Duration::from_millis(35));
+}
+
+#[test]
+fn score_bound_to_arg() {
+    assert_eq!(ScoreBound::Inclusive(1.5).to_arg(), "1.5");
+    assert_eq!(ScoreBound::Exclusive(2f64).to_arg(), "(2");
+    assert_eq!(ScoreBound::NegInfinity.to_arg(), "-inf");
+    assert_eq!(ScoreBound::PosInfinity.to_arg(), "+inf");
+}
+
+#[test]
+fn lex_bound_to_arg() {
+    assert_eq!(LexBound::Inclusive("a").to_arg(), "[a");
+    assert_eq!(LexBound::Exclusive("b").to_arg(), "(b");
+    assert_eq!(LexBound::Min.to_arg(), "-");
+    assert_eq!(LexBound::Max.to_arg(), "+");
+}
+
+#[test]
+fn zadd_options_to_args() {
+    let mut options = ZAddOptions::default();
+    assert!(options.to_args().is_empty());
+
+    options.xx = true;
+    options.gt = true;
+    options.ch = true;
+    assert_eq!(options.to_args(), vec!["XX", "GT", "CH"]);
+}
+
+#[test]
+fn redis_arg_to_bytes() {
+    assert_eq!("text".to_bytes(), b"text".to_vec());
+    assert_eq!("text".to_string().to_bytes(), b"text".to_vec());
+    assert_eq!(15.to_bytes(), b"15".to_vec());
+    assert_eq!(1.5.to_bytes(), b"1.5".to_vec());
+    assert_eq!(true.to_bytes(), b"true".to_vec());
+
+    let binary: &[u8] = &[0, 255, 10];
+    assert_eq!(binary.to_bytes(), vec![0, 255, 10]);
+    assert_eq!(vec![0u8, 255, 10].to_bytes(), vec![0, 255, 10]);
+}
+
+#[test]
+fn stream_trim_to_args() {
+    assert_eq!(StreamTrim::MaxLen(10).to_args(), vec!["MAXLEN", "10"]);
+    assert_eq!(
+        StreamTrim::ApproxMaxLen(10).to_args(),
+        vec!["MAXLEN", "~", "10"]
+    );
+    assert_eq!(
+        StreamTrim::MinId("1-0".to_string()).to_args(),
+        vec!["MINID", "1-0"]
+    );
+    assert_eq!(
+        StreamTrim::ApproxMinId("1-0".to_string()).to_args(),
+        vec!["MINID", "~", "1-0"]
+    );
+}
+
+#[test]
+fn stream_read_options_to_args() {
+    assert!(StreamReadOptions::default().to_args().is_empty());
+
+    let options = StreamReadOptions {
+        count: Some(5),
+        block: Some(Duration::from_millis(100)),
+        noack: true,
+    };
+    assert_eq!(options.to_args(), vec!["COUNT", "5", "BLOCK", "100"]);
+
+    let options = StreamReadOptions {
+        count: None,
+        block: Some(Duration::from_secs(0)),
+        noack: false,
+    };
+    assert_eq!(options.to_args(), vec!["BLOCK", "0"]);
+
+    let options = StreamReadOptions {
+        count: None,
+        block: Some(Duration::from_micros(500)),
+        noack: false,
+    };
+    assert_eq!(options.to_args(), vec!["BLOCK", "1"]);
+}
+
+#[test]
+fn list_direction_to_arg() {
+    assert_eq!(ListDirection::Left.to_arg(), "LEFT");
+    assert_eq!(ListDirection::Right.to_arg(), "RIGHT");
+}
+
+#[test]
+fn set_options_to_args() {
+    assert!(SetOptions::default().to_args().is_empty());
+
+    let options = SetOptions {
+        expiration: Some(SetExpiration::Millis(500)),
+        nx: true,
+        xx: false,
+        get: true,
+    };
+    assert_eq!(options.to_args(), vec!["PX", "500", "NX", "GET"]);
+
+    assert_eq!(SetExpiration::Seconds(5).to_args(), vec!["EX", "5"]);
+    assert_eq!(
+        SetExpiration::UnixTimeSeconds(1000).to_args(),
+        vec!["EXAT", "1000"]
+    );
+    assert_eq!(
+        SetExpiration::UnixTimeMillis(1000).to_args(),
+        vec!["PXAT", "1000"]
+    );
+    assert_eq!(SetExpiration::KeepTtl.to_args(), vec!["KEEPTTL"]);
+}
+
+#[test]
+fn expire_condition_to_arg() {
+    assert_eq!(ExpireCondition::Nx.to_arg(), "NX");
+    assert_eq!(ExpireCondition::Xx.to_arg(), "XX");
+    assert_eq!(ExpireCondition::Gt.to_arg(), "GT");
+    assert_eq!(ExpireCondition::Lt.to_arg(), "LT");
+}
+
+#[test]
+fn hash_field_ttl_from_reply() {
+    assert_eq!(HashFieldTtl::from_reply(-2), Some(HashFieldTtl::NoField));
+    assert_eq!(
+        HashFieldTtl::from_reply(-1),
+        Some(HashFieldTtl::NoExpiration)
+    );
+    assert_eq!(
+        HashFieldTtl::from_reply(10),
+        Some(HashFieldTtl::Expires(10))
+    );
+    assert_eq!(HashFieldTtl::from_reply(-3), None);
+}
+
+#[test]
+fn hash_field_persist_status_from_reply() {
+    assert_eq!(
+        HashFieldPersistStatus::from_reply(-2),
+        Some(HashFieldPersistStatus::NoField)
+    );
+    assert_eq!(
+        HashFieldPersistStatus::from_reply(-1),
+        Some(HashFieldPersistStatus::NoExpiration)
+    );
+    assert_eq!(
+        HashFieldPersistStatus::from_reply(1),
+        Some(HashFieldPersistStatus::Persisted)
+    );
+    assert_eq!(HashFieldPersistStatus::from_reply(0), None);
+}
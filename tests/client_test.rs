@@ -202,3 +202,46 @@ fn quit_live_subscriptions() {
     let message_result = client.get_message(2500);
     assert!(message_result.is_err());
 }
+
+#[test]
+fn builder_run_command() {
+    let mut client = simple_redis::ClientBuilder::new("redis://127.0.0.1:6379/")
+        .connect_timeout(time::Duration::from_secs(1))
+        .read_timeout(time::Duration::from_secs(5))
+        .write_timeout(time::Duration::from_secs(5))
+        .client_name("simple_redis_test")
+        .database(1)
+        .build()
+        .unwrap();
+
+    let name = client
+        .run_command::<String>("CLIENT", vec!["GETNAME"])
+        .unwrap();
+    assert_eq!(name, "simple_redis_test");
+
+    client.set("builder_db_test", "db1").unwrap();
+
+    let mut default_client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    let value = default_client.get_opt::<String>("builder_db_test").unwrap();
+    assert!(value.is_none());
+
+    client.del("builder_db_test").unwrap();
+}
+
+#[test]
+fn with_timeout_commands() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client
+        .with_timeout(time::Duration::from_secs(5))
+        .set("with_timeout_test", "value")
+        .unwrap();
+
+    let value = client
+        .with_timeout(time::Duration::from_secs(5))
+        .get_string("with_timeout_test")
+        .unwrap();
+    assert_eq!(value, "value");
+
+    client.del("with_timeout_test").unwrap();
+}
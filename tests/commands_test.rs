@@ -1,5 +1,12 @@
+#[macro_use]
 extern crate simple_redis;
+use simple_redis::hash::{get_optional_field, RedisHash};
 use simple_redis::types::ErrorInfo::TimeoutError;
+use simple_redis::types::{
+    ExpireCondition, HashFieldExpireStatus, HashFieldPersistStatus, HashFieldTtl, LexBound,
+    ListDirection, RedisResult, ScanOptions, ScoreBound, SetExpiration, SetOptions, ZAddOptions,
+};
+use std::collections::HashMap;
 use std::{thread, time};
 
 #[test]
@@ -389,6 +396,69 @@ fn del_setnx() {
     }
 }
 
+#[test]
+fn set_with_options() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("set_with_options").unwrap();
+
+    let lock_options = SetOptions {
+        expiration: Some(SetExpiration::Millis(10000)),
+        nx: true,
+        ..SetOptions::default()
+    };
+    let mut result = client
+        .set_with("set_with_options", "owner1", lock_options)
+        .unwrap();
+    assert!(result.written);
+    assert!(result.previous.is_none());
+    assert!(
+        client
+            .run_command::<i64>("PTTL", vec!["set_with_options"])
+            .unwrap()
+            > 0
+    );
+
+    result = client
+        .set_with("set_with_options", "owner2", lock_options)
+        .unwrap();
+    assert!(!result.written);
+    assert_eq!(client.get_string("set_with_options").unwrap(), "owner1");
+
+    let mut options = SetOptions::default();
+    options.expiration = Some(SetExpiration::KeepTtl);
+    options.xx = true;
+    options.get = true;
+    result = client
+        .set_with("set_with_options", "owner3", options)
+        .unwrap();
+    assert!(result.written);
+    assert_eq!(result.previous, Some(b"owner1".to_vec()));
+    assert!(
+        client
+            .run_command::<i64>("PTTL", vec!["set_with_options"])
+            .unwrap()
+            > 0
+    );
+
+    client.del("set_with_options").unwrap();
+    result = client
+        .set_with("set_with_options", "owner4", options)
+        .unwrap();
+    assert!(!result.written);
+    assert!(result.previous.is_none());
+    assert!(!client.exists("set_with_options").unwrap());
+
+    let binary: &[u8] = &[0, 159, 255];
+    client.set("set_with_options", binary).unwrap();
+    options.expiration = None;
+    result = client
+        .set_with("set_with_options", "owner5", options)
+        .unwrap();
+    assert!(result.written);
+    assert_eq!(result.previous, Some(binary.to_vec()));
+}
+
 #[test]
 fn getset_i32() {
     let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
@@ -465,6 +535,48 @@ fn exists() {
     }
 }
 
+#[test]
+fn multi_key_strings() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    let keys = vec!["multi_key_1", "multi_key_2", "multi_key_3"];
+    client.del_keys(keys.clone()).unwrap();
+
+    client
+        .mset(vec![("multi_key_1", 1), ("multi_key_2", 2)])
+        .unwrap();
+    let values = client.mget::<i32>(keys.clone()).unwrap();
+    assert_eq!(values, vec![Some(1), Some(2), None]);
+
+    client.set("multi_key_1", &[255u8, 0][..]).unwrap();
+    assert!(client.mget::<String>(keys.clone()).is_err());
+    client.set("multi_key_1", 1).unwrap();
+
+    assert!(!client
+        .msetnx(vec![("multi_key_2", "2"), ("multi_key_3", "3")])
+        .unwrap());
+    assert!(!client.exists("multi_key_3").unwrap());
+    assert!(client.msetnx(vec![("multi_key_3", "3")]).unwrap());
+
+    assert_eq!(client.exists_keys(keys.clone()).unwrap(), 3);
+    assert_eq!(
+        client
+            .exists_keys(vec!["multi_key_1", "multi_key_1", "multi_key_missing"])
+            .unwrap(),
+        2
+    );
+    assert_eq!(client.touch(keys.clone()).unwrap(), 3);
+
+    assert_eq!(
+        client
+            .unlink(vec!["multi_key_1", "multi_key_missing"])
+            .unwrap(),
+        1
+    );
+    assert_eq!(client.del_keys(keys.clone()).unwrap(), 2);
+    assert_eq!(client.exists_keys(keys).unwrap(), 0);
+}
+
 #[test]
 fn expire() {
     let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
@@ -823,6 +935,276 @@ fn hvals() {
     assert!(result.contains(&String::from("test")));
 }
 
+#[test]
+fn hash_multi_fields() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("hash_multi_fields").unwrap();
+
+    let mut int_result = client
+        .hset_fields("hash_multi_fields", vec![("a", 1), ("b", 2), ("c", 3)])
+        .unwrap();
+    assert_eq!(int_result, 3);
+    int_result = client
+        .hset_fields("hash_multi_fields", vec![("c", 30), ("d", 4)])
+        .unwrap();
+    assert_eq!(int_result, 1);
+    assert_eq!(client.hlen("hash_multi_fields").unwrap(), 4);
+    assert_eq!(client.hstrlen("hash_multi_fields", "c").unwrap(), 2);
+    assert_eq!(client.hstrlen("hash_multi_fields", "missing").unwrap(), 0);
+
+    let values = client
+        .hmget::<i32>("hash_multi_fields", vec!["a", "missing", "c"])
+        .unwrap();
+    assert_eq!(values, vec![Some(1), None, Some(30)]);
+
+    assert_eq!(client.hincrby("hash_multi_fields", "a", 10).unwrap(), 11);
+    assert_eq!(
+        client.hincrbyfloat("hash_multi_fields", "b", 0.5).unwrap(),
+        2.5
+    );
+
+    assert!(client.hrandfield("hash_multi_fields").unwrap().is_some());
+    assert_eq!(
+        client
+            .hrandfield_count("hash_multi_fields", 2)
+            .unwrap()
+            .len(),
+        2
+    );
+    let pairs = client
+        .hrandfield_withvalues("hash_multi_fields", -6)
+        .unwrap();
+    assert_eq!(pairs.len(), 6);
+    assert!(pairs
+        .iter()
+        .all(|pair| client.hget_string("hash_multi_fields", &pair.0).unwrap() == pair.1));
+
+    int_result = client
+        .hdel_fields("hash_multi_fields", vec!["a", "b", "missing"])
+        .unwrap();
+    assert_eq!(int_result, 2);
+    assert_eq!(client.hlen("hash_multi_fields").unwrap(), 2);
+    // values which can't be converted fail the call instead of shifting the rest of the values
+    client
+        .hset("hash_multi_fields", "binary", &[255u8, 0][..])
+        .unwrap();
+    assert!(client
+        .hmget::<String>("hash_multi_fields", vec!["binary", "c"])
+        .is_err());
+    assert!(client
+        .hrandfield_withvalues("hash_multi_fields", -6)
+        .is_err());
+}
+
+#[test]
+fn hash_field_expiration() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("hash_field_expiration").unwrap();
+    client
+        .hset_fields("hash_field_expiration", vec![("a", 1), ("b", 2), ("c", 3)])
+        .unwrap();
+
+    let statuses = client
+        .hexpire("hash_field_expiration", 100, None, vec!["a", "missing"])
+        .unwrap();
+    assert_eq!(
+        statuses,
+        vec![HashFieldExpireStatus::Set, HashFieldExpireStatus::NoField]
+    );
+
+    let statuses = client
+        .hpexpire(
+            "hash_field_expiration",
+            200000,
+            Some(ExpireCondition::Nx),
+            vec!["a", "b"],
+        )
+        .unwrap();
+    assert_eq!(
+        statuses,
+        vec![
+            HashFieldExpireStatus::ConditionNotMet,
+            HashFieldExpireStatus::Set
+        ]
+    );
+
+    let ttls = client
+        .httl("hash_field_expiration", vec!["a", "c", "missing"])
+        .unwrap();
+    match ttls[0] {
+        HashFieldTtl::Expires(ttl) => assert!(ttl > 0 && ttl <= 100),
+        _ => panic!("test error"),
+    }
+    assert_eq!(ttls[1], HashFieldTtl::NoExpiration);
+    assert_eq!(ttls[2], HashFieldTtl::NoField);
+
+    match client.hpttl("hash_field_expiration", vec!["b"]).unwrap()[0] {
+        HashFieldTtl::Expires(ttl) => assert!(ttl > 100000),
+        _ => panic!("test error"),
+    }
+
+    let statuses = client
+        .hpersist("hash_field_expiration", vec!["a", "c", "missing"])
+        .unwrap();
+    assert_eq!(
+        statuses,
+        vec![
+            HashFieldPersistStatus::Persisted,
+            HashFieldPersistStatus::NoExpiration,
+            HashFieldPersistStatus::NoField
+        ]
+    );
+
+    let statuses = client
+        .hexpire("hash_field_expiration", 0, None, vec!["c"])
+        .unwrap();
+    assert_eq!(statuses, vec![HashFieldExpireStatus::Deleted]);
+    assert!(!client.hexists("hash_field_expiration", "c").unwrap());
+}
+
+#[test]
+fn hgetdel() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("hgetdel").unwrap();
+    client
+        .hset_fields("hgetdel", vec![("a", 1), ("b", 2)])
+        .unwrap();
+
+    let values = client
+        .hgetdel::<i32>("hgetdel", vec!["a", "missing"])
+        .unwrap();
+    assert_eq!(values, vec![Some(1), None]);
+    assert!(!client.hexists("hgetdel", "a").unwrap());
+    assert!(client.hexists("hgetdel", "b").unwrap());
+}
+
+#[derive(Debug, PartialEq)]
+struct HashUser {
+    name: String,
+    age: u32,
+    admin: bool,
+}
+
+redis_hash!(HashUser { name, age, admin });
+
+#[test]
+fn hash_struct() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("hash_struct").unwrap();
+
+    assert!(client
+        .hgetall_struct_opt::<HashUser>("hash_struct")
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        client
+            .hgetall_struct::<HashUser>("hash_struct")
+            .unwrap_err()
+            .kind(),
+        simple_redis::ErrorKind::Nil
+    );
+
+    let user = HashUser {
+        name: "my name".to_string(),
+        age: 30,
+        admin: true,
+    };
+    assert_eq!(client.hset_struct("hash_struct", &user).unwrap(), 3);
+    assert_eq!(client.hget::<u32>("hash_struct", "age").unwrap(), 30);
+
+    let output = client.hgetall_struct::<HashUser>("hash_struct").unwrap();
+    assert_eq!(output, user);
+
+    client.hset("hash_struct", "age", "old").unwrap();
+    let error = client
+        .hgetall_struct::<HashUser>("hash_struct")
+        .unwrap_err();
+    assert_eq!(error.kind(), simple_redis::ErrorKind::Parse);
+    assert!(error.to_string().contains("age"));
+}
+
+#[derive(Debug, PartialEq)]
+struct HashProfile {
+    nickname: Option<String>,
+    score: Option<u32>,
+}
+
+impl RedisHash for HashProfile {
+    fn to_redis_hash(&self) -> Vec<(String, String)> {
+        let mut fields = vec![];
+        if let Some(ref nickname) = self.nickname {
+            fields.push(("nickname".to_string(), nickname.clone()));
+        }
+        if let Some(score) = self.score {
+            fields.push(("score".to_string(), score.to_string()));
+        }
+
+        fields
+    }
+
+    fn from_redis_hash(fields: &HashMap<String, String>) -> RedisResult<HashProfile> {
+        Ok(HashProfile {
+            nickname: get_optional_field(fields, "nickname")?,
+            score: get_optional_field(fields, "score")?,
+        })
+    }
+}
+
+#[test]
+fn hash_struct_optional_fields() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("hash_struct_optional").unwrap();
+
+    let profile = HashProfile {
+        nickname: Some("nick".to_string()),
+        score: Some(10),
+    };
+    assert_eq!(
+        client
+            .hset_struct("hash_struct_optional", &profile)
+            .unwrap(),
+        2
+    );
+
+    let profile = HashProfile {
+        nickname: None,
+        score: Some(20),
+    };
+    assert_eq!(
+        client
+            .hset_struct("hash_struct_optional", &profile)
+            .unwrap(),
+        1
+    );
+    assert!(!client.hexists("hash_struct_optional", "nickname").unwrap());
+
+    let output = client
+        .hgetall_struct::<HashProfile>("hash_struct_optional")
+        .unwrap();
+    assert_eq!(output, profile);
+
+    let profile = HashProfile {
+        nickname: None,
+        score: None,
+    };
+    assert_eq!(
+        client
+            .hset_struct("hash_struct_optional", &profile)
+            .unwrap(),
+        0
+    );
+    assert!(!client.exists("hash_struct_optional").unwrap());
+    assert!(client
+        .hgetall_struct_opt::<HashProfile>("hash_struct_optional")
+        .unwrap()
+        .is_none());
+}
+
 #[test]
 fn list_multi() {
     let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
@@ -956,3 +1338,743 @@ fn set_all() {
     bool_result = client.sismember("set_all_1", "member100").unwrap();
     assert!(!bool_result);
 }
+
+#[test]
+fn set_algebra() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    for key in &["set_algebra_1", "set_algebra_2", "set_algebra_store"] {
+        client.del(key).unwrap();
+    }
+
+    let mut int_result = client
+        .sadd_members("set_algebra_1", vec!["a", "b", "c", "d"])
+        .unwrap();
+    assert_eq!(int_result, 4);
+    int_result = client
+        .sadd_members("set_algebra_2", vec!["b", "d", "e", "b"])
+        .unwrap();
+    assert_eq!(int_result, 3);
+
+    let mut vec_result = client
+        .sinter(vec!["set_algebra_1", "set_algebra_2"])
+        .unwrap();
+    vec_result.sort();
+    assert_eq!(vec_result, vec!["b", "d"]);
+
+    vec_result = client
+        .sunion(vec!["set_algebra_1", "set_algebra_2"])
+        .unwrap();
+    vec_result.sort();
+    assert_eq!(vec_result, vec!["a", "b", "c", "d", "e"]);
+
+    int_result = client
+        .sintercard(vec!["set_algebra_1", "set_algebra_2"], None)
+        .unwrap();
+    assert_eq!(int_result, 2);
+    int_result = client
+        .sintercard(vec!["set_algebra_1", "set_algebra_2"], Some(1))
+        .unwrap();
+    assert_eq!(int_result, 1);
+
+    int_result = client
+        .sinterstore("set_algebra_store", vec!["set_algebra_1", "set_algebra_2"])
+        .unwrap();
+    assert_eq!(int_result, 2);
+    int_result = client
+        .sunionstore("set_algebra_store", vec!["set_algebra_1", "set_algebra_2"])
+        .unwrap();
+    assert_eq!(int_result, 5);
+    int_result = client
+        .sdiffstore("set_algebra_store", vec!["set_algebra_1", "set_algebra_2"])
+        .unwrap();
+    assert_eq!(int_result, 2);
+
+    let bool_results = client
+        .smismember("set_algebra_store", vec!["a", "b", "c"])
+        .unwrap();
+    assert_eq!(bool_results, vec![true, false, true]);
+
+    assert!(client
+        .smove("set_algebra_store", "set_algebra_2", "a")
+        .unwrap());
+    assert!(!client
+        .smove("set_algebra_store", "set_algebra_2", "a")
+        .unwrap());
+
+    int_result = client
+        .srem_members("set_algebra_2", vec!["a", "b", "missing"])
+        .unwrap();
+    assert_eq!(int_result, 2);
+    assert_eq!(client.srem("set_algebra_2", "d").unwrap(), 1);
+    assert_eq!(client.srem("set_algebra_2", "d").unwrap(), 0);
+
+    let member = client.srandmember::<String>("set_algebra_1").unwrap();
+    assert!(member.is_some());
+    vec_result = client.srandmember_count("set_algebra_1", 2).unwrap();
+    assert_eq!(vec_result.len(), 2);
+    vec_result = client.srandmember_count("set_algebra_1", -10).unwrap();
+    assert_eq!(vec_result.len(), 10);
+
+    vec_result = client.spop_count("set_algebra_1", 3).unwrap();
+    assert_eq!(vec_result.len(), 3);
+    assert!(client.spop::<String>("set_algebra_1").unwrap().is_some());
+    assert!(client.spop::<String>("set_algebra_1").unwrap().is_none());
+    assert!(client
+        .srandmember::<String>("set_algebra_1")
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn sorted_set_all() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("sorted_set_all").unwrap();
+
+    let mut int_result = client.zadd("sorted_set_all", 1.0, "one").unwrap();
+    assert_eq!(int_result, 1);
+    int_result = client
+        .zadd_with_options(
+            "sorted_set_all",
+            vec![(2.0, "two"), (3.0, "three"), (4.0, "four")],
+            ZAddOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(int_result, 3);
+
+    int_result = client.zcard("sorted_set_all").unwrap();
+    assert_eq!(int_result, 4);
+
+    int_result = client
+        .zcount(
+            "sorted_set_all",
+            ScoreBound::Exclusive(1.0),
+            ScoreBound::PosInfinity,
+        )
+        .unwrap();
+    assert_eq!(int_result, 3);
+
+    let mut score = client.zincrby("sorted_set_all", 10.0, "one").unwrap();
+    assert_eq!(score, 11.0);
+
+    assert_eq!(client.zscore("sorted_set_all", "two").unwrap(), Some(2.0));
+    assert_eq!(client.zscore("sorted_set_all", "bad").unwrap(), None);
+    assert_eq!(client.zrank("sorted_set_all", "two").unwrap(), Some(0));
+    assert_eq!(client.zrevrank("sorted_set_all", "two").unwrap(), Some(3));
+    assert_eq!(client.zrank("sorted_set_all", "bad").unwrap(), None);
+
+    let vec_result = client.zrange("sorted_set_all", 0, -1).unwrap();
+    assert_eq!(vec_result, vec!["two", "three", "four", "one"]);
+
+    let mut scored_result = client.zrevrange_withscores("sorted_set_all", 0, 1).unwrap();
+    assert_eq!(
+        scored_result,
+        vec![("one".to_string(), 11.0), ("four".to_string(), 4.0)]
+    );
+
+    scored_result = client
+        .zrangebyscore_withscores(
+            "sorted_set_all",
+            ScoreBound::NegInfinity,
+            ScoreBound::Inclusive(4.0),
+            Some((1, 5)),
+        )
+        .unwrap();
+    assert_eq!(
+        scored_result,
+        vec![("three".to_string(), 3.0), ("four".to_string(), 4.0)]
+    );
+
+    let mut options = ZAddOptions::default();
+    options.xx = true;
+    options.gt = true;
+    let mut incr_result = client
+        .zadd_incr("sorted_set_all", -1.0, "two", options)
+        .unwrap();
+    assert_eq!(incr_result, None);
+    incr_result = client
+        .zadd_incr("sorted_set_all", 1.0, "two", options)
+        .unwrap();
+    assert_eq!(incr_result, Some(3.0));
+
+    int_result = client.zrem("sorted_set_all", vec!["two", "bad"]).unwrap();
+    assert_eq!(int_result, 1);
+
+    scored_result = client.zpopmin("sorted_set_all", 1).unwrap();
+    assert_eq!(scored_result, vec![("three".to_string(), 3.0)]);
+
+    int_result = client
+        .zremrangebyscore(
+            "sorted_set_all",
+            ScoreBound::Inclusive(10.0),
+            ScoreBound::PosInfinity,
+        )
+        .unwrap();
+    assert_eq!(int_result, 1);
+
+    score = client.zscore("sorted_set_all", "four").unwrap().unwrap();
+    assert_eq!(score, 4.0);
+}
+
+#[test]
+fn sorted_set_binary_member() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("sorted_set_binary").unwrap();
+
+    client.zadd("sorted_set_binary", 2.0, "text").unwrap();
+    client
+        .run_binary_command::<i32>(
+            "ZADD",
+            vec![b"sorted_set_binary".to_vec(), b"1".to_vec(), vec![255, 0]],
+        )
+        .unwrap();
+
+    assert!(client
+        .zrange_withscores("sorted_set_binary", 0, -1)
+        .is_err());
+    assert!(client.zpopmin("sorted_set_binary", 2).is_err());
+}
+
+#[test]
+fn sorted_set_lex() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("sorted_set_lex").unwrap();
+
+    client
+        .zadd_with_options(
+            "sorted_set_lex",
+            vec![(0.0, "a"), (0.0, "b"), (0.0, "c"), (0.0, "d")],
+            ZAddOptions::default(),
+        )
+        .unwrap();
+
+    let mut vec_result = client
+        .zrangebylex(
+            "sorted_set_lex",
+            LexBound::Exclusive("a"),
+            LexBound::Inclusive("c"),
+            None,
+        )
+        .unwrap();
+    assert_eq!(vec_result, vec!["b", "c"]);
+
+    vec_result = client
+        .zrevrangebylex("sorted_set_lex", LexBound::Max, LexBound::Min, Some((0, 2)))
+        .unwrap();
+    assert_eq!(vec_result, vec!["d", "c"]);
+
+    let mut int_result = client
+        .zlexcount("sorted_set_lex", LexBound::Min, LexBound::Exclusive("c"))
+        .unwrap();
+    assert_eq!(int_result, 2);
+
+    int_result = client
+        .zremrangebylex("sorted_set_lex", LexBound::Min, LexBound::Inclusive("b"))
+        .unwrap();
+    assert_eq!(int_result, 2);
+
+    int_result = client
+        .zunionstore("sorted_set_lex_union", vec!["sorted_set_lex"])
+        .unwrap();
+    assert_eq!(int_result, 2);
+}
+
+#[test]
+fn scan_all() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    for index in 0..25 {
+        client.set(&format!("scan_all_{}", index), index).unwrap();
+    }
+    client.sadd("scan_all_set", "member").unwrap();
+
+    let mut options = ScanOptions::default();
+    options.pattern = Some("scan_all_*".to_string());
+    options.count = Some(5);
+    options.key_type = Some("string".to_string());
+
+    let mut keys: Vec<String> = client.scan(options).map(|key| key.unwrap()).collect();
+    keys.sort();
+    keys.dedup();
+    assert_eq!(keys.len(), 25);
+    assert!(!keys.contains(&"scan_all_set".to_string()));
+}
+
+#[test]
+fn scan_collections() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("scan_collections_hash").unwrap();
+    client.del("scan_collections_set").unwrap();
+    client.del("scan_collections_zset").unwrap();
+
+    for index in 0..20 {
+        let member = format!("member{}", index);
+        client
+            .hset("scan_collections_hash", &member, index)
+            .unwrap();
+        client.sadd("scan_collections_set", &member).unwrap();
+        client
+            .zadd("scan_collections_zset", index as f64, &member)
+            .unwrap();
+    }
+
+    let mut options = ScanOptions::default();
+    options.count = Some(3);
+
+    let fields: Vec<(String, String)> = client
+        .hscan("scan_collections_hash", options.clone())
+        .map(|entry| entry.unwrap())
+        .collect();
+    assert!(fields.len() >= 20);
+    assert!(fields.contains(&("member7".to_string(), "7".to_string())));
+
+    let members: Vec<String> = client
+        .sscan("scan_collections_set", options.clone())
+        .map(|member| member.unwrap())
+        .collect();
+    assert!(members.len() >= 20);
+
+    options.pattern = Some("member1*".to_string());
+    let scored: Vec<(String, f64)> = client
+        .zscan("scan_collections_zset", options)
+        .map(|member| member.unwrap())
+        .collect();
+    assert!(scored.contains(&("member15".to_string(), 15.0)));
+    assert!(!scored.contains(&("member5".to_string(), 5.0)));
+}
+
+#[test]
+fn binary_values() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    let binary: &[u8] = &[0, 159, 146, 150, 255];
+
+    client.set("binary_values", binary).unwrap();
+    let mut bytes_result = client.get_bytes("binary_values").unwrap().unwrap();
+    assert_eq!(bytes_result, binary);
+
+    client.hset("binary_values_hash", "field", binary).unwrap();
+    bytes_result = client
+        .hget_bytes("binary_values_hash", "field")
+        .unwrap()
+        .unwrap();
+    assert_eq!(bytes_result, binary);
+
+    client.del("binary_values_list").unwrap();
+    client.rpush("binary_values_list", binary.to_vec()).unwrap();
+    client.rpush("binary_values_list", "text").unwrap();
+    bytes_result = client
+        .lindex_bytes("binary_values_list", 0)
+        .unwrap()
+        .unwrap();
+    assert_eq!(bytes_result, binary);
+    bytes_result = client.rpop_bytes("binary_values_list").unwrap().unwrap();
+    assert_eq!(bytes_result, b"text");
+    bytes_result = client.lpop_bytes("binary_values_list").unwrap().unwrap();
+    assert_eq!(bytes_result, binary);
+    assert!(client.lpop_bytes("binary_values_list").unwrap().is_none());
+
+    client.set("binary_values_empty", "").unwrap();
+    assert_eq!(
+        client.get_bytes("binary_values_empty").unwrap(),
+        Some(vec![])
+    );
+
+    let value = client
+        .run_binary_command::<Vec<u8>>("ECHO", vec![binary])
+        .unwrap();
+    assert_eq!(value, binary);
+}
+
+#[test]
+fn optional_values() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("optional_values").unwrap();
+    client.del("optional_values_hash").unwrap();
+    client.del("optional_values_list").unwrap();
+
+    let mut int_result = client.get_opt::<i64>("optional_values").unwrap();
+    assert_eq!(int_result, None);
+    int_result = client.getset_opt("optional_values", 10).unwrap();
+    assert_eq!(int_result, None);
+    int_result = client.get_opt::<i64>("optional_values").unwrap();
+    assert_eq!(int_result, Some(10));
+    int_result = client.getset_opt("optional_values", 20).unwrap();
+    assert_eq!(int_result, Some(10));
+
+    client.set("optional_values", "text").unwrap();
+    assert!(client.get_opt::<i64>("optional_values").is_err());
+
+    let mut string_result = client
+        .hget_opt::<String>("optional_values_hash", "field")
+        .unwrap();
+    assert_eq!(string_result, None);
+    client
+        .hset("optional_values_hash", "field", "value")
+        .unwrap();
+    string_result = client
+        .hget_opt::<String>("optional_values_hash", "field")
+        .unwrap();
+    assert_eq!(string_result, Some("value".to_string()));
+
+    client.rpush("optional_values_list", 1).unwrap();
+    client.rpush("optional_values_list", 2).unwrap();
+    assert_eq!(
+        client.lindex_opt::<i32>("optional_values_list", 5).unwrap(),
+        None
+    );
+    assert_eq!(
+        client.lindex_opt::<i32>("optional_values_list", 1).unwrap(),
+        Some(2)
+    );
+    assert_eq!(
+        client.rpop_opt::<i32>("optional_values_list").unwrap(),
+        Some(2)
+    );
+    assert_eq!(
+        client.lpop_opt::<i32>("optional_values_list").unwrap(),
+        Some(1)
+    );
+    assert_eq!(
+        client.lpop_opt::<i32>("optional_values_list").unwrap(),
+        None
+    );
+    assert_eq!(
+        client.rpop_opt::<i32>("optional_values_list").unwrap(),
+        None
+    );
+}
+
+#[test]
+fn script_all() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("script_counter").unwrap();
+
+    let script = simple_redis::script::Script::new("return redis.call('INCRBY', KEYS[1], ARGV[1])");
+
+    client.script_flush().unwrap();
+    assert_eq!(
+        client.script_exists(vec![script.get_hash()]).unwrap(),
+        vec![false]
+    );
+
+    // first invocation falls back to EVAL and loads the script
+    let value = script
+        .invoke::<i64, _, _>(&mut client, vec!["script_counter"], vec![5])
+        .unwrap();
+    assert_eq!(value, 5);
+    assert_eq!(
+        client.script_exists(vec![script.get_hash()]).unwrap(),
+        vec![true]
+    );
+
+    let value = script
+        .invoke::<i64, _, _>(&mut client, vec!["script_counter"], vec![2])
+        .unwrap();
+    assert_eq!(value, 7);
+
+    let hash = client.script_load("return ARGV[1]").unwrap();
+    let value: String = client
+        .evalsha(&hash, &Vec::<&str>::new(), &["loaded"])
+        .unwrap();
+    assert_eq!(value, "loaded");
+
+    let value: Vec<String> = client
+        .eval("return {KEYS[1], ARGV[1]}", &["key"], &["arg"])
+        .unwrap();
+    assert_eq!(value, vec!["key", "arg"]);
+
+    let error = client
+        .evalsha::<String, &str, &str>("0000000000000000000000000000000000000000", &[], &[])
+        .unwrap_err();
+    assert_eq!(error.kind(), simple_redis::ErrorKind::NoScript);
+}
+
+#[test]
+fn functions_all() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    const LIBRARY: &str = "#!lua name=functions_all
+redis.register_function('functions_all_echo', function(keys, args) return args[1] end)
+redis.register_function{
+  function_name='functions_all_get',
+  callback=function(keys, args) return redis.call('GET', keys[1]) end,
+  flags={ 'no-writes' }
+}";
+
+    client.function_flush().unwrap();
+
+    assert!(client.function_load_if_changed(LIBRARY).unwrap());
+    assert!(!client.function_load_if_changed(LIBRARY).unwrap());
+    assert!(client.function_load(LIBRARY, false).is_err());
+    assert_eq!(
+        client.function_load(LIBRARY, true).unwrap(),
+        "functions_all"
+    );
+
+    let value: String = client
+        .fcall("functions_all_echo", &Vec::<&str>::new(), &["echo"])
+        .unwrap();
+    assert_eq!(value, "echo");
+
+    client.set("functions_all_key", "value").unwrap();
+    let value: String = client
+        .fcall_ro(
+            "functions_all_get",
+            &["functions_all_key"],
+            &Vec::<&str>::new(),
+        )
+        .unwrap();
+    assert_eq!(value, "value");
+
+    let libraries = client.function_list(Some("functions_all"), true).unwrap();
+    assert_eq!(libraries.len(), 1);
+    assert_eq!(libraries[0].name, "functions_all");
+    assert_eq!(libraries[0].functions.len(), 2);
+    assert_eq!(libraries[0].code, Some(LIBRARY.to_string()));
+
+    let payload = client.function_dump().unwrap();
+    client.function_delete("functions_all").unwrap();
+    assert!(client.function_list(None, false).unwrap().is_empty());
+
+    client
+        .function_restore(
+            &payload,
+            Some(simple_redis::types::FunctionRestorePolicy::Replace),
+        )
+        .unwrap();
+    assert_eq!(client.function_list(None, false).unwrap().len(), 1);
+}
+
+#[test]
+fn stream_all() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("stream_all").unwrap();
+
+    let first = client
+        .xadd("stream_all", "*", &[("field", 1), ("other", 2)], None)
+        .unwrap();
+    let second = client
+        .xadd("stream_all", "*", &[("field", 3)], None)
+        .unwrap();
+    client
+        .xadd(
+            "stream_all",
+            "*",
+            &[("field", "last")],
+            Some(simple_redis::types::StreamTrim::MaxLen(2)),
+        )
+        .unwrap();
+    assert_eq!(client.xlen("stream_all").unwrap(), 2);
+
+    let entries = client.xrange("stream_all", "-", "+", None).unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].id, second);
+    assert_eq!(
+        entries[0].fields,
+        vec![("field".to_string(), "3".to_string())]
+    );
+    assert!(entries.iter().all(|entry| entry.id != first));
+
+    let entries = client.xrevrange("stream_all", "+", "-", Some(1)).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].fields[0].1, "last");
+
+    let streams = client
+        .xread(
+            vec![("stream_all", "0")],
+            simple_redis::types::StreamReadOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(streams.len(), 1);
+    assert_eq!(streams[0].0, "stream_all");
+    assert_eq!(streams[0].1.len(), 2);
+
+    let mut options = simple_redis::types::StreamReadOptions::default();
+    options.block = Some(time::Duration::from_millis(10));
+    let streams = client.xread(vec![("stream_all", "$")], options).unwrap();
+    assert!(streams.is_empty());
+
+    assert_eq!(client.xdel("stream_all", vec![&second]).unwrap(), 1);
+    assert_eq!(
+        client
+            .xtrim("stream_all", simple_redis::types::StreamTrim::MaxLen(0))
+            .unwrap(),
+        1
+    );
+    assert_eq!(client.xlen("stream_all").unwrap(), 0);
+}
+
+#[test]
+fn stream_consumer_group() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("stream_group").unwrap();
+    client
+        .xgroup_create("stream_group", "workers", "$", true)
+        .unwrap();
+
+    let first = client
+        .xadd("stream_group", "*", &[("job", "1")], None)
+        .unwrap();
+    let second = client
+        .xadd("stream_group", "*", &[("job", "2")], None)
+        .unwrap();
+
+    let streams = client
+        .xreadgroup(
+            "workers",
+            "crashed",
+            vec![("stream_group", ">")],
+            simple_redis::types::StreamReadOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(streams[0].1.len(), 2);
+
+    let summary = client.xpending("stream_group", "workers").unwrap();
+    assert_eq!(summary.count, 2);
+    assert_eq!(summary.min_id, Some(first.clone()));
+    assert_eq!(summary.consumers, vec![("crashed".to_string(), 2)]);
+
+    let pending = client
+        .xpending_range("stream_group", "workers", "-", "+", 10, Some("crashed"))
+        .unwrap();
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].deliveries, 1);
+
+    let claimed = client
+        .xclaim("stream_group", "workers", "worker", 0, vec![&first])
+        .unwrap();
+    assert_eq!(claimed.len(), 1);
+    assert_eq!(claimed[0].id, first);
+
+    let result = client
+        .xautoclaim("stream_group", "workers", "worker", 0, "0", None)
+        .unwrap();
+    assert_eq!(result.next_id, "0-0");
+    assert_eq!(result.entries.len(), 2);
+
+    let third = client
+        .xadd("stream_group", "*", &[("job", "3")], None)
+        .unwrap();
+
+    let mut options = simple_redis::types::StreamReadOptions::default();
+    options.block = Some(time::Duration::from_millis(10));
+    let mut reader = client.stream_group_reader("stream_group", "workers", "worker", options);
+
+    // pending entries are returned first
+    let entries = reader.read().unwrap();
+    let ids: Vec<&str> = entries.iter().map(|entry| entry.id.as_str()).collect();
+    assert_eq!(ids, vec![first.as_str(), second.as_str()]);
+    assert_eq!(reader.ack(ids).unwrap(), 2);
+
+    let entries = reader.read().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].id, third);
+    assert_eq!(reader.ack(vec![&third]).unwrap(), 1);
+
+    assert!(reader.read().unwrap().is_empty());
+
+    assert!(client.xgroup_destroy("stream_group", "workers").unwrap());
+}
+
+#[test]
+fn blocking_list_commands() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("blocking_list1").unwrap();
+    client.del("blocking_list2").unwrap();
+
+    let timeout = time::Duration::from_millis(100);
+    let value = client
+        .blpop::<String>(vec!["blocking_list1", "blocking_list2"], timeout)
+        .unwrap();
+    assert!(value.is_none());
+    assert!(client.is_connection_open());
+
+    client.rpush("blocking_list2", 1).unwrap();
+    client.rpush("blocking_list2", 2).unwrap();
+    client.rpush("blocking_list2", 3).unwrap();
+
+    let value = client
+        .blpop::<i32>(vec!["blocking_list1", "blocking_list2"], timeout)
+        .unwrap();
+    assert_eq!(value, Some(("blocking_list2".to_string(), 1)));
+
+    let value = client
+        .brpop::<i32>(vec!["blocking_list1", "blocking_list2"], timeout)
+        .unwrap();
+    assert_eq!(value, Some(("blocking_list2".to_string(), 3)));
+
+    let value = client
+        .blmove::<i32>(
+            "blocking_list2",
+            "blocking_list1",
+            ListDirection::Left,
+            ListDirection::Right,
+            timeout,
+        )
+        .unwrap();
+    assert_eq!(value, Some(2));
+
+    let value = client
+        .brpoplpush::<i32>("blocking_list1", "blocking_list2", timeout)
+        .unwrap();
+    assert_eq!(value, Some(2));
+
+    let value = client
+        .brpoplpush::<i32>("blocking_list1", "blocking_list2", timeout)
+        .unwrap();
+    assert!(value.is_none());
+}
+
+#[test]
+fn blocking_pop_longer_than_read_timeout() {
+    let mut client = simple_redis::ClientBuilder::new("redis://127.0.0.1:6379/")
+        .read_timeout(time::Duration::from_millis(50))
+        .build()
+        .unwrap();
+
+    client.del("blocking_pop_timeout").unwrap();
+
+    let value = client
+        .blpop::<String>(
+            vec!["blocking_pop_timeout"],
+            time::Duration::from_millis(500),
+        )
+        .unwrap();
+    assert!(value.is_none());
+    assert!(client.is_connection_open());
+}
+
+#[test]
+fn blocking_sorted_set_commands() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("blocking_zset").unwrap();
+
+    let timeout = time::Duration::from_millis(100);
+    assert!(client
+        .bzpopmin(vec!["blocking_zset"], timeout)
+        .unwrap()
+        .is_none());
+
+    client.zadd("blocking_zset", 1.0, "first").unwrap();
+    client.zadd("blocking_zset", 2.0, "second").unwrap();
+
+    assert_eq!(
+        client.bzpopmin(vec!["blocking_zset"], timeout).unwrap(),
+        Some(("blocking_zset".to_string(), "first".to_string(), 1.0))
+    );
+    assert_eq!(
+        client.bzpopmax(vec!["blocking_zset"], timeout).unwrap(),
+        Some(("blocking_zset".to_string(), "second".to_string(), 2.0))
+    );
+}
@@ -0,0 +1,62 @@
+extern crate simple_redis;
+
+#[test]
+fn execute_typed() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    let (value, length, exists): (String, i32, bool) = client
+        .pipeline()
+        .set("pipeline_execute_typed", "my_value")
+        .get("pipeline_execute_typed")
+        .strlen("pipeline_execute_typed")
+        .exists("pipeline_execute_typed")
+        .cmd("DEL", vec!["pipeline_execute_typed"])
+        .execute::<((), String, i32, bool, ())>()
+        .map(|(_, value, length, exists, _)| (value, length, exists))
+        .unwrap();
+
+    assert_eq!(value, "my_value");
+    assert_eq!(length, 8);
+    assert!(exists);
+}
+
+#[test]
+fn execute_vec() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    let values: Vec<String> = client
+        .pipeline()
+        .echo("1")
+        .echo("2")
+        .echo("3")
+        .execute()
+        .unwrap();
+
+    assert_eq!(values, vec!["1", "2", "3"]);
+}
+
+#[test]
+fn execute_all_errors() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.del("pipeline_execute_all_errors").unwrap();
+
+    let results = client
+        .pipeline()
+        .lpush("pipeline_execute_all_errors", "item")
+        .incr("pipeline_execute_all_errors")
+        .llen("pipeline_execute_all_errors")
+        .execute_all();
+
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    assert!(results[2].is_ok());
+
+    let result = client
+        .pipeline()
+        .lpush("pipeline_execute_all_errors", "item")
+        .incr("pipeline_execute_all_errors")
+        .execute::<Vec<i64>>();
+    assert!(result.is_err());
+}
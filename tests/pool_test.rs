@@ -0,0 +1,53 @@
+extern crate simple_redis;
+use simple_redis::pool::Options;
+use std::thread;
+
+#[test]
+fn get_reuse_client() {
+    let pool = simple_redis::pool::create("redis://127.0.0.1:6379/", Options::default()).unwrap();
+
+    {
+        let mut client = pool.get().unwrap();
+        assert!(!client.is_connection_open());
+
+        match client.echo("testing") {
+            Ok(value) => assert_eq!(value, "testing"),
+            _ => panic!("test error"),
+        }
+
+        assert!(client.is_connection_open());
+    }
+
+    assert_eq!(pool.size(), 1);
+    assert_eq!(pool.idle_size(), 1);
+
+    let client = pool.get().unwrap();
+    assert!(client.is_connection_open());
+    assert_eq!(pool.size(), 1);
+}
+
+#[test]
+fn get_concurrent() {
+    let mut options = Options::default();
+    options.max_size = 4;
+    let pool = simple_redis::pool::create("redis://127.0.0.1:6379/", options).unwrap();
+
+    let mut handles = vec![];
+    for index in 0..8 {
+        let thread_pool = pool.clone();
+        handles.push(thread::spawn(move || {
+            let key = format!("pool_get_concurrent_{}", index);
+
+            let mut client = thread_pool.get().unwrap();
+            client.set(&key, index).unwrap();
+            let value = client.get::<i32>(&key).unwrap();
+            assert_eq!(value, index);
+        }));
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert!(pool.size() <= 4);
+}
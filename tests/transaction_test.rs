@@ -0,0 +1,66 @@
+extern crate simple_redis;
+
+#[test]
+fn transaction_simple() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    let (_, value): ((), i64) = client
+        .transaction(vec![], |transaction| {
+            transaction
+                .set("transaction_simple", 5)
+                .incr("transaction_simple");
+
+            Ok(())
+        })
+        .unwrap();
+
+    assert_eq!(value, 6);
+}
+
+#[test]
+fn transaction_retry_on_watched_change() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+    let mut other_client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.set("transaction_retry", 1).unwrap();
+
+    let mut attempts = 0;
+    let (value,): (i64,) = client
+        .transaction(vec!["transaction_retry"], |transaction| {
+            attempts = attempts + 1;
+
+            let current = transaction.run_command::<i64>("GET", vec!["transaction_retry"])?;
+
+            if attempts == 1 {
+                // modify the watched key so the first attempt is discarded
+                other_client.set("transaction_retry", 10).unwrap();
+            }
+
+            transaction.incrby("transaction_retry", current);
+
+            Ok(())
+        })
+        .unwrap();
+
+    assert_eq!(attempts, 2);
+    assert_eq!(value, 20);
+}
+
+#[test]
+fn transaction_exec_error() {
+    let mut client = simple_redis::create("redis://127.0.0.1:6379/").unwrap();
+
+    client.set("transaction_exec_error", "text").unwrap();
+
+    let result = client.transaction::<(i64,), _>(vec![], |transaction| {
+        transaction.incr("transaction_exec_error");
+
+        Ok(())
+    });
+    assert!(result.is_err());
+
+    match client.echo("testing") {
+        Ok(value) => assert_eq!(value, "testing"),
+        _ => panic!("test error"),
+    }
+}
@@ -0,0 +1,78 @@
+#![cfg(unix)]
+
+extern crate simple_redis;
+use std::path::Path;
+use std::process::{Child, Command};
+use std::{fs, thread, time};
+
+struct RedisServer {
+    process: Child,
+    socket: String,
+}
+
+impl Drop for RedisServer {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+        let _ = fs::remove_file(&self.socket);
+    }
+}
+
+fn start_server(name: &str) -> RedisServer {
+    let socket = format!("/tmp/simple_redis_{}_{}.sock", name, std::process::id());
+    let _ = fs::remove_file(&socket);
+
+    let process = Command::new("redis-server")
+        .args(&["--port", "0", "--save", "", "--unixsocket", &socket])
+        .spawn()
+        .unwrap();
+    let server = RedisServer { process, socket };
+
+    for _ in 0..100 {
+        if Path::new(&server.socket).exists() {
+            return server;
+        }
+
+        thread::sleep(time::Duration::from_millis(50));
+    }
+
+    panic!("redis server did not start");
+}
+
+#[test]
+fn unix_socket_commands() {
+    let server = start_server("commands");
+
+    let mut client = simple_redis::create(&format!("unix://{}", server.socket)).unwrap();
+
+    client.set("unix_socket_key", "value").unwrap();
+    let value = client.get_string("unix_socket_key").unwrap();
+    assert_eq!(value, "value");
+
+    let mut client = simple_redis::create(&format!("redis+unix://{}?db=1", server.socket)).unwrap();
+    let value = client.get_opt::<String>("unix_socket_key").unwrap();
+    assert!(value.is_none());
+}
+
+#[test]
+fn unix_socket_pubsub() {
+    let server = start_server("pubsub");
+    let connection_string = format!("unix://{}", server.socket);
+
+    let mut subscriber = simple_redis::create(&connection_string).unwrap();
+    subscriber.subscribe("unix_socket_channel").unwrap();
+
+    let publish_connection_string = connection_string.clone();
+    thread::spawn(move || {
+        thread::sleep(time::Duration::from_millis(200));
+
+        let mut publisher = simple_redis::create(&publish_connection_string).unwrap();
+        publisher
+            .publish("unix_socket_channel", "my message")
+            .unwrap();
+    });
+
+    let message = subscriber.get_message(5000).unwrap();
+    let payload: String = message.get_payload().unwrap();
+    assert_eq!(payload, "my message");
+}